@@ -158,6 +158,9 @@ async def websocket_consumer(ws_url):
             async for message in websocket:
                 try:
                     data = json.loads(message)
+                    # Seating events only describe the table layout
+                    if data.get('event') == 'seating':
+                        continue
                     data_queue.put(data)
                 except json.JSONDecodeError as e:
                     print(f"Warning: Invalid JSON received: {e}", file=sys.stderr)
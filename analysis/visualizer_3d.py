@@ -297,7 +297,11 @@ async def ws_listen():
             async with ws_client.connect(WS_URL, ping_interval=None) as websocket:
                 print("Connected to Braid Engine.")
                 async for message in websocket:
-                    data_queue.put(json.loads(message))
+                    data = json.loads(message)
+                    # Seating events only describe the table layout
+                    if data.get('event') == 'seating':
+                        continue
+                    data_queue.put(data)
         except Exception as e:
             print(f"Connection error: {e}. Retrying in 2s...")
             await asyncio.sleep(2)
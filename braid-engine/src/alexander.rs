@@ -0,0 +1,226 @@
+use crate::polynomial::LaurentPoly;
+use crate::types::{BraidWord, Generator};
+
+/// Square matrix over `Z[t, t^-1]`, stored row-major.
+type PolyMatrix = Vec<Vec<LaurentPoly>>;
+
+/// Computes the Alexander polynomial of the closure of a braid.
+///
+/// Uses the reduced Burau representation ψ (dimension n-1 on n strands) and
+/// the identity `det(I - ψ(β)) = (1 + t + ... + t^(n-1)) · Δ(t)`.
+///
+/// Normalization: Δ is only defined up to multiplication by `±t^k`, so the
+/// result is shifted to have lowest power `t^0` with a positive constant term
+/// (the trefoil gives `1 - t + t^2`, the unknot `1`).
+///
+/// Only the block of strands the word actually touches is used. Untouched
+/// strands would close into split unknots, which force Δ = 0 for the whole
+/// link and hide all information about the braided part.
+///
+/// # Arguments
+/// * `word` - The braid word
+/// * `strands` - Number of strands the word lives on
+pub fn alexander_polynomial(word: &BraidWord, strands: usize) -> LaurentPoly {
+    let Some((offset, active)) = word.active_span(strands) else {
+        return LaurentPoly::one();
+    };
+    let size = active - 1;
+
+    // ψ(β) = ψ(g_1) · ψ(g_2) · ... (same order as the numeric Burau matrix)
+    let mut psi = identity(size);
+    for gen in word.iter() {
+        let k = gen.index();
+        if k == 0 || k >= strands {
+            continue;
+        }
+        psi = multiply(&psi, &reduced_burau(gen, k - offset, size));
+    }
+
+    // I - ψ(β)
+    let mut system = psi;
+    for (i, row) in system.iter_mut().enumerate() {
+        for (j, entry) in row.iter_mut().enumerate() {
+            let diagonal = if i == j {
+                LaurentPoly::one()
+            } else {
+                LaurentPoly::zero()
+            };
+            *entry = &diagonal - entry;
+        }
+    }
+
+    let numerator = determinant(system);
+    let mut divisor = LaurentPoly::zero();
+    for power in 0..active as i32 {
+        divisor.add_term(1, power);
+    }
+
+    let delta = numerator
+        .div_exact(&divisor)
+        .expect("det(I - ψ) is always divisible by 1 + t + ... + t^(n-1)");
+    normalize(delta)
+}
+
+/// Shifts to lowest power `t^0` and makes the constant term positive.
+fn normalize(poly: LaurentPoly) -> LaurentPoly {
+    let Some(lowest) = poly.min_exponent() else {
+        return poly;
+    };
+    let shifted = poly.shift(-lowest);
+    if shifted.coeff(0) < 0 {
+        -&shifted
+    } else {
+        shifted
+    }
+}
+
+/// The `size x size` identity matrix.
+fn identity(size: usize) -> PolyMatrix {
+    (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| {
+                    if i == j {
+                        LaurentPoly::one()
+                    } else {
+                        LaurentPoly::zero()
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Reduced Burau matrix of `gen` acting at (1-based) index `k`.
+///
+/// σ_k is the identity except row k, which reads `[t, -t, 1]` across columns
+/// k-1, k, k+1; σ_k^-1 has row k equal to `[1, -t^-1, t^-1]`. Columns that
+/// fall outside the matrix (k = 1 or k = n-1) are dropped.
+fn reduced_burau(gen: &Generator, k: usize, size: usize) -> PolyMatrix {
+    let mut matrix = identity(size);
+    let row = k - 1;
+    let (left, middle, right) = if gen.is_overcrossing() {
+        (
+            LaurentPoly::monomial(1, 1),
+            LaurentPoly::monomial(-1, 1),
+            LaurentPoly::one(),
+        )
+    } else {
+        (
+            LaurentPoly::one(),
+            LaurentPoly::monomial(-1, -1),
+            LaurentPoly::monomial(1, -1),
+        )
+    };
+
+    if row > 0 {
+        matrix[row][row - 1] = left;
+    }
+    matrix[row][row] = middle;
+    if row + 1 < size {
+        matrix[row][row + 1] = right;
+    }
+    matrix
+}
+
+/// Multiplies two square polynomial matrices.
+fn multiply(a: &PolyMatrix, b: &PolyMatrix) -> PolyMatrix {
+    let size = a.len();
+    (0..size)
+        .map(|i| {
+            (0..size)
+                .map(|j| {
+                    let mut sum = LaurentPoly::zero();
+                    for (k, b_row) in b.iter().enumerate() {
+                        if !a[i][k].is_zero() && !b_row[j].is_zero() {
+                            sum = &sum + &(&a[i][k] * &b_row[j]);
+                        }
+                    }
+                    sum
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Determinant by fraction-free (Bareiss) elimination, which keeps every
+/// intermediate entry in `Z[t, t^-1]`.
+fn determinant(mut m: PolyMatrix) -> LaurentPoly {
+    let size = m.len();
+    if size == 0 {
+        return LaurentPoly::one();
+    }
+
+    let mut sign = 1;
+    let mut previous_pivot = LaurentPoly::one();
+    for k in 0..size - 1 {
+        // Find a non-zero pivot, swapping rows if needed
+        if m[k][k].is_zero() {
+            match (k + 1..size).find(|&r| !m[r][k].is_zero()) {
+                Some(r) => {
+                    m.swap(k, r);
+                    sign = -sign;
+                }
+                None => return LaurentPoly::zero(),
+            }
+        }
+
+        for i in k + 1..size {
+            for j in k + 1..size {
+                let cross = &(&m[i][j] * &m[k][k]) - &(&m[i][k] * &m[k][j]);
+                m[i][j] = cross
+                    .div_exact(&previous_pivot)
+                    .expect("Bareiss elimination divides exactly");
+            }
+        }
+        previous_pivot = m[k][k].clone();
+    }
+
+    let det = m[size - 1][size - 1].clone();
+    if sign < 0 {
+        -&det
+    } else {
+        det
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alexander(word: &str, strands: usize) -> String {
+        alexander_polynomial(&word.parse().unwrap(), strands).to_string()
+    }
+
+    #[test]
+    fn test_unknot() {
+        assert_eq!(alexander("", 4), "1");
+        assert_eq!(alexander("s1", 2), "1");
+        assert_eq!(alexander("s1 s2'", 3), "1");
+    }
+
+    #[test]
+    fn test_trefoil() {
+        assert_eq!(alexander("s1 s1 s1", 2), "1 - t + t^2");
+        // Mirror image has the same Alexander polynomial
+        assert_eq!(alexander("s1' s1' s1'", 2), "1 - t + t^2");
+        // Same braid on the 3-strand presentation σ1 σ2 σ1 σ2
+        assert_eq!(alexander("s1 s2 s1 s2", 3), "1 - t + t^2");
+    }
+
+    #[test]
+    fn test_figure_eight() {
+        assert_eq!(alexander("s1 s2' s1 s2'", 3), "1 - 3t + t^2");
+    }
+
+    #[test]
+    fn test_hopf_link() {
+        // Two-component link: Δ = 1 - t (up to units)
+        assert_eq!(alexander("s1 s1", 2), "1 - t");
+    }
+
+    #[test]
+    fn test_untouched_strands_are_ignored() {
+        assert_eq!(alexander("s4 s4 s4", 9), "1 - t + t^2");
+    }
+}
@@ -1,15 +1,27 @@
-use crate::types::Generator;
+use crate::normalization::normalize;
+use crate::record::HandSummary;
+use crate::types::{BraidWord, Generator, InvalidGenerator, Seat, Street};
 use nalgebra::DMatrix;
 use num_complex::Complex;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use serde::Serialize;
 
 /// Player-specific metrics for topological profiling.
-#[derive(Debug, Clone, Serialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Default)]
 pub struct PlayerMetrics {
     pub name: String,     // e.g. "Alex202"
     pub writhe: i32,      // Net cumulative crossings initiated by this player
     pub complexity: f64,  // Personal entanglement (Diagonal of Burau Matrix)
+    pub activity: f64,    // 1.0 right after acting, decays toward 0 while idle
+}
+
+/// Crossings a single strand takes part in, see `FingerprintState::strand_metrics`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Default)]
+pub struct StrandMetrics {
+    /// Signed sum of the crossings involving the strand
+    pub writhe: i32,
+    /// Number of crossings involving the strand
+    pub crossing_count: usize,
 }
 
 /// Trait for incremental updates to fingerprint state.
@@ -19,6 +31,93 @@ pub trait IncrementalUpdate {
     fn update(&mut self, gen: &Generator);
 }
 
+/// A user-defined scalar invariant that can be plugged into `FingerprintState`.
+///
+/// Registered invariants are fed every generator the fingerprint sees and are
+/// reset along with it. Implementors only need `Clone` (see `InvariantClone`).
+pub trait Invariant: InvariantClone + std::fmt::Debug + Send + Sync {
+    /// Key under which the value is reported in the metrics map.
+    fn name(&self) -> &str;
+    /// Updates the invariant with a new generator.
+    fn update(&mut self, gen: &Generator);
+    /// Current value of the invariant.
+    fn value(&self) -> f64;
+    /// Returns the invariant to its initial value (start of a new hand).
+    fn reset(&mut self);
+    /// Reverses a previous `update(gen)`, returning false if the invariant
+    /// can't step back. The default can't, so `FingerprintState::undo`
+    /// refuses while such an invariant is registered.
+    fn undo(&mut self, _gen: &Generator) -> bool {
+        false
+    }
+}
+
+/// Error returned by `FingerprintState::undo` when a registered `Invariant`
+/// can't step back. The state is unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoUnsupported {
+    /// Name of the invariant that refused
+    pub invariant: String,
+}
+
+impl std::fmt::Display for UndoUnsupported {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invariant '{}' can't undo a generator", self.invariant)
+    }
+}
+
+impl std::error::Error for UndoUnsupported {}
+
+/// Object-safe cloning for boxed invariants, implemented for every `Clone` invariant.
+pub trait InvariantClone {
+    fn clone_box(&self) -> Box<dyn Invariant>;
+}
+
+impl<T: Invariant + Clone + 'static> InvariantClone for T {
+    fn clone_box(&self) -> Box<dyn Invariant> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Invariant> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Reference `Invariant`: the writhe (sum of signed crossings).
+#[derive(Debug, Clone, Default)]
+pub struct WritheInvariant {
+    writhe: i32,
+}
+
+impl Invariant for WritheInvariant {
+    fn name(&self) -> &str {
+        "writhe"
+    }
+
+    fn update(&mut self, gen: &Generator) {
+        if gen.is_overcrossing() {
+            self.writhe += 1;
+        } else {
+            self.writhe -= 1;
+        }
+    }
+
+    fn value(&self) -> f64 {
+        self.writhe as f64
+    }
+
+    fn reset(&mut self) {
+        self.writhe = 0;
+    }
+
+    fn undo(&mut self, gen: &Generator) -> bool {
+        self.writhe -= if gen.is_overcrossing() { 1 } else { -1 };
+        true
+    }
+}
+
 /// Fingerprint state for braid invariants.
 /// Implements a tiered strategy:
 /// - Tier 1: Instant (integer arithmetic only)
@@ -29,10 +128,39 @@ pub struct FingerprintState {
     // Tier 1: Instant (Integer arithmetic only)
     pub writhe: i32,
     pub crossing_count: usize,
+    /// Largest |writhe| reached since the last reset
+    peak_abs_writhe: i32,
+    /// Times the writhe changed direction since the last reset (churn)
+    writhe_reversals: usize,
+    /// Actions between distinct seats that wrapped onto the same strand and
+    /// so braided nothing. Not cleared by `reset`: a non-zero count means the
+    /// dimension is too small for the table.
+    pub collisions: usize,
+    /// Generators whose index was outside `[1, dimension-1]`: `update`
+    /// counts them but they leave the Burau matrices untouched. Not cleared
+    /// by `reset`.
+    pub dropped_generators: usize,
+    /// Generators applied per index over the whole session (`[k - 1]` counts
+    /// σ_k and σ_k⁻¹), showing which positions see the most action. Not
+    /// cleared by `reset`.
+    pub generator_histogram: Vec<usize>,
+    /// Street the next generators belong to (advanced by street boundaries)
+    street: Street,
+    /// Writhe and crossing count per street of the current hand
+    street_metrics: HashMap<Street, (i32, usize)>,
+    /// Street each generator of the current hand was applied on and whether
+    /// it was an overcrossing, so `undo` takes it off the street that counted
+    /// it and knows whether it reversed the writhe
+    applied: Vec<(Street, bool)>,
+    /// Signed crossings between each pair of adjacent strand positions (N x N,
+    /// symmetric): σ_k adds 1 to (k-1, k) and (k, k-1), σ_k⁻¹ subtracts 1
+    linking_matrix: DMatrix<i32>,
 
     // Tier 2: Fast (Linear Algebra / Burau Representation)
     /// Burau matrix representation (N x N, where N is the number of strands/seats)
     pub burau_matrix: DMatrix<Complex<f64>>,
+    /// Reduced Burau matrix ((N-1) x (N-1)), without the trivial eigenvalue 1
+    pub reduced_burau: DMatrix<Complex<f64>>,
     /// Complex parameter t for Burau representation (e^(i * 1.0) - "Golden Phase")
     pub t_param: Complex<f64>,
     /// Dimension of the braid (number of seats)
@@ -40,11 +168,55 @@ pub struct FingerprintState {
 
     // Tier 3: Slow (Jones Polynomial)
     // Only computed on demand, not incrementally updated
+    /// Generators applied since the last reset (needed for Tier 3). With a
+    /// normalization window set this is an equivalent, freely reduced word
+    /// rather than the full history.
+    pub word: BraidWord,
+    /// Freely reduce `word` once it grows past this many generators
+    normalization_window: Option<usize>,
+    /// Length at which `word` is next reduced (doubles if reduction can't
+    /// get it back under the window, so irreducible words stay linear)
+    next_normalization: usize,
+    /// Last result of `compute_jones`, cleared whenever a generator is applied
     pub jones_poly_cache: Option<String>,
 
     // Player-Specific Profiling
     /// Per-seat metrics for individual player tracking
     pub player_stats: HashMap<usize, PlayerMetrics>,
+    /// Factor applied to every seat's activity on each global step (0.0..=1.0)
+    pub activity_decay: f64,
+    /// Seats whose player stood up. Kept across resets; their actions aren't
+    /// braided and they are left out of `activity_ranking`
+    inactive_seats: HashSet<usize>,
+    /// Players whose seats get `player_stats` (everyone if empty)
+    player_filter: Vec<String>,
+
+    // Plugins
+    /// Custom invariants updated alongside the built-in tiers
+    invariants: Vec<Box<dyn Invariant>>,
+}
+
+/// Copy of a `FingerprintState`'s braid progress, see
+/// `FingerprintState::checkpoint`.
+///
+/// Settings (dimension, t, filters, normalization window), seat activity and
+/// registered `Invariant` plugins are not part of it.
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    writhe: i32,
+    crossing_count: usize,
+    peak_abs_writhe: i32,
+    writhe_reversals: usize,
+    generator_histogram: Vec<usize>,
+    street: Street,
+    street_metrics: HashMap<Street, (i32, usize)>,
+    applied: Vec<(Street, bool)>,
+    linking_matrix: DMatrix<i32>,
+    burau_matrix: DMatrix<Complex<f64>>,
+    reduced_burau: DMatrix<Complex<f64>>,
+    word: BraidWord,
+    next_normalization: usize,
+    player_stats: HashMap<usize, PlayerMetrics>,
 }
 
 impl FingerprintState {
@@ -55,18 +227,49 @@ impl FingerprintState {
     pub fn new(dimension: usize) -> Self {
         // Golden Phase: t = e^(i * 1.0) = cos(1.0) + i*sin(1.0)
         let t_param = Complex::new(1.0_f64.cos(), 1.0_f64.sin());
-        
-        // Initialize Burau matrix as identity
+        Self::with_t_param(dimension, t_param)
+    }
+
+    /// Creates a new empty fingerprint state evaluating Burau at an arbitrary t.
+    ///
+    /// Useful for sweeping t across the unit circle to study how sensitive the
+    /// trace invariant is to the evaluation point. t must be non-zero (σ_k^{-1}
+    /// divides by t).
+    ///
+    /// # Arguments
+    /// * `dimension` - Number of strands/seats
+    /// * `t_param` - Complex evaluation point for the Burau representation
+    pub fn with_t_param(dimension: usize, t_param: Complex<f64>) -> Self {
+        // Initialize Burau matrices as identity
         let burau_matrix = DMatrix::identity(dimension, dimension);
+        let reduced_dimension = dimension.saturating_sub(1);
+        let reduced_burau = DMatrix::identity(reduced_dimension, reduced_dimension);
 
         FingerprintState {
             writhe: 0,
             crossing_count: 0,
+            peak_abs_writhe: 0,
+            writhe_reversals: 0,
+            collisions: 0,
+            dropped_generators: 0,
+            generator_histogram: vec![0; reduced_dimension],
+            street: Street::Preflop,
+            street_metrics: HashMap::new(),
+            applied: Vec::new(),
+            linking_matrix: DMatrix::zeros(dimension, dimension),
             burau_matrix,
+            reduced_burau,
             t_param,
             dimension,
+            word: BraidWord::new(),
+            normalization_window: None,
+            next_normalization: 0,
             jones_poly_cache: None,
             player_stats: HashMap::new(),
+            activity_decay: 0.8,
+            inactive_seats: HashSet::new(),
+            player_filter: Vec::new(),
+            invariants: Vec::new(),
         }
     }
 
@@ -80,8 +283,113 @@ impl FingerprintState {
     pub fn reset(&mut self) {
         self.writhe = 0;
         self.crossing_count = 0;
+        self.peak_abs_writhe = 0;
+        self.writhe_reversals = 0;
         self.burau_matrix = DMatrix::identity(self.dimension, self.dimension);
+        let reduced_dimension = self.dimension.saturating_sub(1);
+        self.reduced_burau = DMatrix::identity(reduced_dimension, reduced_dimension);
+        self.word = BraidWord::new();
+        self.next_normalization = self.normalization_window.unwrap_or(0);
+        self.jones_poly_cache = None;
         self.player_stats.clear();
+        self.street = Street::Preflop;
+        self.street_metrics.clear();
+        self.applied.clear();
+        self.linking_matrix.fill(0);
+        for invariant in &mut self.invariants {
+            invariant.reset();
+        }
+    }
+
+    /// Largest |writhe| reached since the last reset. Together with the
+    /// final writhe it tells a hand that swung back and forth from one that
+    /// crept one way.
+    pub fn peak_abs_writhe(&self) -> i32 {
+        self.peak_abs_writhe
+    }
+
+    /// Number of times the writhe turned around since the last reset: a
+    /// generator whose sign differs from the one before it. A hand that
+    /// oscillated scores high, one that crept monotonically scores 0, however
+    /// many crossings either had.
+    pub fn writhe_reversals(&self) -> usize {
+        self.writhe_reversals
+    }
+
+    /// Signed crossings between each pair of strand positions this hand,
+    /// i.e. how entangled two seats' action lines are. Symmetric, with a zero
+    /// diagonal.
+    pub fn linking_matrix(&self) -> &DMatrix<i32> {
+        &self.linking_matrix
+    }
+
+    /// Adds `sign` to the linking entries of the pair `gen` crosses.
+    fn add_linking(&mut self, gen: &Generator, sign: i32) {
+        let k = gen.index();
+        if k == 0 || k >= self.dimension {
+            return;
+        }
+        let delta = if gen.is_overcrossing() { sign } else { -sign };
+        self.linking_matrix[(k - 1, k)] += delta;
+        self.linking_matrix[(k, k - 1)] += delta;
+    }
+
+    /// Street of the current hand that new generators are attributed to.
+    pub fn street(&self) -> Street {
+        self.street
+    }
+
+    /// Moves on to the next street (call on each `StreetBoundary`).
+    pub fn next_street(&mut self) {
+        self.street = self.street.next();
+    }
+
+    /// Writhe and crossing count of each street of the current hand that has
+    /// braided anything, e.g. preflop writhe vs river writhe.
+    pub fn street_metrics(&self) -> HashMap<Street, (i32, usize)> {
+        self.street_metrics.clone()
+    }
+
+    /// Bounds the stored braid word for long-lived sessions.
+    ///
+    /// Once `word` exceeds `window` generators it is freely reduced in place.
+    /// The word stays equivalent to the braid applied so far (so Tier 3 results
+    /// are unchanged) but is no longer the literal history. `None` (the
+    /// default) keeps every generator.
+    pub fn set_normalization_window(&mut self, window: Option<usize>) {
+        self.normalization_window = window;
+        self.next_normalization = window.unwrap_or(0);
+        self.normalize_word_if_due();
+    }
+
+    /// The normalization window set with `set_normalization_window`.
+    pub fn normalization_window(&self) -> Option<usize> {
+        self.normalization_window
+    }
+
+    /// Reduces `word` if a normalization window is set and has been crossed.
+    fn normalize_word_if_due(&mut self) {
+        let Some(window) = self.normalization_window else {
+            return;
+        };
+        if self.word.len() <= self.next_normalization {
+            return;
+        }
+        normalize(&mut self.word);
+        self.next_normalization = window.max(2 * self.word.len());
+    }
+
+    /// Registers a custom invariant to be updated with every generator.
+    pub fn register_invariant(&mut self, invariant: Box<dyn Invariant>) {
+        self.invariants.push(invariant);
+    }
+
+    /// Returns the current value of every registered invariant, keyed by name.
+    pub fn invariant_values(&self) -> HashMap<String, f64> {
+        self.invariants
+            .iter()
+            .map(|invariant| (invariant.name().to_string(), invariant.value()))
+            .collect()
     }
 
     /// Returns the dimension of the braid.
@@ -95,6 +403,9 @@ impl FingerprintState {
     /// This scalar invariant is suitable for display on a HUD and represents the
     /// "energy" or "complexity" of the hand.
     /// 
+    /// With t on the unit circle it is already chirality-independent: a hand
+    /// and its mirror image have conjugate traces, so equal magnitudes.
+    ///
     /// # Returns
     /// The magnitude (norm) of the complex trace
     pub fn burau_trace_magnitude(&self) -> f64 {
@@ -102,6 +413,94 @@ impl FingerprintState {
         trace.norm()
     }
 
+    /// Burau trace with the framing (writhe) phase divided out, symmetrised
+    /// over the hand and its mirror image.
+    ///
+    /// The trace is divided by exp(i·θ·writhe), θ = arg(t). Mirroring a braid
+    /// conjugates its trace and negates its writhe, so the corrected traces of
+    /// a hand and its mirror are conjugates; their average (evaluating at t
+    /// and t̄) is the real part, and this returns its absolute value. Unlike
+    /// `burau_trace_magnitude`, which keeps the writhe's phase in the
+    /// modulus, it drops whatever part of the trace only lines up with the
+    /// framing, so it is at most the raw magnitude.
+    pub fn framing_corrected_magnitude(&self) -> f64 {
+        let trace = self.burau_matrix.diagonal().iter().sum::<Complex<f64>>();
+        let framing = Complex::from_polar(1.0, self.t_param.arg() * self.writhe as f64);
+        (trace / framing).re.abs()
+    }
+
+    /// Returns the Jones polynomial of the closure of the braid so far.
+    ///
+    /// Formatted as a Laurent polynomial in t with ascending powers, e.g.
+    /// `t + t^3 - t^4` (half-integer powers such as `t^1/2` appear for links
+    /// with an even number of components). The result is cached until the
+    /// next generator is applied or the state is reset.
+    ///
+    /// This is Tier 3: exponential in the number of active strands.
+    pub fn compute_jones(&mut self) -> &str {
+        if self.jones_poly_cache.is_none() {
+            self.jones_poly_cache = Some(Self::jones_for(&self.word, self.dimension));
+        }
+        self.jones_poly_cache.as_deref().unwrap_or_default()
+    }
+
+    /// The Jones polynomial of `word` on `dimension` strands, formatted like
+    /// `compute_jones`. Lets callers compute it away from the state (e.g. on a
+    /// blocking thread) and store it in `jones_poly_cache` afterwards.
+    pub fn jones_for(word: &BraidWord, dimension: usize) -> String {
+        crate::jones::jones_polynomial(word, dimension).to_string_in("t", 2)
+    }
+
+    /// Calculates the magnitude of the trace of the reduced Burau matrix.
+    ///
+    /// Equals the unreduced trace minus the trivial eigenvalue 1, so it only
+    /// measures the part of the representation that carries information.
+    pub fn reduced_burau_trace_magnitude(&self) -> f64 {
+        let trace = self.reduced_burau.diagonal().iter().sum::<Complex<f64>>();
+        trace.norm()
+    }
+
+    /// Returns the Alexander polynomial of the closure of the braid so far.
+    ///
+    /// Computed symbolically from the reduced Burau representation of the
+    /// retained word (the numeric `burau_matrix` is evaluated at a fixed t and
+    /// can't be turned back into a polynomial). Normalized to lowest power t^0
+    /// with a positive constant term, e.g. `1 - t + t^2` for the trefoil; see
+    /// `alexander::alexander_polynomial` for details.
+    pub fn alexander_polynomial(&self) -> String {
+        crate::alexander::alexander_polynomial(&self.word, self.dimension).to_string()
+    }
+
+    /// Returns the writhe and crossing count over the crossings that strand
+    /// `seat` (1-based) takes part in, i.e. every σ_{seat-1}^{±1} and σ_{seat}^{±1}
+    /// in the retained word.
+    ///
+    /// Unlike `PlayerMetrics::writhe`, which only counts the crossings a seat
+    /// initiates, this includes crossings caused by its neighbours' actions.
+    pub fn strand_metrics(&self, seat: usize) -> StrandMetrics {
+        self.word
+            .iter()
+            .filter(|gen| gen.index() == seat || gen.index() + 1 == seat)
+            .fold(StrandMetrics::default(), |mut metrics, gen| {
+                metrics.writhe += if gen.is_overcrossing() { 1 } else { -1 };
+                metrics.crossing_count += 1;
+                metrics
+            })
+    }
+
+    /// Counts a seat collision if a move between two distinct seats produced
+    /// no generators (both seats wrapped onto the same strand).
+    ///
+    /// # Returns
+    /// True if this move was a collision
+    pub fn check_collision(&mut self, from: Seat, to: Seat, generators: usize) -> bool {
+        let collided = from != to && generators == 0;
+        if collided {
+            self.collisions += 1;
+        }
+        collided
+    }
+
     /// Updates the fingerprint state with a generator and tracks per-seat metrics.
     /// 
     /// This method updates both global and per-seat statistics when a generator
@@ -120,12 +519,19 @@ impl FingerprintState {
             return;
         }
 
+        // Untracked player (the seat may have changed hands): no metrics
+        if !self.tracks_player(&name) {
+            self.player_stats.remove(&seat);
+            return;
+        }
+
         // Get or create player metrics
         let metrics = self.player_stats.entry(seat).or_insert_with(|| {
             PlayerMetrics {
                 name: name.clone(),
                 writhe: 0,
                 complexity: 0.0,
+                activity: 1.0,
             }
         });
 
@@ -154,9 +560,151 @@ impl FingerprintState {
         }
     }
 
+    /// Restricts `player_stats` to the given players; global metrics still
+    /// see every generator. An empty list tracks everyone (the default).
+    pub fn set_player_filter(&mut self, players: Vec<String>) {
+        self.player_filter = players;
+        let filter = &self.player_filter;
+        self.player_stats
+            .retain(|_, metrics| player_matches(filter, &metrics.name));
+    }
+
+    /// The players set with `set_player_filter`.
+    pub fn player_filter(&self) -> &[String] {
+        &self.player_filter
+    }
+
+    /// Returns true if `player_stats` are kept for this player ID. A filter
+    /// entry matches the full ID or its name part (`Bob` matches `Bob_p2`).
+    pub fn tracks_player(&self, player_id: &str) -> bool {
+        player_matches(&self.player_filter, player_id)
+    }
+
+    /// Consumes the state and keeps only its scalar metrics, dropping the
+    /// Burau matrices and the braid word. Use it to hold on to the results of
+    /// many hands without holding on to every state.
+    pub fn into_summary(self) -> HandSummary {
+        self.summary()
+    }
+
+    /// The scalar metrics of `into_summary`, for a state that keeps going
+    /// (e.g. one about to be reset for the next hand).
+    pub fn summary(&self) -> HandSummary {
+        HandSummary {
+            writhe: self.writhe,
+            crossing_count: self.crossing_count,
+            burau_trace_magnitude: self.burau_trace_magnitude(),
+            seat_writhe: self
+                .player_stats
+                .iter()
+                .map(|(&seat, metrics)| (seat, metrics.writhe))
+                .collect(),
+        }
+    }
+
+    /// Burau complexity (|diagonal entry|) of a 1-based seat after its last
+    /// crossing, or `None` if the seat has no metrics yet.
+    pub fn seat_complexity(&self, seat: usize) -> Option<f64> {
+        self.player_stats
+            .get(&seat)
+            .map(|metrics| metrics.complexity)
+    }
+
+    /// Net crossings initiated by a 1-based seat this hand, or `None` if the
+    /// seat has no metrics yet.
+    pub fn seat_writhe(&self, seat: usize) -> Option<i32> {
+        self.player_stats.get(&seat).map(|metrics| metrics.writhe)
+    }
+
+    /// Advances the activity "cool-down" by one global step.
+    ///
+    /// Every seat's activity is multiplied by `activity_decay`, then the seat
+    /// that just acted is bumped back to 1.0. Call once per action (not per
+    /// generator) so idle players fade out at a steady rate.
+    ///
+    /// # Arguments
+    /// * `acting_seat` - The seat (1-based) that took the action
+    pub fn tick_activity(&mut self, acting_seat: usize) {
+        for (seat, metrics) in self.player_stats.iter_mut() {
+            if *seat == acting_seat {
+                metrics.activity = 1.0;
+            } else {
+                metrics.activity *= self.activity_decay;
+            }
+        }
+    }
+
+    /// Marks a seat as sitting out (`false`) or back at the table (`true`).
+    /// The seat's metrics are kept either way.
+    pub fn set_seat_active(&mut self, seat: usize, active: bool) {
+        if active {
+            self.inactive_seats.remove(&seat);
+        } else {
+            self.inactive_seats.insert(seat);
+        }
+    }
+
+    /// Returns false while the seat's player is standing up.
+    pub fn is_seat_active(&self, seat: usize) -> bool {
+        !self.inactive_seats.contains(&seat)
+    }
+
+    /// Seats whose player is standing up, in seat order.
+    pub fn inactive_seats(&self) -> Vec<usize> {
+        let mut seats: Vec<usize> = self.inactive_seats.iter().copied().collect();
+        seats.sort_unstable();
+        seats
+    }
+
+    /// Seats with metrics, most recently active first, skipping seats whose
+    /// player is standing up.
+    pub fn activity_ranking(&self) -> Vec<usize> {
+        let mut seats: Vec<(usize, f64)> = self
+            .player_stats
+            .iter()
+            .filter(|(seat, _)| self.is_seat_active(**seat))
+            .map(|(&seat, metrics)| (seat, metrics.activity))
+            .collect();
+        seats.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        seats.into_iter().map(|(seat, _)| seat).collect()
+    }
+
+    /// Seats with metrics, most complex (Burau diagonal) first, skipping
+    /// seats whose player is standing up. Equal complexities go to the lower
+    /// seat, so the order never depends on `HashMap` iteration.
+    pub fn players_ranked(&self) -> Vec<usize> {
+        let mut seats: Vec<(usize, f64)> = self
+            .player_stats
+            .iter()
+            .filter(|(seat, _)| self.is_seat_active(**seat))
+            .map(|(&seat, metrics)| (seat, metrics.complexity))
+            .collect();
+        seats.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        seats.into_iter().map(|(seat, _)| seat).collect()
+    }
+
+    /// The pair of 1-based seats with the most signed crossings between them
+    /// (by absolute value in `linking_matrix`), or None before any crossing
+    /// is left standing. Ties go to the lowest pair.
+    pub fn top_entangled_pair(&self) -> Option<(usize, usize)> {
+        let mut best: Option<((usize, usize), i32)> = None;
+        for i in 0..self.dimension {
+            for j in i + 1..self.dimension {
+                let links = self.linking_matrix[(i, j)].abs();
+                // Strictly greater, so the first (lowest) pair keeps a tie
+                if links > best.map_or(0, |(_, top)| top) {
+                    best = Some(((i + 1, j + 1), links));
+                }
+            }
+        }
+        best.map(|(pair, _)| pair)
+    }
+
     /// Processes an action and updates the fingerprint state.
     /// 
-    /// If the action is a Reset, the state is reset to identity.
+    /// If the action is a Reset, the state is reset to identity. StandUp and
+    /// SitBack toggle the seat's active status, and actions by a seat that is
+    /// standing up are ignored.
     /// Otherwise, the action is expanded to generators and applied incrementally.
     /// 
     /// # Arguments
@@ -164,7 +712,7 @@ impl FingerprintState {
     /// * `current_seat` - Current seat (for action expansion)
     /// 
     /// # Returns
-    /// The number of generators applied (0 for Reset)
+    /// The number of generators applied (0 for markers and inactive seats)
     pub fn process_action(
         &mut self,
         action: &crate::types::Action,
@@ -176,20 +724,48 @@ impl FingerprintState {
             self.reset();
             return 0;
         }
+        match action.action_type {
+            // Markers don't braid; callers decide whether to checkpoint
+            ActionType::StreetBoundary => {
+                self.next_street();
+                return 0;
+            }
+            ActionType::StandUp | ActionType::SitBack => {
+                self.set_seat_active(
+                    action.seat.value(),
+                    action.action_type == ActionType::SitBack,
+                );
+                return 0;
+            }
+            _ if !self.is_seat_active(action.seat.value()) => return 0,
+            _ => {}
+        }
         
-        // Expand action to generators
+        // Expand action to generators and apply each one as it is produced
         let from_seat = current_seat.unwrap_or(action.seat);
-        let generators = crate::mapping::expand_action(from_seat, action.seat, self.dimension());
-        
-        // Apply each generator
-        for gen in &generators {
-            self.update(gen);
+        let mut applied = 0;
+        for gen in crate::mapping::expand_action_iter(from_seat, action.seat, self.dimension()) {
+            self.update(&gen);
+            applied += 1;
         }
+        self.check_collision(from_seat, action.seat, applied);
+        self.tick_activity(action.seat.value());
         
-        generators.len()
+        applied
     }
 }
 
+/// Player filter check shared by `tracks_player` and `set_player_filter`.
+fn player_matches(filter: &[String], player_id: &str) -> bool {
+    let name = player_id
+        .rsplit_once('_')
+        .map_or(player_id, |(name, _)| name);
+    filter.is_empty()
+        || filter
+            .iter()
+            .any(|player| player == player_id || player == name)
+}
+
 impl Default for FingerprintState {
     fn default() -> Self {
         FingerprintState::with_default_dimension()
@@ -204,21 +780,201 @@ impl IncrementalUpdate for FingerprintState {
     /// - crossing_count: incremented by 1
     /// - Burau matrix: multiplied by generator matrix U_k or U_k^{-1}
     fn update(&mut self, gen: &Generator) {
+        if gen.index() == 0 || gen.index() >= self.dimension {
+            self.dropped_generators += 1;
+        }
+        let delta = if gen.is_overcrossing() { 1 } else { -1 };
+        self.writhe += delta;
+        self.peak_abs_writhe = self.peak_abs_writhe.max(self.writhe.abs());
+        if self
+            .applied
+            .last()
+            .is_some_and(|&(_, over)| over != gen.is_overcrossing())
+        {
+            self.writhe_reversals += 1;
+        }
+        self.apply_generator_matrices(gen);
+        self.add_linking(gen, 1);
+        self.crossing_count += 1;
+        let street = self.street_metrics.entry(self.street).or_default();
+        street.0 += if gen.is_overcrossing() { 1 } else { -1 };
+        street.1 += 1;
+        self.applied.push((self.street, gen.is_overcrossing()));
+        if let Some(count) = gen
+            .index()
+            .checked_sub(1)
+            .and_then(|i| self.generator_histogram.get_mut(i))
+        {
+            *count += 1;
+        }
+        self.word.push(*gen);
+        self.normalize_word_if_due();
+        self.jones_poly_cache = None;
+        for invariant in &mut self.invariants {
+            invariant.update(gen);
+        }
+    }
+}
+
+impl FingerprintState {
+    /// Like `update`, but rejects a generator whose index doesn't fit the
+    /// dimension instead of counting it in `dropped_generators`. The state is
+    /// unchanged on error.
+    pub fn try_update(&mut self, gen: &Generator) -> Result<(), InvalidGenerator> {
+        if gen.index() == 0 || gen.index() >= self.dimension {
+            return Err(InvalidGenerator {
+                generator: *gen,
+                strands: self.dimension,
+            });
+        }
+        self.update(gen);
+        Ok(())
+    }
+
+    /// Reverses a previous `update(gen)`, stepping the fingerprint back by
+    /// one generator.
+    ///
+    /// Restores writhe, crossing count, writhe reversals, the metrics of the street the
+    /// generator was applied on, the generator histogram,
+    /// `dropped_generators` and registered `Invariant` plugins, and
+    /// multiplies both Burau matrices by the inverse generator matrix. The
+    /// peak writhe only moves forward and is left as it is. `player_stats` is
+    /// never touched by `update` (per-seat metrics are recorded per action by
+    /// the caller), so there is nothing to undo there.
+    ///
+    /// Fails, leaving the state unchanged, if a registered invariant can't
+    /// step back.
+    pub fn undo(&mut self, gen: &Generator) -> Result<(), UndoUnsupported> {
+        for i in 0..self.invariants.len() {
+            if !self.invariants[i].undo(gen) {
+                // Put the ones already stepped back forward again
+                for invariant in &mut self.invariants[..i] {
+                    invariant.update(gen);
+                }
+                return Err(UndoUnsupported {
+                    invariant: self.invariants[i].name().to_string(),
+                });
+            }
+        }
+        if gen.index() == 0 || gen.index() >= self.dimension {
+            self.dropped_generators = self.dropped_generators.saturating_sub(1);
+        }
+        self.writhe -= if gen.is_overcrossing() { 1 } else { -1 };
+        self.apply_generator_matrices(&gen.inverse());
+        self.add_linking(gen, -1);
+        self.crossing_count = self.crossing_count.saturating_sub(1);
+        let applied_on = match self.applied.pop() {
+            Some((street, over)) => {
+                if self
+                    .applied
+                    .last()
+                    .is_some_and(|&(_, previous)| previous != over)
+                {
+                    self.writhe_reversals = self.writhe_reversals.saturating_sub(1);
+                }
+                street
+            }
+            None => self.street,
+        };
+        if let Some(street) = self.street_metrics.get_mut(&applied_on) {
+            street.0 -= if gen.is_overcrossing() { 1 } else { -1 };
+            street.1 = street.1.saturating_sub(1);
+            if street.1 == 0 {
+                self.street_metrics.remove(&applied_on);
+            }
+        }
+        if let Some(count) = gen
+            .index()
+            .checked_sub(1)
+            .and_then(|i| self.generator_histogram.get_mut(i))
+        {
+            *count = count.saturating_sub(1);
+        }
+        // A normalized word may no longer end in `gen`; appending the inverse
+        // keeps it equivalent to what has been applied
+        match self.word.pop() {
+            Some(last) if last == *gen => {}
+            Some(last) => {
+                self.word.push(last);
+                self.word.push(gen.inverse());
+            }
+            None => self.word.push(gen.inverse()),
+        }
+        self.jones_poly_cache = None;
+        Ok(())
+    }
+
+    /// Captures the current braid progress so a speculative line (e.g. "what
+    /// if this player had raised?") can be explored and rolled back with
+    /// `restore`.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            writhe: self.writhe,
+            crossing_count: self.crossing_count,
+            peak_abs_writhe: self.peak_abs_writhe,
+            writhe_reversals: self.writhe_reversals,
+            generator_histogram: self.generator_histogram.clone(),
+            street: self.street,
+            street_metrics: self.street_metrics.clone(),
+            applied: self.applied.clone(),
+            linking_matrix: self.linking_matrix.clone(),
+            burau_matrix: self.burau_matrix.clone(),
+            reduced_burau: self.reduced_burau.clone(),
+            word: self.word.clone(),
+            next_normalization: self.next_normalization,
+            player_stats: self.player_stats.clone(),
+        }
+    }
+
+    /// Puts back the braid progress captured by `checkpoint`.
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.writhe = checkpoint.writhe;
+        self.crossing_count = checkpoint.crossing_count;
+        self.peak_abs_writhe = checkpoint.peak_abs_writhe;
+        self.writhe_reversals = checkpoint.writhe_reversals;
+        self.generator_histogram = checkpoint.generator_histogram;
+        self.street = checkpoint.street;
+        self.street_metrics = checkpoint.street_metrics;
+        self.applied = checkpoint.applied;
+        self.linking_matrix = checkpoint.linking_matrix;
+        self.burau_matrix = checkpoint.burau_matrix;
+        self.reduced_burau = checkpoint.reduced_burau;
+        self.word = checkpoint.word;
+        self.next_normalization = checkpoint.next_normalization;
+        self.player_stats = checkpoint.player_stats;
+        self.jones_poly_cache = None;
+    }
+
+    /// Estimates the floating-point error accumulated in `burau_matrix`.
+    ///
+    /// Every generator matrix has rows summing to 1, so the exact Burau
+    /// matrix maps the all-ones vector to itself; this returns the largest
+    /// deviation of a row sum from 1. It grows with the number of generators
+    /// applied, independently of how the word was built, and costs one pass
+    /// over the matrix. Once it nears the precision you need, the trace
+    /// magnitude is no longer trustworthy.
+    pub fn numerical_drift(&self) -> f64 {
+        let one = Complex::new(1.0, 0.0);
+        self.burau_matrix
+            .row_iter()
+            .map(|row| (row.iter().sum::<Complex<f64>>() - one).norm())
+            .fold(0.0, f64::max)
+    }
+
+    /// Multiplies the full and reduced Burau matrices by the matrices of `gen`.
+    fn apply_generator_matrices(&mut self, gen: &Generator) {
         match gen {
             Generator::Sigma(k) => {
-                self.writhe += 1;
                 self.apply_sigma_matrix(*k);
+                self.apply_sigma_matrix_reduced(*k);
             }
             Generator::InverseSigma(k) => {
-                self.writhe -= 1;
                 self.apply_inverse_sigma_matrix(*k);
+                self.apply_inverse_sigma_matrix_reduced(*k);
             }
         }
-        self.crossing_count += 1;
     }
-}
 
-impl FingerprintState {
     /// Applies the generator matrix U_k for σ_k to the Burau matrix.
     /// 
     /// U_k is the identity matrix except for the 2x2 block at indices (k-1, k):
@@ -278,6 +1034,136 @@ impl FingerprintState {
         // Multiply: M_new = M_old * U_k^{-1}
         self.burau_matrix = &self.burau_matrix * &u_k_inv;
     }
+
+    /// Applies the reduced Burau matrix for σ_k to `reduced_burau`.
+    ///
+    /// The (N-1) x (N-1) matrix is the identity except row k-1 (0-based),
+    /// which holds `[t, -t, 1]` in columns k-2, k-1, k. Entries that fall
+    /// outside the matrix (k = 1 or k = N-1) are dropped.
+    fn apply_sigma_matrix_reduced(&mut self, k: usize) {
+        let one = Complex::new(1.0, 0.0);
+        let t = self.t_param;
+        self.apply_reduced_row(k, t, -t, one);
+    }
+
+    /// Applies the reduced Burau matrix for σ_k^{-1} to `reduced_burau`.
+    ///
+    /// Row k-1 (0-based) holds `[1, -1/t, 1/t]` in columns k-2, k-1, k.
+    fn apply_inverse_sigma_matrix_reduced(&mut self, k: usize) {
+        let one = Complex::new(1.0, 0.0);
+        let one_over_t = one / self.t_param;
+        self.apply_reduced_row(k, one, -one_over_t, one_over_t);
+    }
+
+    /// Right-multiplies `reduced_burau` by the identity with row k-1 replaced
+    /// by `left`, `middle`, `right` around the diagonal.
+    fn apply_reduced_row(
+        &mut self,
+        k: usize,
+        left: Complex<f64>,
+        middle: Complex<f64>,
+        right: Complex<f64>,
+    ) {
+        // Validate k is in range [1, dimension-1]
+        if k == 0 || k >= self.dimension {
+            return;
+        }
+
+        let size = self.dimension - 1;
+        let row = k - 1;
+        let mut generator = DMatrix::identity(size, size);
+        if row > 0 {
+            generator[(row, row - 1)] = left;
+        }
+        generator[(row, row)] = middle;
+        if row + 1 < size {
+            generator[(row, row + 1)] = right;
+        }
+
+        self.reduced_burau = &self.reduced_burau * &generator;
+    }
+}
+
+/// Outcome of a single consistency check from `validate_invariants`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct InvariantCheck {
+    pub name: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Runs internal consistency checks between a fingerprint and the braid word
+/// that produced it (since the last reset).
+///
+/// Checks:
+/// - `writhe`: positive − negative crossings == writhe
+/// - `crossing_count`: positive + negative crossings == crossing_count
+/// - `permutation`: the Burau matrix at t = 1 is the word's permutation matrix
+/// - `determinant`: det(Burau) == (−t)^writhe, so |det| == 1 on the unit circle
+///
+/// # Arguments
+/// * `state` - The fingerprint to check
+/// * `word` - The generators applied to `state` since its last reset
+pub fn validate_invariants(state: &FingerprintState, word: &BraidWord) -> Vec<InvariantCheck> {
+    const TOLERANCE: f64 = 1e-6;
+    let dimension = state.dimension();
+
+    let positive = word.iter().filter(|g| g.is_overcrossing()).count() as i32;
+    let negative = word.iter().filter(|g| g.is_undercrossing()).count() as i32;
+
+    let mut checks = Vec::new();
+
+    checks.push(InvariantCheck {
+        name: "writhe",
+        passed: positive - negative == state.writhe,
+        detail: format!("{} - {} vs writhe {}", positive, negative, state.writhe),
+    });
+
+    checks.push(InvariantCheck {
+        name: "crossing_count",
+        passed: (positive + negative) as usize == state.crossing_count,
+        detail: format!(
+            "{} + {} vs crossing_count {}",
+            positive, negative, state.crossing_count
+        ),
+    });
+
+    // At t = 1 every generator matrix collapses to an adjacent transposition,
+    // so replaying the word gives the permutation matrix of the braid
+    let mut at_one = FingerprintState::new(dimension);
+    at_one.t_param = Complex::new(1.0, 0.0);
+    for gen in word.iter() {
+        at_one.update(gen);
+    }
+    let permutation = word.permutation(dimension);
+    let max_error = (0..dimension)
+        .flat_map(|row| (0..dimension).map(move |col| (row, col)))
+        .map(|(row, col)| {
+            let expected = if permutation[row] == col { 1.0 } else { 0.0 };
+            (at_one.burau_matrix[(row, col)] - Complex::new(expected, 0.0)).norm()
+        })
+        .fold(0.0, f64::max);
+    checks.push(InvariantCheck {
+        name: "permutation",
+        passed: max_error < TOLERANCE,
+        detail: format!("max deviation from permutation matrix {:.2e}", max_error),
+    });
+
+    // det(U_k) = -t and det(U_k^{-1}) = -1/t
+    let determinant = state.burau_matrix.determinant();
+    let expected = (-state.t_param).powi(state.writhe);
+    checks.push(InvariantCheck {
+        name: "determinant",
+        passed: (determinant - expected).norm() < TOLERANCE
+            && (determinant.norm() - expected.norm()).abs() < TOLERANCE,
+        detail: format!(
+            "|det| = {:.6}, expected {:.6}",
+            determinant.norm(),
+            expected.norm()
+        ),
+    });
+
+    checks
 }
 
 #[cfg(test)]
@@ -299,6 +1185,54 @@ mod tests {
         assert_eq!(state.burau_matrix, identity);
     }
 
+    #[test]
+    fn test_with_t_param() {
+        let mut default = FingerprintState::new(3);
+        let mut negative = FingerprintState::with_t_param(3, Complex::new(-1.0, 0.0));
+        assert_eq!(negative.t_param, Complex::new(-1.0, 0.0));
+        assert_eq!(negative.burau_matrix, DMatrix::identity(3, 3));
+
+        default.update(&Generator::Sigma(1));
+        negative.update(&Generator::Sigma(1));
+
+        // At t = -1 the block is [[2, -1], [1, 0]]
+        assert_eq!(negative.burau_matrix[(0, 0)], Complex::new(2.0, 0.0));
+        assert_eq!(negative.burau_matrix[(0, 1)], Complex::new(-1.0, 0.0));
+        assert_ne!(negative.burau_matrix, default.burau_matrix);
+        assert!((negative.burau_trace_magnitude() - default.burau_trace_magnitude()).abs() > 1e-6);
+    }
+
+    #[test]
+    fn test_reduced_burau_three_strands() {
+        let one = Complex::new(1.0, 0.0);
+        let zero = Complex::new(0.0, 0.0);
+
+        // σ1 on 3 strands: [[-t, 1], [0, 1]]
+        let mut state = FingerprintState::new(3);
+        let t = state.t_param;
+        state.update(&Generator::Sigma(1));
+        assert_eq!(
+            state.reduced_burau,
+            DMatrix::from_row_slice(2, 2, &[-t, one, zero, one])
+        );
+        assert!((state.reduced_burau_trace_magnitude() - (one - t).norm()).abs() < 1e-12);
+
+        // σ1^{-1} on 3 strands: [[-1/t, 1/t], [0, 1]]
+        let mut state = FingerprintState::new(3);
+        state.update(&Generator::InverseSigma(1));
+        let expected = DMatrix::from_row_slice(2, 2, &[-one / t, one / t, zero, one]);
+        assert!((&state.reduced_burau - &expected)
+            .iter()
+            .all(|c| c.norm() < 1e-12));
+
+        // σ1 σ1^{-1} is the identity again, and reset restores identity
+        state.update(&Generator::Sigma(1));
+        let diff = &state.reduced_burau - DMatrix::<Complex<f64>>::identity(2, 2);
+        assert!(diff.iter().all(|c| c.norm() < 1e-12));
+        state.reset();
+        assert_eq!(state.reduced_burau, DMatrix::identity(2, 2));
+    }
+
     #[test]
     fn test_update_sigma() {
         let mut state = FingerprintState::new(4);
@@ -331,6 +1265,195 @@ mod tests {
         assert_eq!(state.crossing_count, 3);
     }
 
+    #[test]
+    fn test_undo_restores_prior_state() {
+        let mut state = FingerprintState::new(4);
+        state.update(&Generator::Sigma(2));
+        let burau = state.burau_matrix.clone();
+        let reduced = state.reduced_burau.clone();
+
+        state.update(&Generator::Sigma(1));
+        state.undo(&Generator::Sigma(1)).unwrap();
+
+        assert_eq!(state.writhe, 1);
+        assert_eq!(state.crossing_count, 1);
+        assert_eq!(
+            state.word,
+            BraidWord::from_generators(vec![Generator::Sigma(2)])
+        );
+        assert!((&state.burau_matrix - &burau)
+            .iter()
+            .all(|c| c.norm() < 1e-10));
+        assert!((&state.reduced_burau - &reduced)
+            .iter()
+            .all(|c| c.norm() < 1e-10));
+    }
+
+    #[test]
+    fn test_undo_leaves_no_phantom_street_entry() {
+        let mut state = FingerprintState::new(4);
+        state.update(&Generator::Sigma(2));
+        state.update(&Generator::Sigma(1));
+        state.next_street();
+        state.update(&Generator::InverseSigma(3));
+
+        // Undoing across the street boundary takes σ1 off preflop, not the flop
+        state.undo(&Generator::InverseSigma(3)).unwrap();
+        assert_eq!(state.street_metrics().get(&Street::Flop), None);
+        state.undo(&Generator::Sigma(1)).unwrap();
+        assert_eq!(state.street_metrics()[&Street::Preflop], (1, 1));
+        assert_eq!(state.street_metrics().get(&Street::Flop), None);
+        let crossings: usize = state
+            .street_metrics()
+            .values()
+            .map(|(_, count)| count)
+            .sum();
+        assert_eq!(crossings, state.crossing_count);
+
+        // Out-of-range generators are counted on the way in and out
+        state.update(&Generator::Sigma(7));
+        assert_eq!(state.dropped_generators, 1);
+        state.undo(&Generator::Sigma(7)).unwrap();
+        assert_eq!(state.dropped_generators, 0);
+    }
+
+    #[test]
+    fn test_undo_steps_invariants_back() {
+        let mut state = FingerprintState::new(4);
+        state.register_invariant(Box::new(WritheInvariant::default()));
+        state.update(&Generator::Sigma(1));
+        state.update(&Generator::InverseSigma(2));
+        state.undo(&Generator::InverseSigma(2)).unwrap();
+        assert_eq!(state.invariant_values()["writhe"], state.writhe as f64);
+
+        // An invariant that only moves forward blocks the undo entirely
+        state.register_invariant(Box::new(SigmaOneCount::default()));
+        state.update(&Generator::Sigma(1));
+        let err = state.undo(&Generator::Sigma(1)).unwrap_err();
+        assert_eq!(err.invariant, "sigma1_count");
+        assert_eq!(state.writhe, 2);
+        assert_eq!(state.invariant_values()["writhe"], 2.0);
+        assert_eq!(state.invariant_values()["sigma1_count"], 1.0);
+    }
+
+    #[test]
+    fn test_peak_writhe_and_churn() {
+        use Generator::{InverseSigma, Sigma};
+
+        let mut state = FingerprintState::new(4);
+        for gen in [Sigma(1), Sigma(1), InverseSigma(1), InverseSigma(1)] {
+            state.update(&gen);
+        }
+        assert_eq!(state.writhe, 0);
+        assert_eq!(state.peak_abs_writhe(), 2);
+        assert_eq!(state.writhe_reversals(), 1);
+
+        state.reset();
+        assert_eq!(state.peak_abs_writhe(), 0);
+        assert_eq!(state.writhe_reversals(), 0);
+
+        // Same length: oscillating turns around every step, creeping never does
+        let mut oscillating = FingerprintState::new(4);
+        let mut monotone = FingerprintState::new(4);
+        for i in 0..6 {
+            oscillating.update(&if i % 2 == 0 {
+                Sigma(1)
+            } else {
+                InverseSigma(2)
+            });
+            monotone.update(&Sigma(i % 3 + 1));
+        }
+        assert_eq!(oscillating.crossing_count, monotone.crossing_count);
+        assert_eq!(oscillating.writhe_reversals(), 5);
+        assert_eq!(monotone.writhe_reversals(), 0);
+
+        // Undo takes back the reversal the last generator made
+        oscillating.undo(&InverseSigma(2)).unwrap();
+        assert_eq!(oscillating.writhe_reversals(), 4);
+    }
+
+    #[test]
+    fn test_checkpoint_restore() {
+        let mut state = FingerprintState::new(4);
+        state.update_for_seat(&Generator::Sigma(1), 1, "Alice".to_string());
+        state.update_for_seat(&Generator::InverseSigma(2), 2, "Bob".to_string());
+        let before = state.clone();
+
+        let checkpoint = state.checkpoint();
+        state.update_for_seat(&Generator::Sigma(3), 3, "Carol".to_string());
+        state.update_for_seat(&Generator::Sigma(2), 2, "Bob".to_string());
+        state.next_street();
+        state.update_for_seat(&Generator::Sigma(1), 1, "Alice".to_string());
+        state.restore(checkpoint);
+
+        assert_eq!(state.writhe, before.writhe);
+        assert_eq!(state.crossing_count, before.crossing_count);
+        assert_eq!(state.peak_abs_writhe(), before.peak_abs_writhe());
+        assert_eq!(state.writhe_reversals(), before.writhe_reversals());
+        assert_eq!(state.generator_histogram, before.generator_histogram);
+        assert_eq!(state.street(), before.street());
+        assert_eq!(state.street_metrics(), before.street_metrics());
+        assert_eq!(state.linking_matrix(), before.linking_matrix());
+        assert_eq!(state.burau_matrix, before.burau_matrix);
+        assert_eq!(state.reduced_burau, before.reduced_burau);
+        assert_eq!(state.word, before.word);
+        assert_eq!(state.player_stats, before.player_stats);
+    }
+
+    #[test]
+    fn test_try_update_checks_generator_range() {
+        let mut state = FingerprintState::new(4);
+        assert!(state.try_update(&Generator::Sigma(3)).is_ok());
+        assert_eq!(state.crossing_count, 1);
+
+        let err = state.try_update(&Generator::InverseSigma(4)).unwrap_err();
+        assert_eq!(
+            err,
+            InvalidGenerator {
+                generator: Generator::InverseSigma(4),
+                strands: 4
+            }
+        );
+        assert!(err.to_string().contains("4 strands"), "{}", err);
+        assert_eq!(
+            state.crossing_count, 1,
+            "Rejected generator must leave the state alone"
+        );
+        assert_eq!(state.dropped_generators, 0);
+
+        // The lenient path counts what it can't braid
+        state.update(&Generator::Sigma(0));
+        state.update(&Generator::Sigma(4));
+        assert_eq!(state.dropped_generators, 2);
+    }
+
+    #[test]
+    fn test_numerical_drift_grows_with_word_length() {
+        // A mixed word with no cancelling pairs
+        let drift_after = |len: usize| {
+            let mut state = FingerprintState::new(4);
+            for i in 0..len {
+                let gen = match i % 5 {
+                    0 | 3 => Generator::Sigma(1 + i % 3),
+                    1 => Generator::InverseSigma(1 + (i + 1) % 3),
+                    _ => Generator::Sigma(1 + (i + 2) % 3),
+                };
+                state.update(&gen);
+            }
+            state.numerical_drift()
+        };
+
+        assert_eq!(FingerprintState::new(4).numerical_drift(), 0.0);
+        let short = drift_after(10);
+        let long = drift_after(2000);
+        assert!(
+            long > short,
+            "Drift should grow with word length: {} vs {}",
+            short,
+            long
+        );
+    }
+
     #[test]
     fn test_reset() {
         let mut state = FingerprintState::new(4);
@@ -344,6 +1467,226 @@ mod tests {
         assert_eq!(state.burau_matrix, identity);
     }
 
+    #[test]
+    fn test_activity_decays_for_idle_seats() {
+        let mut state = FingerprintState::new(4);
+        state.update_for_seat(&Generator::Sigma(1), 1, "Alice".to_string());
+        state.tick_activity(1);
+        assert_eq!(state.player_stats[&1].activity, 1.0);
+
+        // Seat 2 acts repeatedly while seat 1 sits idle
+        for _ in 0..10 {
+            state.update_for_seat(&Generator::Sigma(2), 2, "Bob".to_string());
+            state.tick_activity(2);
+        }
+        assert_eq!(state.player_stats[&2].activity, 1.0);
+        assert!(
+            state.player_stats[&1].activity < 0.2,
+            "Idle seat should have decayed, got {}",
+            state.player_stats[&1].activity
+        );
+    }
+
+    #[test]
+    fn test_into_summary_keeps_scalar_metrics() {
+        let mut state = FingerprintState::new(4);
+        state.update_for_seat(&Generator::Sigma(1), 1, "Alice".to_string());
+        state.update_for_seat(&Generator::Sigma(2), 2, "Bob".to_string());
+        state.update_for_seat(&Generator::InverseSigma(1), 2, "Bob".to_string());
+        state.update_for_seat(&Generator::InverseSigma(3), 3, "Carol".to_string());
+
+        let writhe = state.writhe;
+        let crossings = state.crossing_count;
+        let magnitude = state.burau_trace_magnitude();
+        let seat_writhe: Vec<_> = (1..=3)
+            .map(|seat| (seat, state.seat_writhe(seat).unwrap()))
+            .collect();
+
+        let summary = state.into_summary();
+        assert_eq!(summary.writhe, writhe);
+        assert_eq!(summary.crossing_count, crossings);
+        assert_eq!(summary.burau_trace_magnitude, magnitude);
+        assert_eq!(
+            summary.seat_writhe.into_iter().collect::<Vec<_>>(),
+            seat_writhe
+        );
+    }
+
+    #[test]
+    fn test_seat_accessors() {
+        let mut state = FingerprintState::new(4);
+        assert_eq!(state.seat_writhe(1), None);
+
+        state.update_for_seat(&Generator::Sigma(1), 1, "Alice".to_string());
+        state.update_for_seat(&Generator::Sigma(2), 3, "Carol".to_string());
+        state.update_for_seat(&Generator::InverseSigma(2), 3, "Carol".to_string());
+        state.update_for_seat(&Generator::InverseSigma(3), 3, "Carol".to_string());
+
+        assert_eq!(state.seat_writhe(1), Some(1));
+        assert_eq!(state.seat_writhe(3), Some(-1));
+        assert_eq!(
+            state.seat_complexity(3),
+            Some(state.burau_matrix[(2, 2)].norm())
+        );
+        assert_eq!(
+            state.seat_complexity(1),
+            Some(state.player_stats[&1].complexity)
+        );
+        assert_eq!(state.seat_writhe(2), None);
+        assert_eq!(state.seat_complexity(2), None);
+        assert_eq!(state.seat_complexity(0), None);
+    }
+
+    #[test]
+    fn test_ranking_ties_go_to_lower_seat() {
+        let mut state = FingerprintState::new(4);
+        for (seat, complexity) in [(3, 0.5), (2, 0.5), (4, 0.9), (1, 0.1)] {
+            state.player_stats.insert(
+                seat,
+                PlayerMetrics {
+                    complexity,
+                    ..Default::default()
+                },
+            );
+        }
+        for _ in 0..10 {
+            assert_eq!(state.players_ranked(), vec![4, 2, 3, 1]);
+        }
+        state.set_seat_active(4, false);
+        assert_eq!(
+            state.players_ranked(),
+            vec![2, 3, 1],
+            "Standing players aren't ranked"
+        );
+        state.set_seat_active(4, true);
+
+        // σ2 and σ3⁻¹ tie on one crossing each: the lower pair wins
+        assert_eq!(state.top_entangled_pair(), None);
+        state.update(&Generator::InverseSigma(3));
+        state.update(&Generator::Sigma(2));
+        assert_eq!(state.top_entangled_pair(), Some((2, 3)));
+        state.update(&Generator::InverseSigma(3));
+        assert_eq!(state.top_entangled_pair(), Some((3, 4)));
+    }
+
+    #[test]
+    fn test_player_filter_limits_player_stats() {
+        let mut state = FingerprintState::new(4);
+        state.set_player_filter(vec!["Bob".to_string()]);
+
+        state.update_for_seat(&Generator::Sigma(1), 1, "Alice_p1".to_string());
+        state.update_for_seat(&Generator::Sigma(2), 2, "Bob_p2".to_string());
+        state.update_for_seat(&Generator::InverseSigma(3), 3, "Carol_p3".to_string());
+
+        assert_eq!(state.player_stats.keys().collect::<Vec<_>>(), [&2]);
+        assert_eq!(state.player_stats[&2].writhe, 1);
+        // Global metrics still see everyone
+        assert_eq!(state.writhe, 1);
+        assert_eq!(state.crossing_count, 3);
+
+        // Someone else taking Bob's seat drops the seat's metrics
+        state.update_for_seat(&Generator::Sigma(1), 2, "Dave_p9".to_string());
+        assert!(state.player_stats.is_empty());
+    }
+
+    /// Counts occurrences of σ₁ (either sign).
+    #[derive(Debug, Clone, Default)]
+    struct SigmaOneCount(usize);
+
+    impl Invariant for SigmaOneCount {
+        fn name(&self) -> &str {
+            "sigma1_count"
+        }
+
+        fn update(&mut self, gen: &Generator) {
+            if gen.index() == 1 {
+                self.0 += 1;
+            }
+        }
+
+        fn value(&self) -> f64 {
+            self.0 as f64
+        }
+
+        fn reset(&mut self) {
+            self.0 = 0;
+        }
+    }
+
+    #[test]
+    fn test_custom_invariant_appears_in_metrics() {
+        let mut state = FingerprintState::new(4);
+        state.register_invariant(Box::new(SigmaOneCount::default()));
+        state.register_invariant(Box::new(WritheInvariant::default()));
+
+        state.update(&Generator::Sigma(1));
+        state.update(&Generator::Sigma(2));
+        state.update(&Generator::InverseSigma(1));
+
+        let values = state.invariant_values();
+        assert_eq!(values["sigma1_count"], 2.0);
+        assert_eq!(values["writhe"], state.writhe as f64);
+
+        // Cloned states carry independent copies of the plugins
+        let mut cloned = state.clone();
+        cloned.update(&Generator::Sigma(1));
+        assert_eq!(cloned.invariant_values()["sigma1_count"], 3.0);
+        assert_eq!(state.invariant_values()["sigma1_count"], 2.0);
+
+        state.reset();
+        assert_eq!(state.invariant_values()["sigma1_count"], 0.0);
+    }
+
+    #[test]
+    fn test_compute_jones_trefoil() {
+        let mut state = FingerprintState::new(2);
+        for _ in 0..3 {
+            state.update(&Generator::Sigma(1));
+        }
+        assert_eq!(state.compute_jones(), "t + t^3 - t^4");
+        assert_eq!(state.jones_poly_cache.as_deref(), Some("t + t^3 - t^4"));
+
+        // A cached value is reused until the next generator arrives
+        state.jones_poly_cache = Some("cached".to_string());
+        assert_eq!(state.compute_jones(), "cached");
+        state.update(&Generator::InverseSigma(1));
+        assert_eq!(state.compute_jones(), "-t^1/2 - t^5/2"); // σ1² closes to the Hopf link
+
+        state.reset();
+        assert!(state.word.is_empty());
+        assert_eq!(state.compute_jones(), "-t^-1/2 - t^1/2"); // two-component unlink
+    }
+
+    #[test]
+    fn test_alexander_polynomial() {
+        let mut state = FingerprintState::new(9);
+        assert_eq!(state.alexander_polynomial(), "1");
+
+        // Figure-eight knot σ1 σ2^{-1} σ1 σ2^{-1}
+        for gen in [Generator::Sigma(1), Generator::InverseSigma(2)].repeat(2) {
+            state.update(&gen);
+        }
+        assert_eq!(state.alexander_polynomial(), "1 - 3t + t^2");
+    }
+
+    #[test]
+    fn test_validate_invariants_detects_tampering() {
+        let word: BraidWord = "s1 s2 s1' s3".parse().unwrap();
+        let mut state = FingerprintState::new(4);
+        for gen in word.iter() {
+            state.update(gen);
+        }
+        assert!(validate_invariants(&state, &word).iter().all(|c| c.passed));
+
+        state.writhe += 1;
+        let failed: Vec<&str> = validate_invariants(&state, &word)
+            .iter()
+            .filter(|c| !c.passed)
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(failed, vec!["writhe", "determinant"]);
+    }
+
     #[test]
     fn test_sigma_inverse_cancellation() {
         // σ_1 * σ_1^{-1} should approximately return to identity
@@ -358,4 +1701,221 @@ mod tests {
         // Should be very close to identity (within floating point error)
         assert!(max_diff < 1e-10, "Matrix should be close to identity after cancellation");
     }
+
+    #[test]
+    fn test_trace_magnitude_ignores_chirality() {
+        let hand = [
+            Generator::Sigma(1),
+            Generator::Sigma(2),
+            Generator::Sigma(1),
+            Generator::InverseSigma(3),
+            Generator::Sigma(2),
+        ];
+        let mut state = FingerprintState::new(4);
+        let mut mirror = FingerprintState::new(4);
+        for gen in &hand {
+            state.update(gen);
+            mirror.update(&gen.inverse());
+        }
+
+        let trace =
+            |state: &FingerprintState| state.burau_matrix.diagonal().iter().sum::<Complex<f64>>();
+        assert_ne!(state.writhe, mirror.writhe);
+        assert!(
+            (trace(&mirror) - trace(&state).conj()).norm() < 1e-10,
+            "Mirror trace should be the conjugate"
+        );
+        assert!(
+            (state.burau_trace_magnitude() - mirror.burau_trace_magnitude()).abs() < 1e-10,
+            "{} vs {}",
+            state.burau_trace_magnitude(),
+            mirror.burau_trace_magnitude()
+        );
+    }
+
+    #[test]
+    fn test_framing_corrected_magnitude_ignores_chirality() {
+        let hand = [
+            Generator::Sigma(1),
+            Generator::Sigma(2),
+            Generator::Sigma(1),
+            Generator::InverseSigma(3),
+            Generator::Sigma(2),
+        ];
+        let mut state = FingerprintState::new(4);
+        let mut mirror = FingerprintState::new(4);
+        for gen in &hand {
+            state.update(gen);
+            mirror.update(&gen.inverse());
+        }
+
+        assert_ne!(state.writhe, mirror.writhe);
+        assert!(
+            (state.framing_corrected_magnitude() - mirror.framing_corrected_magnitude()).abs()
+                < 1e-10,
+            "{} vs {}",
+            state.framing_corrected_magnitude(),
+            mirror.framing_corrected_magnitude()
+        );
+        // A different measure from the raw magnitude, never above it
+        assert!(state.framing_corrected_magnitude() < state.burau_trace_magnitude() - 1e-6);
+
+        // The single crossing σ1 on 2 strands: trace 1 - t, writhe 1, so the
+        // corrected trace is (1 - t)/t = t̄ - 1 with real part cos 1 - 1
+        let mut crossing = FingerprintState::new(2);
+        crossing.update(&Generator::Sigma(1));
+        let expected = 1.0 - 1.0_f64.cos();
+        assert!((crossing.framing_corrected_magnitude() - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_wrapped_seats_count_as_collision() {
+        use crate::types::{Action, ActionType};
+
+        let mut state = FingerprintState::new(9);
+        // Seat 10 wraps onto strand 1
+        let applied = state.process_action(
+            &Action::new(Seat::new(10), ActionType::Call, 50),
+            Some(Seat::new(1)),
+        );
+        assert_eq!(applied, 0);
+        assert_eq!(state.collisions, 1);
+
+        // Same seat acting again isn't a collision
+        state.process_action(
+            &Action::new(Seat::new(10), ActionType::Bet, 50),
+            Some(Seat::new(10)),
+        );
+        assert_eq!(state.collisions, 1);
+
+        state.reset();
+        assert_eq!(state.collisions, 1, "Collisions survive hand resets");
+    }
+
+    #[test]
+    fn test_generator_histogram_spans_hands() {
+        let mut state = FingerprintState::new(4);
+        assert_eq!(state.generator_histogram, vec![0, 0, 0]);
+
+        state.update(&Generator::Sigma(1));
+        state.update(&Generator::InverseSigma(1));
+        state.update(&Generator::Sigma(3));
+        state.reset();
+        state.update(&Generator::Sigma(2));
+        // Out of range generators aren't counted
+        state.update(&Generator::Sigma(4));
+
+        assert_eq!(state.generator_histogram, vec![2, 1, 1]);
+    }
+
+    #[test]
+    fn test_stand_up_and_sit_back() {
+        use crate::types::{Action, ActionType};
+
+        let mut state = FingerprintState::new(4);
+        state.update_for_seat(&Generator::Sigma(1), 2, "Bob".to_string());
+        state.tick_activity(2);
+        state.update_for_seat(&Generator::Sigma(2), 3, "Carol".to_string());
+        state.tick_activity(3);
+        assert_eq!(state.activity_ranking(), vec![3, 2]);
+
+        state.process_action(
+            &Action::new(Seat::new(2), ActionType::StandUp, 0),
+            Some(Seat::new(3)),
+        );
+        assert!(!state.is_seat_active(2));
+        assert_eq!(state.activity_ranking(), vec![3]);
+        assert_eq!(
+            state.process_action(
+                &Action::new(Seat::new(2), ActionType::Bet, 50),
+                Some(Seat::new(3))
+            ),
+            0,
+            "Inactive seats aren't braided"
+        );
+
+        state.process_action(
+            &Action::new(Seat::new(2), ActionType::SitBack, 0),
+            Some(Seat::new(3)),
+        );
+        assert!(state.is_seat_active(2));
+        assert_eq!(
+            state.player_stats[&2].writhe, 1,
+            "Metrics survive standing up"
+        );
+        assert_eq!(state.activity_ranking(), vec![3, 2]);
+    }
+
+    #[test]
+    fn test_strand_metrics() {
+        let mut state = FingerprintState::new(3);
+        state.update(&Generator::Sigma(1));
+        state.update(&Generator::Sigma(2));
+
+        assert_eq!(
+            state.strand_metrics(1),
+            StrandMetrics {
+                writhe: 1,
+                crossing_count: 1
+            }
+        );
+        assert_eq!(
+            state.strand_metrics(2),
+            StrandMetrics {
+                writhe: 2,
+                crossing_count: 2
+            }
+        );
+        assert_eq!(
+            state.strand_metrics(3),
+            StrandMetrics {
+                writhe: 1,
+                crossing_count: 1
+            }
+        );
+
+        state.update(&Generator::InverseSigma(2));
+        assert_eq!(
+            state.strand_metrics(3),
+            StrandMetrics {
+                writhe: 0,
+                crossing_count: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_normalization_window_bounds_word() {
+        let mut state = FingerprintState::new(3);
+        state.set_normalization_window(Some(64));
+        for _ in 0..10_000 {
+            state.update(&Generator::Sigma(1));
+            state.update(&Generator::InverseSigma(1));
+            assert!(
+                state.word.len() <= 65,
+                "Buffer grew to {}",
+                state.word.len()
+            );
+        }
+        assert_eq!(state.crossing_count, 20_000);
+        let mut rest = state.word.clone();
+        normalize(&mut rest);
+        assert!(
+            rest.is_empty(),
+            "Buffer must still represent the trivial braid"
+        );
+
+        // Irreducible words are kept whole (and stay equivalent)
+        state.reset();
+        for _ in 0..3 {
+            state.update(&Generator::Sigma(1));
+            state.update(&Generator::Sigma(2));
+            state.update(&Generator::InverseSigma(2));
+        }
+        for _ in 0..200 {
+            state.update(&Generator::Sigma(2));
+        }
+        assert_eq!(state.word.len(), 203);
+        assert!(state.word.iter().take(3).all(|g| *g == Generator::Sigma(1)));
+    }
 }
@@ -0,0 +1,235 @@
+use crate::polynomial::LaurentPoly;
+use crate::types::{BraidWord, Generator};
+use std::collections::HashMap;
+
+/// A Temperley–Lieb diagram on `n` strands, stored as a perfect matching of
+/// its 2n endpoints: bottom points `0..n` and top points `n..2n`.
+/// `partner[p]` is the endpoint connected to `p`.
+type Diagram = Vec<u8>;
+
+/// Linear combination of diagrams with coefficients in `Z[A, A^-1]`.
+type TlElement = HashMap<Diagram, LaurentPoly>;
+
+/// Computes the Jones polynomial of the closure of a braid.
+///
+/// Uses the Kauffman bracket evaluated through the Temperley–Lieb algebra:
+/// each crossing is resolved as `σ_i = A·1 + A^-1·e_i` and
+/// `σ_i^-1 = A^-1·1 + A·e_i`, every closed loop is worth `d = -A^2 - A^-2`,
+/// and the braid closure of a diagram with `L` loops contributes `d^(L-1)`.
+/// The bracket is then normalized by `(-A^3)^-writhe` and `A = t^(-1/4)`.
+///
+/// Strands no generator touches close up into separate unknots and only
+/// contribute a factor of `d` each, so they are not carried through the
+/// (exponential-size) state sum.
+///
+/// # Arguments
+/// * `word` - The braid word
+/// * `strands` - Number of strands the word lives on
+///
+/// # Returns
+/// The polynomial in `t^(1/2)`: exponent `k` stands for `t^(k/2)`. Links with
+/// an even number of components have odd `k` (half-integer powers of `t`).
+pub fn jones_polynomial(word: &BraidWord, strands: usize) -> LaurentPoly {
+    let in_range = |gen: &&Generator| gen.index() >= 1 && gen.index() < strands;
+
+    // Restrict to the strands actually touched by the word
+    let (offset, active) = word.active_span(strands).unwrap_or((0, 0));
+    let idle = strands - active;
+
+    let d = loop_value();
+    let mut bracket = if active == 0 {
+        LaurentPoly::one()
+    } else {
+        let mut element: TlElement = HashMap::new();
+        element.insert(identity(active), LaurentPoly::one());
+        for gen in word.iter().filter(in_range) {
+            element = apply_crossing(
+                &element,
+                gen.index() - offset - 1,
+                gen.is_overcrossing(),
+                &d,
+            );
+        }
+
+        let mut closure = LaurentPoly::zero();
+        for (diagram, coeff) in &element {
+            let loops = closure_loops(diagram);
+            closure = &closure + &(coeff * &d.pow(loops - 1));
+        }
+        closure
+    };
+
+    // Each idle strand is an extra unknotted component
+    let extra_loops = if active == 0 {
+        idle.saturating_sub(1)
+    } else {
+        idle
+    };
+    bracket = &bracket * &d.pow(extra_loops as u32);
+
+    // Normalize by (-A^3)^(-writhe) to get an isotopy invariant
+    let writhe: i32 = word
+        .iter()
+        .filter(in_range)
+        .map(|gen| if gen.is_overcrossing() { 1 } else { -1 })
+        .sum();
+    let sign = if writhe % 2 == 0 { 1 } else { -1 };
+    let normalized = &bracket * &LaurentPoly::monomial(sign, -3 * writhe);
+
+    // A = t^(-1/4): A^k becomes t^(-k/4), i.e. exponent -k/2 in t^(1/2).
+    // All exponents of the normalized bracket are even.
+    let mut jones = LaurentPoly::zero();
+    for (exponent, coeff) in normalized.terms() {
+        debug_assert!(exponent % 2 == 0, "Jones exponent should be even in A");
+        jones.add_term(coeff, -exponent / 2);
+    }
+    jones
+}
+
+/// The loop value `d = -A^2 - A^-2`.
+fn loop_value() -> LaurentPoly {
+    &LaurentPoly::monomial(-1, 2) + &LaurentPoly::monomial(-1, -2)
+}
+
+/// The identity diagram: bottom point `i` connects straight up to top point `n + i`.
+fn identity(n: usize) -> Diagram {
+    (0..2 * n)
+        .map(|p| if p < n { (p + n) as u8 } else { (p - n) as u8 })
+        .collect()
+}
+
+/// Multiplies every diagram by the resolved crossing at position `i`
+/// (0-based, between strands `i` and `i + 1`).
+fn apply_crossing(element: &TlElement, i: usize, positive: bool, d: &LaurentPoly) -> TlElement {
+    // σ_i = A·1 + A^-1·e_i, σ_i^-1 = A^-1·1 + A·e_i
+    let (identity_power, cup_cap_power) = if positive { (1, -1) } else { (-1, 1) };
+
+    let mut next: TlElement = HashMap::with_capacity(element.len() * 2);
+    for (diagram, coeff) in element {
+        accumulate(&mut next, diagram.clone(), coeff.shift(identity_power));
+
+        let (capped, closed_loop) = stack_cup_cap(diagram, i);
+        let mut term = coeff.shift(cup_cap_power);
+        if closed_loop {
+            term = &term * d;
+        }
+        accumulate(&mut next, capped, term);
+    }
+    next.retain(|_, coeff| !coeff.is_zero());
+    next
+}
+
+/// Adds `coeff · diagram` into a Temperley–Lieb element.
+fn accumulate(element: &mut TlElement, diagram: Diagram, coeff: LaurentPoly) {
+    match element.get_mut(&diagram) {
+        Some(existing) => *existing = &*existing + &coeff,
+        None => {
+            element.insert(diagram, coeff);
+        }
+    }
+}
+
+/// Stacks the generator `e_i` (a cap joining top points `i`, `i + 1` and a
+/// new cup above them) on top of `diagram`.
+///
+/// Returns the new diagram and whether a closed loop was formed (when the
+/// two top points were already joined to each other).
+fn stack_cup_cap(diagram: &Diagram, i: usize) -> (Diagram, bool) {
+    let n = diagram.len() / 2;
+    let left = n + i;
+    let right = n + i + 1;
+
+    let mut result = diagram.clone();
+    let p = diagram[left] as usize;
+    let q = diagram[right] as usize;
+    if p == right {
+        return (result, true);
+    }
+
+    // The cap joins the old partners of the two top points ...
+    result[p] = q as u8;
+    result[q] = p as u8;
+    // ... and the cup joins the new top points to each other
+    result[left] = right as u8;
+    result[right] = left as u8;
+    (result, false)
+}
+
+/// Counts the loops formed when a diagram is closed as a braid closure
+/// (top point `n + j` joined around to bottom point `j`).
+fn closure_loops(diagram: &Diagram) -> u32 {
+    let n = diagram.len() / 2;
+    let mut visited = vec![false; diagram.len()];
+    let mut loops = 0;
+
+    for start in 0..diagram.len() {
+        if visited[start] {
+            continue;
+        }
+        loops += 1;
+        let mut p = start;
+        loop {
+            visited[p] = true;
+            // Walk along the diagram, then around the closure arc
+            let across = diagram[p] as usize;
+            visited[across] = true;
+            p = if across < n { across + n } else { across - n };
+            if visited[p] {
+                break;
+            }
+        }
+    }
+    loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jones(word: &str, strands: usize) -> String {
+        let word: BraidWord = word.parse().unwrap();
+        jones_polynomial(&word, strands).to_string_in("t", 2)
+    }
+
+    #[test]
+    fn test_unknot() {
+        assert_eq!(jones("", 1), "1");
+        assert_eq!(jones("s1", 2), "1");
+        assert_eq!(jones("s1' s2", 3), "1");
+    }
+
+    #[test]
+    fn test_unlink() {
+        // Two unlinked circles: -t^(1/2) - t^(-1/2)
+        assert_eq!(jones("", 2), "-t^-1/2 - t^1/2");
+    }
+
+    #[test]
+    fn test_trefoils() {
+        // σ1³ closes to the right-handed trefoil, σ1^-3 to its mirror
+        assert_eq!(jones("s1 s1 s1", 2), "t + t^3 - t^4");
+        assert_eq!(jones("s1' s1' s1'", 2), "-t^-4 + t^-3 + t^-1");
+    }
+
+    #[test]
+    fn test_figure_eight() {
+        // Amphichiral, so the polynomial is symmetric
+        assert_eq!(jones("s1 s2' s1 s2'", 3), "t^-2 - t^-1 + 1 - t + t^2");
+    }
+
+    #[test]
+    fn test_hopf_link() {
+        assert_eq!(jones("s1 s1", 2), "-t^1/2 - t^5/2");
+    }
+
+    #[test]
+    fn test_idle_strands_are_split_unknots() {
+        // Trefoil plus one untouched strand = trefoil ⊔ unknot
+        let with_idle = jones_polynomial(&"s1 s1 s1".parse().unwrap(), 3);
+        let trefoil = jones_polynomial(&"s1 s1 s1".parse().unwrap(), 2);
+        let unknot_factor = &LaurentPoly::monomial(-1, -1) + &LaurentPoly::monomial(-1, 1);
+        assert_eq!(with_idle, &trefoil * &unknot_factor);
+        // Shifting the word along the strands changes nothing
+        assert_eq!(jones("s3 s3 s3", 5), jones("s1 s1 s1", 5));
+    }
+}
@@ -1,9 +1,24 @@
+pub mod alexander;
 pub mod invariants;
+pub mod jones;
 pub mod mapping;
 pub mod normalization;
+pub mod polynomial;
+pub mod record;
 pub mod types;
 
-pub use invariants::{FingerprintState, IncrementalUpdate};
-pub use mapping::expand_action;
-pub use normalization::normalize;
-pub use types::{Action, ActionType, BraidWord, Generator, Seat};
+pub use invariants::{
+    validate_invariants, Checkpoint, FingerprintState, IncrementalUpdate, Invariant,
+    InvariantCheck, StrandMetrics, UndoUnsupported, WritheInvariant,
+};
+pub use mapping::{
+    expand_action, expand_action_graph, expand_action_iter, expand_action_with,
+    expand_wrap_aggressor, FlowModel, OrderingMode, SeatExpander, SeatGraph,
+};
+pub use normalization::{dehornoy_reduce, normalize, normalize_braid_relations, normalize_compact};
+pub use polynomial::LaurentPoly;
+pub use record::{HandRecord, HandSummary};
+pub use types::{
+    Action, ActionType, BraidWord, CompactBraidWord, Generator, InvalidGenerator,
+    ParseGeneratorError, Seat, SeatError, Street,
+};
@@ -1,4 +1,32 @@
 use crate::types::{Generator, Seat};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, BinaryHeap, HashMap};
+use std::sync::Arc;
+
+/// How a call against an open bet is braided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FlowModel {
+    /// Every action moves the flow from the previous seat (`expand_action`)
+    #[default]
+    Sequential,
+    /// Calls wrap around the aggressor's strand and return (`expand_wrap_aggressor`)
+    WrapAggressor,
+}
+
+/// How seats are laid out when expanding an action into generators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum OrderingMode {
+    /// Seats on a line 1..N; flow never crosses from N back to 1 directly
+    #[default]
+    Linear,
+    /// Seats on a ring; flow takes the way round with fewer seat hops,
+    /// crossing the seam with a band generator. Ring-faithful, not shorter:
+    /// a seam crossing costs 2N - 3 generators (see `expand_action_with`)
+    Circular,
+}
 
 /// Safely maps a seat number to the valid range using modulo arithmetic.
 /// 
@@ -46,41 +74,349 @@ pub(crate) fn safe_seat(seat: usize, total: usize) -> usize {
 /// Seat numbers are safely mapped using modulo arithmetic if they exceed total_seats.
 /// This prevents panics when players churn and seat numbers grow beyond the dimension.
 pub fn expand_action(from: Seat, to: Seat, total_seats: usize) -> Vec<Generator> {
+    expand_action_iter(from, to, total_seats).collect()
+}
+
+/// Lazily expands an action into Artin generators without allocating.
+///
+/// Yields exactly the same sequence as [`expand_action`], which is implemented
+/// by collecting this iterator. Prefer this variant when the generators are
+/// folded straight into a matrix or braid word.
+///
+/// # Arguments
+/// * `from` - The source seat (1-based)
+/// * `to` - The target seat (1-based)
+/// * `total_seats` - Total number of seats at the table
+pub fn expand_action_iter(
+    from: Seat,
+    to: Seat,
+    total_seats: usize,
+) -> impl Iterator<Item = Generator> {
     let from_val = from.value();
     let to_val = to.value();
 
     // Validate seat numbers are 1-based (must be > 0)
-    if from_val == 0 || to_val == 0 {
-        // Invalid seat, return empty (shouldn't happen with Seat::new, but be safe)
+    // Invalid seats yield nothing (shouldn't happen with Seat::new, but be safe)
+    let (mut current, target) = if from_val == 0 || to_val == 0 {
+        (0, 0)
+    } else {
+        // Apply safe mapping to handle seats that exceed total_seats
+        (
+            safe_seat(from_val, total_seats),
+            safe_seat(to_val, total_seats),
+        )
+    };
+
+    // Decompose the movement into adjacent swaps
+    std::iter::from_fn(move || {
+        if current < target {
+            // Moving forward: emit σ_current
+            let gen = Generator::Sigma(current);
+            current += 1;
+            Some(gen)
+        } else if current > target {
+            // Moving backward: emit σ_{current-1}^{-1}
+            let gen = Generator::InverseSigma(current - 1);
+            current -= 1;
+            Some(gen)
+        } else {
+            // Same seat, no movement
+            None
+        }
+    })
+}
+
+/// Expands an action using the given seat ordering.
+///
+/// `OrderingMode::Linear` is exactly [`expand_action`]. In
+/// `OrderingMode::Circular` the flow walks the way round the table with fewer
+/// seat hops (forward on a tie). Steps between neighbouring seats use the
+/// usual σ_i / σ_i^{-1}; the step across the seam between seat N and seat 1
+/// is the band generator crossing strands N and 1 (see [`band_generator`]),
+/// positive going forward (N → 1) and negative going backward (1 → N).
+///
+/// Circular mode trades word length for a ring-faithful seam: the band
+/// generator is a conjugate of σ_1 made of in-range generators, so every
+/// invariant sees the seam crossing as one net crossing (±1 writhe), but it
+/// is 2N - 3 generators long. A path across the seam is therefore always
+/// longer than the linear chain the other way round (9 → 1 on 9 seats: 15
+/// generators against 8) and `crossing_count` grows accordingly. Hops are
+/// counted on the ring, not generators.
+///
+/// # Arguments
+/// * `from` - The source seat (1-based)
+/// * `to` - The target seat (1-based)
+/// * `total_seats` - Total number of seats at the table
+/// * `mode` - Linear or circular seat ordering
+pub fn expand_action_with(
+    from: Seat,
+    to: Seat,
+    total_seats: usize,
+    mode: OrderingMode,
+) -> Vec<Generator> {
+    if mode == OrderingMode::Linear {
+        return expand_action(from, to, total_seats);
+    }
+    if from.value() == 0 || to.value() == 0 || total_seats == 0 {
         return Vec::new();
     }
 
-    // Apply safe mapping to handle seats that exceed total_seats
-    let from_val = safe_seat(from_val, total_seats);
-    let to_val = safe_seat(to_val, total_seats);
+    let n = total_seats;
+    let start = safe_seat(from.value(), n);
+    let target = safe_seat(to.value(), n);
+    let forward = (target + n - start) % n;
+    let backward = (start + n - target) % n;
 
-    // If same seat, no movement
-    if from_val == to_val {
-        return Vec::new();
+    let mut current = start;
+    let mut word = Vec::with_capacity(forward.min(backward));
+    if forward <= backward {
+        for _ in 0..forward {
+            // σ_current swaps seats current and current + 1; N → 1 crosses the seam
+            if current == n {
+                word.extend(band_generator(1, n, true));
+            } else {
+                word.push(Generator::Sigma(current));
+            }
+            current = Seat(current).neighbors(n).1.value();
+        }
+    } else {
+        for _ in 0..backward {
+            // Step down to the previous seat (seat 1 wraps to N across the seam)
+            let previous = Seat(current).neighbors(n).0.value();
+            if current == 1 {
+                word.extend(band_generator(1, n, false));
+            } else {
+                word.push(Generator::InverseSigma(previous));
+            }
+            current = previous;
+        }
     }
+    word
+}
 
-    let mut generators = Vec::new();
-    let mut current = from_val;
+/// The band generator crossing strands `low` and `high` (1-based, `low <
+/// high`) in front of the strands between them, written with Artin
+/// generators: σ_{high-1} ⋯ σ_{low+1} σ_low^{±1} σ_{low+1}^{-1} ⋯ σ_{high-1}^{-1}.
+///
+/// For neighbouring strands this is just σ_low^{±1}. The conjugating
+/// generators cancel in the writhe, leaving ±1.
+pub(crate) fn band_generator(low: usize, high: usize, positive: bool) -> Vec<Generator> {
+    let mut word: Vec<Generator> = (low + 1..high).rev().map(Generator::Sigma).collect();
+    word.push(if positive {
+        Generator::Sigma(low)
+    } else {
+        Generator::InverseSigma(low)
+    });
+    word.extend((low + 1..high).map(Generator::InverseSigma));
+    word
+}
 
-    // Decompose the movement into adjacent swaps
-    while current != to_val {
-        if current < to_val {
-            // Moving forward: emit σ_current
-            generators.push(Generator::Sigma(current));
-            current += 1;
-        } else {
-            // Moving backward: emit σ_{current-1}^{-1}
-            generators.push(Generator::InverseSigma(current - 1));
-            current -= 1;
+/// Which seats count as adjacent, for tables where "next to" is not seat order.
+///
+/// Flow between two seats follows a path of adjacent hops (see
+/// [`expand_action_graph`]). `SeatGraph::linear(n)` is the chain 1 - 2 - ... - n
+/// and reproduces [`expand_action`] exactly; `SeatGraph::empty(n)` starts with
+/// no adjacencies at all.
+///
+/// Strands stay in seat order, so a hop between seats i and k that aren't
+/// neighbouring numbers is encoded as the band generator crossing the two
+/// strands in front of the ones between them: 2|k - i| - 1 generators, one
+/// net crossing (see [`band_generator`]). Paths are chosen by the number of
+/// generators they emit, so such a shortcut is only taken when it is cheaper
+/// than going round by other edges, never longer than the seat-order chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeatGraph {
+    /// `neighbors[i]` holds the seats adjacent to seat i + 1
+    neighbors: Vec<BTreeSet<usize>>,
+}
+
+impl SeatGraph {
+    /// Seats 1..=total_seats on a line, each adjacent to the next.
+    pub fn linear(total_seats: usize) -> Self {
+        let mut graph = SeatGraph {
+            neighbors: vec![BTreeSet::new(); total_seats],
+        };
+        for seat in 1..total_seats {
+            graph.add_edge(Seat::new(seat), Seat::new(seat + 1));
         }
+        graph
+    }
+
+    /// Seats 1..=total_seats with no adjacencies; add them with `add_edge`.
+    pub fn empty(total_seats: usize) -> Self {
+        SeatGraph {
+            neighbors: vec![BTreeSet::new(); total_seats],
+        }
+    }
+
+    /// Number of seats in the graph.
+    pub fn total_seats(&self) -> usize {
+        self.neighbors.len()
+    }
+
+    /// Makes two seats adjacent. Seats past the table size wrap like
+    /// everywhere else; an edge from a seat to itself is ignored.
+    pub fn add_edge(&mut self, a: Seat, b: Seat) {
+        let n = self.total_seats();
+        if n == 0 || a.value() == 0 || b.value() == 0 {
+            return;
+        }
+        let (a, b) = (safe_seat(a.value(), n), safe_seat(b.value(), n));
+        if a != b {
+            self.neighbors[a - 1].insert(b);
+            self.neighbors[b - 1].insert(a);
+        }
+    }
+
+    /// Seats visited on the path from `from` to `to` (both included) that
+    /// emits the fewest generators, each hop between seats i and k costing
+    /// 2|k - i| - 1 (see the type docs).
+    ///
+    /// Ties go to the lower-numbered neighbour. Returns `None` if `to` can't
+    /// be reached.
+    pub fn shortest_path(&self, from: Seat, to: Seat) -> Option<Vec<usize>> {
+        let n = self.total_seats();
+        if n == 0 || from.value() == 0 || to.value() == 0 {
+            return None;
+        }
+        let (start, target) = (safe_seat(from.value(), n), safe_seat(to.value(), n));
+
+        // Dijkstra over generator counts, remembering where each seat was
+        // reached from
+        let mut cost = vec![usize::MAX; n + 1];
+        let mut previous = vec![0; n + 1];
+        cost[start] = 0;
+        previous[start] = start;
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        while let Some(Reverse((seat_cost, seat))) = queue.pop() {
+            if seat == target {
+                let mut path = vec![target];
+                while *path.last()? != start {
+                    path.push(previous[*path.last()?]);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            if seat_cost > cost[seat] {
+                continue;
+            }
+            for &next in &self.neighbors[seat - 1] {
+                let next_cost = seat_cost + 2 * seat.abs_diff(next) - 1;
+                if next_cost < cost[next] {
+                    cost[next] = next_cost;
+                    previous[next] = seat;
+                    queue.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Expands an action along the path through a [`SeatGraph`] that emits the
+/// fewest generators (see [`SeatGraph::shortest_path`]).
+///
+/// Each hop uses the linear convention: moving up from seat i emits σ_i,
+/// moving down to seat j emits σ_j^{-1}. For chain edges that is exactly
+/// [`expand_action`]; a shortcut edge between i and k (i < k) crosses strands
+/// i and k directly, as the band generator
+/// σ_{k-1} ⋯ σ_{i+1} σ_i^{±1} σ_{i+1}^{-1} ⋯ σ_{k-1}^{-1} (positive going up).
+/// That is 2(k - i) - 1 generators, so with the chain edges present a
+/// shortcut never wins; it is used when the table's graph has no cheaper
+/// route. Unreachable seats yield no generators.
+///
+/// # Arguments
+/// * `from` - The source seat (1-based)
+/// * `to` - The target seat (1-based)
+/// * `graph` - Seat adjacency for the table
+pub fn expand_action_graph(from: Seat, to: Seat, graph: &SeatGraph) -> Vec<Generator> {
+    let Some(path) = graph.shortest_path(from, to) else {
+        return Vec::new();
+    };
+    path.windows(2)
+        .flat_map(|hop| {
+            let (current, next) = (hop[0], hop[1]);
+            band_generator(current.min(next), current.max(next), next > current)
+        })
+        .collect()
+}
+
+/// Memoizing wrapper around [`expand_action`] for hot loops.
+///
+/// Batch processing calls `expand_action` with a small set of distinct
+/// `(from, to, total_seats)` triples, so each expansion is computed once and
+/// shared afterwards. Stateless callers should keep using the free function.
+#[derive(Debug, Clone, Default)]
+pub struct SeatExpander {
+    cache: HashMap<(usize, usize, usize), Arc<[Generator]>>,
+    hits: usize,
+}
+
+impl SeatExpander {
+    /// Creates an expander with an empty cache.
+    pub fn new() -> Self {
+        SeatExpander::default()
+    }
+
+    /// Same result as `expand_action(from, to, total_seats)`, computed at most
+    /// once per distinct triple.
+    pub fn expand(&mut self, from: Seat, to: Seat, total_seats: usize) -> Arc<[Generator]> {
+        let key = (from.value(), to.value(), total_seats);
+        if let Some(generators) = self.cache.get(&key) {
+            self.hits += 1;
+            return Arc::clone(generators);
+        }
+        let generators: Arc<[Generator]> = expand_action_iter(from, to, total_seats).collect();
+        self.cache.insert(key, Arc::clone(&generators));
+        generators
+    }
+
+    /// Number of distinct triples cached so far.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns true if nothing has been cached yet.
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Number of calls answered from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+}
+
+/// Expands a call as the caller's strand wrapping once around the aggressor's.
+///
+/// Produces the pure braid generator A_{i,j} for the two seats (i < j):
+/// σ_{j-1} ⋯ σ_{i+1} · σ_i² · σ_{i+1}^{-1} ⋯ σ_{j-1}^{-1}
+///
+/// The word is symmetric in the two seats, full-twists their strands around
+/// each other and leaves every strand in its starting position, so a run of
+/// calls against one raiser reads as repeated wraps around the raiser.
+///
+/// # Arguments
+/// * `aggressor` - The seat that opened or last raised (1-based)
+/// * `caller` - The seat calling the bet (1-based)
+/// * `total_seats` - Total number of seats at the table
+pub fn expand_wrap_aggressor(aggressor: Seat, caller: Seat, total_seats: usize) -> Vec<Generator> {
+    if aggressor.value() == 0 || caller.value() == 0 {
+        return Vec::new();
+    }
+    let a = safe_seat(aggressor.value(), total_seats);
+    let c = safe_seat(caller.value(), total_seats);
+    if a == c {
+        return Vec::new();
     }
+    let (i, j) = (a.min(c), a.max(c));
 
-    generators
+    let mut word = Vec::with_capacity(2 * (j - i));
+    word.extend(((i + 1)..j).rev().map(Generator::Sigma));
+    word.push(Generator::Sigma(i));
+    word.push(Generator::Sigma(i));
+    word.extend(((i + 1)..j).map(Generator::InverseSigma));
+    word
 }
 
 #[cfg(test)]
@@ -132,6 +468,61 @@ mod tests {
         assert_eq!(result, Vec::<Generator>::new());
     }
 
+    #[test]
+    fn test_iter_matches_vec() {
+        let pairs = [(1, 2), (2, 1), (1, 4), (4, 2), (3, 3), (10, 12), (25, 26)];
+        for (from, to) in pairs {
+            let collected: Vec<Generator> =
+                expand_action_iter(Seat::new(from), Seat::new(to), 9).collect();
+            assert_eq!(
+                collected,
+                expand_action(Seat::new(from), Seat::new(to), 9),
+                "Mismatch for {} -> {}",
+                from,
+                to
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrap_aggressor_adjacent() {
+        let result = expand_wrap_aggressor(Seat::new(2), Seat::new(3), 4);
+        assert_eq!(result, vec![Generator::Sigma(2), Generator::Sigma(2)]);
+    }
+
+    #[test]
+    fn test_wrap_aggressor_three_callers() {
+        use crate::types::BraidWord;
+
+        let raiser = Seat::new(2);
+        for caller in [4, 5, 1] {
+            let word = expand_wrap_aggressor(raiser, Seat::new(caller), 6);
+            let indices: Vec<usize> = word.iter().map(Generator::index).collect();
+            let mut reversed = indices.clone();
+            reversed.reverse();
+
+            // Symmetric: same index pattern forwards and backwards, with the
+            // double crossing between the two strands in the middle
+            assert_eq!(
+                indices, reversed,
+                "Caller {} should wrap symmetrically",
+                caller
+            );
+            assert_eq!(
+                word.iter().filter(|g| g.is_overcrossing()).count(),
+                word.len() / 2 + 1
+            );
+
+            // Every strand (raiser included) returns to its position
+            let braid = BraidWord::from_generators(word.clone());
+            assert_eq!(braid.permutation(6), (0..6).collect::<Vec<_>>());
+
+            // Swapping roles gives the same crossing
+            assert_eq!(word, expand_wrap_aggressor(Seat::new(caller), raiser, 6));
+        }
+        assert!(expand_wrap_aggressor(raiser, raiser, 6).is_empty());
+    }
+
     #[test]
     fn test_seat_modulo_wrapping() {
         // Test that seats exceeding total_seats are safely wrapped
@@ -163,5 +554,196 @@ mod tests {
         assert_eq!(safe_seat(1, 9), 1);   // Edge case: minimum
         assert_eq!(safe_seat(9, 9), 9);   // Edge case: maximum
     }
-}
 
+    #[test]
+    fn test_circular_wraps_across_seam() {
+        use Generator::{InverseSigma, Sigma};
+
+        // 4 → 1 crosses strands 4 and 1 in front of 2 and 3
+        let (four, one) = (Seat::new(4), Seat::new(1));
+        assert_eq!(
+            expand_action_with(four, one, 4, OrderingMode::Circular),
+            vec![
+                Sigma(3),
+                Sigma(2),
+                Sigma(1),
+                InverseSigma(2),
+                InverseSigma(3)
+            ]
+        );
+        assert_eq!(
+            expand_action_with(one, four, 4, OrderingMode::Circular),
+            vec![
+                Sigma(3),
+                Sigma(2),
+                InverseSigma(1),
+                InverseSigma(2),
+                InverseSigma(3)
+            ]
+        );
+
+        // Every generator exists on the table's strands and the seam is one
+        // net crossing
+        let (nine, one) = (Seat::new(9), Seat::new(1));
+        let seam = crate::types::BraidWord::try_from_generators(
+            expand_action_with(nine, one, 9, OrderingMode::Circular),
+            9,
+        )
+        .unwrap();
+        assert_eq!(seam.exponent_sums(9).iter().sum::<i32>(), 1);
+
+        // Linear walks the whole table instead
+        let linear = expand_action_with(nine, one, 9, OrderingMode::Linear);
+        assert_eq!(linear, expand_action(nine, one, 9));
+        assert_eq!(linear.len(), 8);
+    }
+
+    #[test]
+    fn test_circular_seam_is_one_step_of_writhe_but_longer() {
+        use crate::invariants::{FingerprintState, IncrementalUpdate};
+
+        // 9 → 1 is a single hop on the ring: one net crossing, 2N - 3 generators
+        let mut circular = FingerprintState::new(9);
+        for gen in expand_action_with(Seat::new(9), Seat::new(1), 9, OrderingMode::Circular) {
+            circular.update(&gen);
+        }
+        assert_eq!(circular.writhe, 1);
+        assert_eq!(circular.crossing_count, 15);
+
+        // The linear chain back across the table is shorter but braids 8 steps
+        let mut linear = FingerprintState::new(9);
+        for gen in expand_action_with(Seat::new(9), Seat::new(1), 9, OrderingMode::Linear) {
+            linear.update(&gen);
+        }
+        assert_eq!(linear.writhe, -8);
+        assert_eq!(linear.crossing_count, 8);
+    }
+
+    #[test]
+    fn test_circular_takes_shorter_direction() {
+        // 8 → 2 on 9 seats: forward 8 → 9 → 1 → 2 (3 steps) beats 6 back
+        assert_eq!(
+            expand_action_with(Seat::new(8), Seat::new(2), 9, OrderingMode::Circular),
+            [
+                vec![Generator::Sigma(8)],
+                band_generator(1, 9, true),
+                vec![Generator::Sigma(1)]
+            ]
+            .concat()
+        );
+        // 2 → 8: backward 2 → 1 → 9 → 8
+        assert_eq!(
+            expand_action_with(Seat::new(2), Seat::new(8), 9, OrderingMode::Circular),
+            [
+                vec![Generator::InverseSigma(1)],
+                band_generator(1, 9, false),
+                vec![Generator::InverseSigma(8)]
+            ]
+            .concat()
+        );
+        // Short hops away from the seam match the linear expansion
+        for (from, to) in [(3, 5), (5, 3), (4, 4)] {
+            assert_eq!(
+                expand_action_with(Seat::new(from), Seat::new(to), 9, OrderingMode::Circular),
+                expand_action(Seat::new(from), Seat::new(to), 9)
+            );
+        }
+    }
+
+    #[test]
+    fn test_linear_graph_matches_expand_action() {
+        let graph = SeatGraph::linear(6);
+        for from in 1..=8 {
+            for to in 1..=8 {
+                assert_eq!(
+                    expand_action_graph(Seat::new(from), Seat::new(to), &graph),
+                    expand_action(Seat::new(from), Seat::new(to), 6),
+                    "{} -> {}",
+                    from,
+                    to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_graph_shortcut_is_a_band_generator() {
+        // Next to the chain, the 1 - 4 shortcut (5 generators) loses to
+        // σ1 σ2 σ3, so a shortcut is never longer than the linear expansion
+        let mut chain = SeatGraph::linear(6);
+        chain.add_edge(Seat::new(1), Seat::new(4));
+        assert_eq!(
+            chain.shortest_path(Seat::new(1), Seat::new(4)),
+            Some(vec![1, 2, 3, 4])
+        );
+        assert_eq!(
+            expand_action_graph(Seat::new(1), Seat::new(4), &chain),
+            expand_action(Seat::new(1), Seat::new(4), 6)
+        );
+
+        // Without the chain, 1 and 4 are adjacent and cross in front of 2
+        // and 3 in one hop: σ3 σ2 σ1 σ2⁻¹ σ3⁻¹, a single net crossing
+        let mut graph = SeatGraph::empty(6);
+        graph.add_edge(Seat::new(1), Seat::new(4));
+        graph.add_edge(Seat::new(4), Seat::new(5));
+        assert_eq!(
+            graph.shortest_path(Seat::new(1), Seat::new(4)),
+            Some(vec![1, 4])
+        );
+        assert_eq!(
+            expand_action_graph(Seat::new(1), Seat::new(4), &graph),
+            band_generator(1, 4, true)
+        );
+
+        // 5 -> 4 is a neighbour hop, 4 -> 1 the shortcut taken downwards
+        assert_eq!(
+            expand_action_graph(Seat::new(5), Seat::new(1), &graph),
+            vec![
+                Generator::InverseSigma(4),
+                Generator::Sigma(3),
+                Generator::Sigma(2),
+                Generator::InverseSigma(1),
+                Generator::InverseSigma(2),
+                Generator::InverseSigma(3),
+            ]
+        );
+
+        // A detour through cheap hops beats a long shortcut: 1 - 2 - 3 (2
+        // generators) rather than 1 - 3 directly (3)
+        let mut detour = SeatGraph::empty(3);
+        detour.add_edge(Seat::new(1), Seat::new(3));
+        detour.add_edge(Seat::new(1), Seat::new(2));
+        detour.add_edge(Seat::new(2), Seat::new(3));
+        assert_eq!(
+            detour.shortest_path(Seat::new(1), Seat::new(3)),
+            Some(vec![1, 2, 3])
+        );
+
+        // Seats with no route between them braid nothing
+        let split = SeatGraph::empty(3);
+        assert!(expand_action_graph(Seat::new(1), Seat::new(3), &split).is_empty());
+    }
+
+    #[test]
+    fn test_seat_expander_caches() {
+        let mut expander = SeatExpander::new();
+        let triples = [(1, 5, 9), (5, 1, 9), (3, 3, 9), (9, 2, 9), (12, 4, 9)];
+
+        for _ in 0..10_000 {
+            for (from, to, total) in triples {
+                let cached = expander.expand(Seat::new(from), Seat::new(to), total);
+                assert_eq!(
+                    &cached[..],
+                    &expand_action(Seat::new(from), Seat::new(to), total)[..]
+                );
+            }
+        }
+        assert_eq!(expander.len(), triples.len());
+        assert_eq!(expander.hits(), 10_000 * triples.len() - triples.len());
+
+        // Hits share the cached expansion rather than recomputing it
+        let a = expander.expand(Seat::new(1), Seat::new(5), 9);
+        let b = expander.expand(Seat::new(1), Seat::new(5), 9);
+        assert!(Arc::ptr_eq(&a, &b));
+    }
+}
@@ -1,4 +1,4 @@
-use crate::types::{BraidWord, Generator};
+use crate::types::{BraidWord, CompactBraidWord, Generator};
 
 /// Reduces a braid word by removing adjacent inverse pairs (Free Reduction).
 /// 
@@ -68,10 +68,177 @@ pub fn normalize(word: &mut BraidWord) {
     }
 }
 
+/// Rewrites a braid word into a canonical representative using the braid relations.
+///
+/// Repeatedly applies, until none match:
+/// - Free reduction: σ_i · σ_i^{-1} → (empty)
+/// - Far commutation: σ_j · σ_i → σ_i · σ_j when j - i ≥ 2 (lower index first)
+/// - Braid relation: σ_{i+1} σ_i σ_{i+1} → σ_i σ_{i+1} σ_i (and the same for
+///   three inverses), preferring the lower index on the outside
+///
+/// Every rule lowers (length, sum of indices, number of out-of-order commuting
+/// pairs) lexicographically, so rewriting terminates, and the result is a fixed
+/// point of every rule, so the function is idempotent.
+///
+/// This is a cheap canonicalizer, not a full solution to the word problem:
+/// some equal braids (e.g. ones needing mixed-sign relations) can still end up
+/// with different representatives.
+///
+/// # Arguments
+/// * `word` - The braid word to rewrite (modified in place)
+pub fn normalize_braid_relations(word: &mut BraidWord) {
+    let mut generators: Vec<Generator> = word.iter().copied().collect();
+
+    while let Some(rewritten) = rewrite_once(&generators) {
+        generators = rewritten;
+    }
+
+    word.replace_generators(generators);
+}
+
+/// Applies the first matching rewrite rule, or returns `None` at a fixed point.
+fn rewrite_once(generators: &[Generator]) -> Option<Vec<Generator>> {
+    for i in 0..generators.len() {
+        let a = generators[i];
+
+        if let Some(&b) = generators.get(i + 1) {
+            // Free reduction
+            if a.index() == b.index() && a.is_overcrossing() != b.is_overcrossing() {
+                let mut out = generators.to_vec();
+                out.drain(i..i + 2);
+                return Some(out);
+            }
+
+            // Far commutation into ascending index order
+            if a.index() >= b.index() + 2 {
+                let mut out = generators.to_vec();
+                out.swap(i, i + 1);
+                return Some(out);
+            }
+        }
+
+        // Braid relation σ_{k+1} σ_k σ_{k+1} → σ_k σ_{k+1} σ_k (same signs)
+        if let (Some(&b), Some(&c)) = (generators.get(i + 1), generators.get(i + 2)) {
+            let same_sign = a.is_overcrossing() == b.is_overcrossing()
+                && b.is_overcrossing() == c.is_overcrossing();
+            if same_sign && a == c && b.index() + 1 == a.index() {
+                let mut out = generators.to_vec();
+                out[i] = b;
+                out[i + 1] = a;
+                out[i + 2] = b;
+                return Some(out);
+            }
+        }
+    }
+    None
+}
+
+/// Reduces a braid word with Dehornoy's handle reduction.
+///
+/// A σ_i-handle is a subword σ_i^e · u · σ_i^{-e} where `u` contains no σ_j
+/// with j ≤ i. Reducing it deletes the two ends and replaces every σ_{i+1}^d
+/// in `u` by σ_{i+1}^{-e} σ_i^d σ_{i+1}^e, which leaves the braid unchanged
+/// (free reduction is the case of an empty `u`).
+///
+/// The handle that ends first is always reduced, so it never contains a
+/// nested handle and the process terminates. The result contains no handle,
+/// which means it is either empty or has a lowest generator appearing with
+/// only one sign; in particular a braid is trivial iff it reduces to the
+/// empty word.
+///
+/// The intermediate words can grow before they shrink, so prefer `normalize`
+/// when only cheap cleanup is needed.
+///
+/// # Arguments
+/// * `word` - The braid word to reduce (modified in place)
+pub fn dehornoy_reduce(word: &mut BraidWord) {
+    let mut generators: Vec<Generator> = word.iter().copied().collect();
+
+    while let Some((start, end)) = first_handle(&generators) {
+        let head = generators[start];
+        let (index, e) = (head.index(), head.is_overcrossing());
+
+        let mut reduced = Vec::with_capacity(generators.len() + 2 * (end - start));
+        reduced.extend_from_slice(&generators[..start]);
+        for &gen in &generators[start + 1..end] {
+            if gen.index() == index + 1 {
+                reduced.push(signed(index + 1, !e));
+                reduced.push(signed(index, gen.is_overcrossing()));
+                reduced.push(signed(index + 1, e));
+            } else {
+                reduced.push(gen);
+            }
+        }
+        reduced.extend_from_slice(&generators[end + 1..]);
+        generators = reduced;
+    }
+
+    word.replace_generators(generators);
+}
+
+/// Finds the handle with the leftmost end, as `(start, end)` positions.
+fn first_handle(generators: &[Generator]) -> Option<(usize, usize)> {
+    for end in 1..generators.len() {
+        let last = generators[end];
+        for start in (0..end).rev() {
+            let gen = generators[start];
+            if gen.index() < last.index() {
+                break;
+            }
+            if gen.index() == last.index() {
+                if gen.is_overcrossing() != last.is_overcrossing() {
+                    return Some((start, end));
+                }
+                break;
+            }
+        }
+    }
+    None
+}
+
+/// σ_index when `positive`, σ_index^{-1} otherwise.
+fn signed(index: usize, positive: bool) -> Generator {
+    if positive {
+        Generator::Sigma(index)
+    } else {
+        Generator::InverseSigma(index)
+    }
+}
+
+/// Free reduction on a run-length encoded word, without expanding it.
+///
+/// Adjacent runs on the same index are merged by adding their powers
+/// (σ_i^a · σ_i^b = σ_i^{a+b}) and runs whose power reaches zero are dropped.
+/// A stack is used so that cancelling one run can expose a new merge with the
+/// run before it, giving the same result as `normalize` in a single pass.
+///
+/// # Arguments
+/// * `word` - The compact braid word to reduce (modified in place)
+pub fn normalize_compact(word: &mut CompactBraidWord) {
+    let mut stack: Vec<(usize, i32)> = Vec::with_capacity(word.len());
+
+    for &(index, power) in word.runs() {
+        if power == 0 {
+            continue;
+        }
+        match stack.last_mut() {
+            Some((top_index, top_power)) if *top_index == index => {
+                *top_power += power;
+                if *top_power == 0 {
+                    stack.pop();
+                }
+            }
+            _ => stack.push((index, power)),
+        }
+    }
+
+    word.replace_runs(stack);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{BraidWord, Generator};
+    use crate::types::{BraidWord, CompactBraidWord, Generator};
 
     #[test]
     fn test_normalize_empty() {
@@ -173,5 +340,144 @@ mod tests {
         assert_eq!(generators[0], Generator::Sigma(1));
         assert_eq!(generators[1], Generator::Sigma(1));
     }
-}
 
+    #[test]
+    fn test_normalize_compact_cancels_runs() {
+        let mut word = CompactBraidWord::from_runs(vec![(1, 3), (1, -3), (2, 1)]);
+        normalize_compact(&mut word);
+        assert_eq!(word.runs(), &[(2, 1)]);
+    }
+
+    #[test]
+    fn test_normalize_compact_matches_normalize() {
+        // σ_1, σ_2, σ_1, σ_1^{-1}, σ_2^{-1}, σ_1 (same as test_normalize_complex_sequence)
+        let mut word: BraidWord = "s1 s2 s1 s1' s2' s1".parse().unwrap();
+        let mut compact = CompactBraidWord::from(&word);
+
+        normalize(&mut word);
+        normalize_compact(&mut compact);
+        assert_eq!(compact.runs(), &[(1, 2)]);
+        assert_eq!(compact.to_braid_word(), word);
+    }
+
+    #[test]
+    fn test_word_times_inverse_normalizes_to_empty() {
+        let mut word: BraidWord = "s1 s3 s2' s2' s4 s1' s3 s2".parse().unwrap();
+        let inverse = word.inverse();
+        assert_eq!(inverse.len(), word.len());
+        assert_eq!(inverse.iter().next(), Some(&Generator::InverseSigma(2)));
+
+        word.extend(&inverse);
+        normalize(&mut word);
+        assert!(word.is_empty(), "w · w⁻¹ should reduce to empty");
+    }
+
+    #[test]
+    fn test_braid_relations_equal_words_meet() {
+        // σ2 σ1 σ2 σ4 and σ4 σ1 σ2 σ1 are equal via the braid and commutation relations
+        let mut a: BraidWord = "s2 s1 s2 s4".parse().unwrap();
+        let mut b: BraidWord = "s4 s1 s2 s1".parse().unwrap();
+        normalize_braid_relations(&mut a);
+        normalize_braid_relations(&mut b);
+        assert_eq!(a, b);
+        assert_eq!(a.to_string(), "σ1 σ2 σ1 σ4");
+
+        // Inverse braid relation plus free reduction
+        let mut c: BraidWord = "s3 s2' s1' s2' s3'".parse().unwrap();
+        let mut d: BraidWord = "s3 s1' s2' s1' s3'".parse().unwrap();
+        normalize_braid_relations(&mut c);
+        normalize_braid_relations(&mut d);
+        assert_eq!(c, d);
+    }
+
+    #[test]
+    fn test_braid_relations_idempotent() {
+        let mut word: BraidWord = "s3 s1 s2 s3 s2 s1' s4 s4' s2".parse().unwrap();
+        normalize_braid_relations(&mut word);
+        let once = word.clone();
+        normalize_braid_relations(&mut word);
+        assert_eq!(word, once);
+    }
+
+    #[test]
+    fn test_braid_relations_preserve_braid() {
+        use crate::invariants::{FingerprintState, IncrementalUpdate};
+
+        // Rewriting must not change the braid, so its Burau image is unchanged
+        let original: BraidWord = "s3 s1 s2 s3 s2 s1' s4 s4' s2".parse().unwrap();
+        let mut word = original.clone();
+        normalize_braid_relations(&mut word);
+        assert!(word.len() < original.len());
+
+        let burau = |w: &BraidWord| {
+            let mut state = FingerprintState::new(5);
+            for gen in w.iter() {
+                state.update(gen);
+            }
+            state.burau_matrix
+        };
+        let diff = burau(&word) - burau(&original);
+        assert!(diff.iter().all(|c| c.norm() < 1e-9));
+    }
+
+    #[test]
+    fn test_dehornoy_reduces_hidden_identities() {
+        // σ1 σ2 σ1 · (σ2 σ1 σ2)⁻¹ is trivial by the braid relation
+        let mut word: BraidWord = "s1 s2 s1 s2' s1' s2'".parse().unwrap();
+        let mut freely = word.clone();
+        normalize(&mut freely);
+        assert_eq!(freely.len(), 6, "Free reduction can't see this one");
+        dehornoy_reduce(&mut word);
+        assert!(word.is_empty());
+
+        // Far generators commute: [σ1, σ3] = 1
+        assert!("s1 s3 s1' s3'".parse::<BraidWord>().unwrap().is_trivial());
+        // A conjugate of a braid relation
+        assert!("s2 s3 s1 s2 s1 s2' s1' s2' s3' s2'"
+            .parse::<BraidWord>()
+            .unwrap()
+            .is_trivial());
+        assert!(BraidWord::new().is_trivial());
+    }
+
+    #[test]
+    fn test_dehornoy_keeps_nontrivial_braids() {
+        for word in ["s1", "s1 s2 s1' s2'", "s1 s2' s1 s2'", "s2 s1 s1' s3 s2'"] {
+            let word: BraidWord = word.parse().unwrap();
+            assert!(!word.is_trivial(), "{} is not trivial", word);
+        }
+    }
+
+    #[test]
+    fn test_dehornoy_decides_equality() {
+        // Two hands give the same braid iff a · b⁻¹ is trivial
+        let a: BraidWord = "s2 s1 s2 s3".parse().unwrap();
+        let b: BraidWord = "s1 s2 s1 s3".parse().unwrap();
+        let c: BraidWord = "s1 s2 s3 s2".parse().unwrap();
+        let mut ab = a.clone();
+        ab.extend(&b.inverse());
+        assert!(ab.is_trivial());
+        let mut ac = a.clone();
+        ac.extend(&c.inverse());
+        assert!(!ac.is_trivial());
+    }
+
+    #[test]
+    fn test_dehornoy_preserves_braid() {
+        use crate::invariants::{FingerprintState, IncrementalUpdate};
+
+        let original: BraidWord = "s1 s2' s3 s1' s2 s2 s3' s1 s2'".parse().unwrap();
+        let mut word = original.clone();
+        dehornoy_reduce(&mut word);
+
+        let burau = |w: &BraidWord| {
+            let mut state = FingerprintState::new(4);
+            for gen in w.iter() {
+                state.update(gen);
+            }
+            state.burau_matrix
+        };
+        let diff = burau(&word) - burau(&original);
+        assert!(diff.iter().all(|c| c.norm() < 1e-9));
+    }
+}
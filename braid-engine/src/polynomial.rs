@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Laurent polynomial with integer coefficients in a single variable.
+///
+/// Stored sparsely as `exponent -> coefficient`; zero coefficients are never
+/// kept, so two equal polynomials always compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LaurentPoly {
+    terms: BTreeMap<i32, i64>,
+}
+
+impl LaurentPoly {
+    /// The zero polynomial.
+    pub fn zero() -> Self {
+        LaurentPoly::default()
+    }
+
+    /// The constant polynomial 1.
+    pub fn one() -> Self {
+        LaurentPoly::monomial(1, 0)
+    }
+
+    /// The single term `coeff · x^exponent`.
+    pub fn monomial(coeff: i64, exponent: i32) -> Self {
+        let mut poly = LaurentPoly::zero();
+        poly.add_term(coeff, exponent);
+        poly
+    }
+
+    /// Adds `coeff · x^exponent` in place.
+    pub fn add_term(&mut self, coeff: i64, exponent: i32) {
+        if coeff == 0 {
+            return;
+        }
+        let entry = self.terms.entry(exponent).or_insert(0);
+        *entry += coeff;
+        if *entry == 0 {
+            self.terms.remove(&exponent);
+        }
+    }
+
+    /// Returns true if every coefficient is zero.
+    pub fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns the coefficient of `x^exponent`.
+    pub fn coeff(&self, exponent: i32) -> i64 {
+        self.terms.get(&exponent).copied().unwrap_or(0)
+    }
+
+    /// Iterates over `(exponent, coefficient)` pairs in ascending exponent order.
+    pub fn terms(&self) -> impl Iterator<Item = (i32, i64)> + '_ {
+        self.terms.iter().map(|(&e, &c)| (e, c))
+    }
+
+    /// Lowest exponent with a non-zero coefficient.
+    pub fn min_exponent(&self) -> Option<i32> {
+        self.terms.keys().next().copied()
+    }
+
+    /// Highest exponent with a non-zero coefficient.
+    pub fn max_exponent(&self) -> Option<i32> {
+        self.terms.keys().next_back().copied()
+    }
+
+    /// Multiplies by `x^shift`.
+    pub fn shift(&self, shift: i32) -> Self {
+        LaurentPoly {
+            terms: self.terms.iter().map(|(&e, &c)| (e + shift, c)).collect(),
+        }
+    }
+
+    /// Substitutes `x -> x^factor` (`factor` may be negative).
+    pub fn scale_exponents(&self, factor: i32) -> Self {
+        let mut poly = LaurentPoly::zero();
+        for (e, c) in self.terms() {
+            poly.add_term(c, e * factor);
+        }
+        poly
+    }
+
+    /// Raises the polynomial to a non-negative power.
+    pub fn pow(&self, exponent: u32) -> Self {
+        let mut result = LaurentPoly::one();
+        for _ in 0..exponent {
+            result = &result * self;
+        }
+        result
+    }
+
+    /// Exact division, returning `None` if `divisor` does not divide `self`
+    /// (or is zero).
+    pub fn div_exact(&self, divisor: &LaurentPoly) -> Option<LaurentPoly> {
+        let (divisor_min, divisor_max) = (divisor.min_exponent()?, divisor.max_exponent()?);
+        let lead = divisor.coeff(divisor_min);
+
+        let mut quotient = LaurentPoly::zero();
+        let mut remainder = self.clone();
+        let highest = self.max_exponent().unwrap_or(0) - divisor_max;
+
+        // Cancel the lowest term of the remainder until nothing is left
+        while let Some(low) = remainder.min_exponent() {
+            let exponent = low - divisor_min;
+            let coeff = remainder.coeff(low);
+            if exponent > highest || coeff % lead != 0 {
+                return None;
+            }
+            let term = LaurentPoly::monomial(coeff / lead, exponent);
+            remainder = &remainder - &(&term * divisor);
+            quotient = &quotient + &term;
+        }
+        Some(quotient)
+    }
+
+    /// Formats the polynomial in `var`, dividing every exponent by
+    /// `denominator` (e.g. 2 for polynomials in `var^(1/2)`).
+    ///
+    /// Terms are listed in ascending order: `-t^-2 + t^-1 + 1`, `t^1/2 - t^5/2`.
+    pub fn to_string_in(&self, var: &str, denominator: i32) -> String {
+        if self.is_zero() {
+            return "0".to_string();
+        }
+
+        let mut out = String::new();
+        for (i, (exponent, coeff)) in self.terms().enumerate() {
+            let sign = if coeff < 0 { "-" } else { "+" };
+            if i == 0 {
+                if coeff < 0 {
+                    out.push('-');
+                }
+            } else {
+                out.push_str(&format!(" {} ", sign));
+            }
+
+            let magnitude = coeff.unsigned_abs();
+            let power = format_exponent(exponent, denominator);
+            match power {
+                None => out.push_str(&magnitude.to_string()),
+                Some(power) => {
+                    if magnitude != 1 {
+                        out.push_str(&magnitude.to_string());
+                    }
+                    out.push_str(var);
+                    out.push_str(&power);
+                }
+            }
+        }
+        out
+    }
+}
+
+/// Formats `exponent / denominator` as a suffix (`""`, `"^2"`, `"^-3/2"`),
+/// or `None` for the constant term.
+fn format_exponent(exponent: i32, denominator: i32) -> Option<String> {
+    if exponent == 0 {
+        return None;
+    }
+    if exponent % denominator == 0 {
+        let whole = exponent / denominator;
+        if whole == 1 {
+            return Some(String::new());
+        }
+        return Some(format!("^{}", whole));
+    }
+    // Reduce the fraction (denominators are tiny, so a gcd loop is plenty)
+    let mut gcd = denominator;
+    while exponent % gcd != 0 || denominator % gcd != 0 {
+        gcd -= 1;
+    }
+    Some(format!("^{}/{}", exponent / gcd, denominator / gcd))
+}
+
+impl fmt::Display for LaurentPoly {
+    /// Formats in the variable `t`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_in("t", 1))
+    }
+}
+
+impl Add for &LaurentPoly {
+    type Output = LaurentPoly;
+
+    fn add(self, other: &LaurentPoly) -> LaurentPoly {
+        let mut result = self.clone();
+        for (e, c) in other.terms() {
+            result.add_term(c, e);
+        }
+        result
+    }
+}
+
+impl Sub for &LaurentPoly {
+    type Output = LaurentPoly;
+
+    fn sub(self, other: &LaurentPoly) -> LaurentPoly {
+        self + &(-other)
+    }
+}
+
+impl Neg for &LaurentPoly {
+    type Output = LaurentPoly;
+
+    fn neg(self) -> LaurentPoly {
+        LaurentPoly {
+            terms: self.terms.iter().map(|(&e, &c)| (e, -c)).collect(),
+        }
+    }
+}
+
+impl Mul for &LaurentPoly {
+    type Output = LaurentPoly;
+
+    fn mul(self, other: &LaurentPoly) -> LaurentPoly {
+        let mut result = LaurentPoly::zero();
+        for (e1, c1) in self.terms() {
+            for (e2, c2) in other.terms() {
+                result.add_term(c1 * c2, e1 + e2);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        // (1 + t)(1 - t) = 1 - t^2
+        let a = &LaurentPoly::one() + &LaurentPoly::monomial(1, 1);
+        let b = &LaurentPoly::one() - &LaurentPoly::monomial(1, 1);
+        let product = &a * &b;
+        assert_eq!(product, &LaurentPoly::one() - &LaurentPoly::monomial(1, 2));
+        assert!((&product - &product).is_zero());
+        assert_eq!(a.pow(2).coeff(1), 2);
+    }
+
+    #[test]
+    fn test_div_exact() {
+        // (1 + t^3) / (1 + t) = 1 - t + t^2
+        let numerator = &LaurentPoly::one() + &LaurentPoly::monomial(1, 3);
+        let divisor = &LaurentPoly::one() + &LaurentPoly::monomial(1, 1);
+        assert_eq!(
+            numerator.div_exact(&divisor).unwrap().to_string(),
+            "1 - t + t^2"
+        );
+
+        // Works with negative exponents
+        let shifted = numerator.shift(-5);
+        assert_eq!(
+            shifted.div_exact(&divisor).unwrap().to_string(),
+            "t^-5 - t^-4 + t^-3"
+        );
+
+        assert!(divisor.div_exact(&numerator).is_none());
+        assert!(numerator.div_exact(&LaurentPoly::zero()).is_none());
+        assert!(LaurentPoly::monomial(3, 0)
+            .div_exact(&LaurentPoly::monomial(2, 0))
+            .is_none());
+    }
+
+    #[test]
+    fn test_formatting() {
+        let mut poly = LaurentPoly::monomial(-1, -2);
+        poly.add_term(1, -1);
+        poly.add_term(1, 0);
+        assert_eq!(poly.to_string(), "-t^-2 + t^-1 + 1");
+
+        let mut half = LaurentPoly::monomial(-1, 1);
+        half.add_term(-3, 5);
+        assert_eq!(half.to_string_in("t", 2), "-t^1/2 - 3t^5/2");
+        assert_eq!(LaurentPoly::monomial(2, 4).to_string_in("t", 2), "2t^2");
+        assert_eq!(LaurentPoly::zero().to_string(), "0");
+    }
+}
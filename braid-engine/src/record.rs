@@ -0,0 +1,159 @@
+use crate::invariants::FingerprintState;
+use crate::types::{Action, ActionType, BraidWord, Seat};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Invariants of a finished hand, as reported in a `HandRecord`.
+///
+/// Scalars only, so it is cheap to keep thousands of them; see
+/// `FingerprintState::into_summary`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandSummary {
+    pub writhe: i32,
+    pub crossing_count: usize,
+    pub burau_trace_magnitude: f64,
+    /// Net crossings per 1-based seat, for seats with per-seat metrics
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub seat_writhe: BTreeMap<usize, i32>,
+}
+
+/// Single-document export of one hand: who sat where, what they did, and the
+/// braid it produced. Serializes to JSON for archiving and sharing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandRecord {
+    /// `(seat, name)` for every seat that acted, in seat order
+    pub seats: Vec<(usize, String)>,
+    /// The hand's actions in order (street markers included, resets not)
+    pub actions: Vec<Action>,
+    /// Braid word of the whole hand
+    pub final_word: BraidWord,
+    pub metrics: HandSummary,
+}
+
+impl HandRecord {
+    /// Splits a session into hands at every Reset and replays each one with
+    /// the sequential flow model and no other options. To export a hand as it
+    /// was braided live (another flow model, minimum amounts, resets on fold),
+    /// record it with `from_state` instead.
+    ///
+    /// # Arguments
+    /// * `actions` - The session's actions, in order
+    /// * `dimension` - Number of strands/seats
+    /// * `name` - Display name for a seat
+    ///
+    /// # Returns
+    /// One record per hand; empty hands (e.g. back-to-back resets) are skipped.
+    pub fn split_hands(
+        actions: &[Action],
+        dimension: usize,
+        name: impl Fn(Seat) -> String,
+    ) -> Vec<HandRecord> {
+        actions
+            .split(|action| action.action_type == ActionType::Reset)
+            .filter(|hand| !hand.is_empty())
+            .map(|hand| HandRecord::from_hand(hand, dimension, &name))
+            .collect()
+    }
+
+    /// Rebuilds the hand's final state from its stored actions rather than
+    /// trusting `metrics`, so a loaded record can be checked or stepped
+    /// through again.
+    ///
+    /// # Arguments
+    /// * `dimension` - Number of strands/seats (as used for the export)
+    pub fn replay(&self, dimension: usize) -> FingerprintState {
+        replay_hand(&self.actions, dimension).0
+    }
+
+    /// Records a hand from the state that braided it, e.g. the live
+    /// fingerprint right before it is reset, so the record matches the steps
+    /// that were reported whatever options shaped the braid.
+    ///
+    /// # Arguments
+    /// * `actions` - The hand's actions in order (without the Reset)
+    /// * `seats` - `(seat, name)` for every seat that acted, in seat order
+    /// * `fingerprint` - The state after the hand's last action
+    pub fn from_state(
+        actions: Vec<Action>,
+        seats: Vec<(usize, String)>,
+        fingerprint: &FingerprintState,
+    ) -> HandRecord {
+        HandRecord {
+            seats,
+            actions,
+            final_word: fingerprint.word.clone(),
+            metrics: fingerprint.summary(),
+        }
+    }
+
+    /// Builds the record for a single hand (which must not contain a Reset).
+    fn from_hand(
+        actions: &[Action],
+        dimension: usize,
+        name: impl Fn(Seat) -> String,
+    ) -> HandRecord {
+        let (fingerprint, seats) = replay_hand(actions, dimension);
+        HandRecord {
+            seats: seats
+                .into_iter()
+                .map(|seat| (seat, name(Seat::new(seat))))
+                .collect(),
+            actions: actions.to_vec(),
+            final_word: fingerprint.word.clone(),
+            metrics: fingerprint.into_summary(),
+        }
+    }
+}
+
+/// Runs a hand's actions through a fresh fingerprint with the sequential flow
+/// model, returning it with the seats that moved the flow.
+fn replay_hand(actions: &[Action], dimension: usize) -> (FingerprintState, BTreeSet<usize>) {
+    let mut fingerprint = FingerprintState::new(dimension);
+    let mut current_seat = None;
+    let mut seats = BTreeSet::new();
+    for action in actions {
+        fingerprint.process_action(action, current_seat);
+        // Markers and seats sitting out don't move the flow
+        let is_marker = matches!(
+            action.action_type,
+            ActionType::StreetBoundary | ActionType::StandUp | ActionType::SitBack
+        );
+        if is_marker || !fingerprint.is_seat_active(action.seat.value()) {
+            continue;
+        }
+        current_seat = Some(action.seat);
+        seats.insert(action.seat.value());
+    }
+    (fingerprint, seats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_hands_on_reset() {
+        let actions = [
+            Action::new(Seat::new(1), ActionType::Reset, 0),
+            Action::new(Seat::new(1), ActionType::Bet, 20),
+            Action::new(Seat::new(2), ActionType::Call, 20),
+            Action::new(Seat::new(1), ActionType::Reset, 0),
+            Action::new(Seat::new(1), ActionType::Reset, 0),
+            Action::new(Seat::new(3), ActionType::Raise, 60),
+        ];
+        let hands = HandRecord::split_hands(&actions, 3, |seat| format!("P{}", seat.value()));
+
+        assert_eq!(hands.len(), 2);
+        assert_eq!(hands[0].actions.len(), 2);
+        assert_eq!(
+            hands[0].seats,
+            vec![(1, "P1".to_string()), (2, "P2".to_string())]
+        );
+        assert_eq!(
+            hands[0].final_word,
+            BraidWord::from_generators(vec![crate::types::Generator::Sigma(1)])
+        );
+        assert_eq!(hands[1].seats, vec![(3, "P3".to_string())]);
+        assert!(hands[1].final_word.is_empty());
+    }
+}
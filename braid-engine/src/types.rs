@@ -1,14 +1,44 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
 /// Seat represents a player's position at the table.
 /// Uses 1-based indexing for mathematical operations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Serializes as the bare seat number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Seat(pub usize);
 
+/// Error returned when constructing an invalid `Seat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeatError {
+    /// Seats are 1-based, so 0 is not a valid seat
+    Zero,
+}
+
+impl fmt::Display for SeatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeatError::Zero => write!(f, "Seat must be 1-based (seat > 0)"),
+        }
+    }
+}
+
+impl std::error::Error for SeatError {}
+
 impl Seat {
     /// Creates a new Seat with 1-based indexing.
-    /// Panics if seat is 0.
+    /// Panics if seat is 0; use `try_new` for untrusted input.
     pub fn new(seat: usize) -> Self {
-        assert!(seat > 0, "Seat must be 1-based (seat > 0)");
-        Seat(seat)
+        Seat::try_new(seat).expect("Seat must be 1-based (seat > 0)")
+    }
+
+    /// Creates a new Seat with 1-based indexing, returning an error for 0.
+    pub fn try_new(seat: usize) -> Result<Self, SeatError> {
+        if seat == 0 {
+            return Err(SeatError::Zero);
+        }
+        Ok(Seat(seat))
     }
 
     /// Returns the 1-based seat number.
@@ -20,10 +50,29 @@ impl Seat {
     pub fn index(&self) -> usize {
         self.0 - 1
     }
+
+    /// Returns the seats on either side of this one, `(previous, next)`,
+    /// treating the table as a ring (seat 1 sits next to seat `total_seats`).
+    ///
+    /// A single generator swaps a seat with either neighbour: σ_{i-1} with
+    /// the previous seat and σ_i with the next. Across the seam (seat N and
+    /// seat 1) the swap is the band generator σ_{N-1} ⋯ σ_2 σ_1 σ_2⁻¹ ⋯
+    /// σ_{N-1}⁻¹ instead (see `expand_action_with`). Seats past
+    /// `total_seats` are wrapped first, as in `expand_action`.
+    pub fn neighbors(&self, total_seats: usize) -> (Seat, Seat) {
+        if total_seats == 0 {
+            return (*self, *self);
+        }
+        let seat = crate::mapping::safe_seat(self.0, total_seats);
+        let previous = if seat == 1 { total_seats } else { seat - 1 };
+        let next = seat % total_seats + 1;
+        (Seat(previous), Seat(next))
+    }
 }
 
 /// Action type in poker.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ActionType {
     Fold,
     Check,
@@ -33,36 +82,86 @@ pub enum ActionType {
     ReRaise,
     AllIn,
     Reset, // Represents "starting hand" or explicit reset
+    StreetBoundary, // A new betting round (flop, turn, river) within the same hand
+    StandUp,        // Player leaves their seat for now (seat kept, marked inactive)
+    SitBack,        // Player returns to their seat
+}
+
+/// Betting round within a hand. PokerNow logs mark each new street with a
+/// board line, parsed as `ActionType::StreetBoundary`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize,
+)]
+pub enum Street {
+    #[default]
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    /// The street after this one. River stays River (e.g. a second run of
+    /// the board deals another "Flop" line with no betting).
+    pub fn next(self) -> Street {
+        match self {
+            Street::Preflop => Street::Flop,
+            Street::Flop => Street::Turn,
+            Street::Turn | Street::River => Street::River,
+        }
+    }
 }
 
 /// An action taken by a player.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Action {
     pub seat: Seat,
     pub action_type: ActionType,
-    pub amount: u64,
+    /// Chips moved; negative for refunds and corrections
+    pub amount: i64,
 }
 
 impl Action {
-    pub fn new(seat: Seat, action_type: ActionType, amount: u64) -> Self {
+    pub fn new(seat: Seat, action_type: ActionType, amount: i64) -> Self {
         Action {
             seat,
             action_type,
             amount,
         }
     }
+
+    /// Returns true for a call, bet, raise or all-in of less than `min_amount`
+    /// chips (antes, tiny straddles). Folds, checks and markers never are.
+    pub fn is_below_min_amount(&self, min_amount: i64) -> bool {
+        matches!(
+            self.action_type,
+            ActionType::Call
+                | ActionType::Bet
+                | ActionType::Raise
+                | ActionType::ReRaise
+                | ActionType::AllIn
+        ) && self.amount < min_amount
+    }
 }
 
 /// Artin generator for braid groups.
 /// Sigma(i) represents σ_i (overcrossing)
 /// InverseSigma(i) represents σ_i^{-1} (undercrossing)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Generator {
     Sigma(usize),
     InverseSigma(usize),
 }
 
 impl Generator {
+    /// Returns the inverse generator (σ_i <-> σ_i^{-1}).
+    pub fn inverse(&self) -> Generator {
+        match self {
+            Generator::Sigma(i) => Generator::InverseSigma(*i),
+            Generator::InverseSigma(i) => Generator::Sigma(*i),
+        }
+    }
+
     /// Returns the index of the generator (1-based).
     pub fn index(&self) -> usize {
         match self {
@@ -81,8 +180,105 @@ impl Generator {
     }
 }
 
-/// A braid word is a sequence of generators.
+impl fmt::Display for Generator {
+    /// Renders σ notation: `σ1` for an overcrossing, `σ2⁻¹` for an undercrossing.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Generator::Sigma(i) => write!(f, "σ{}", i),
+            Generator::InverseSigma(i) => write!(f, "σ{}⁻¹", i),
+        }
+    }
+}
+
+/// Error returned when parsing a `Generator` or `BraidWord` from text.
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGeneratorError {
+    /// The token does not start with `s`, `S` or `σ`, or has trailing junk
+    Malformed(String),
+    /// The token has no index after the prefix
+    MissingIndex(String),
+    /// Generator indices are 1-based, so `s0` is rejected
+    ZeroIndex(String),
+}
+
+impl fmt::Display for ParseGeneratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGeneratorError::Malformed(token) => write!(f, "Malformed generator: '{}'", token),
+            ParseGeneratorError::MissingIndex(token) => {
+                write!(f, "Missing generator index: '{}'", token)
+            }
+            ParseGeneratorError::ZeroIndex(token) => {
+                write!(f, "Generator index must be >= 1: '{}'", token)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseGeneratorError {}
+
+impl FromStr for Generator {
+    type Err = ParseGeneratorError;
+
+    /// Parses `s1`, `S1`, `σ1` (overcrossing) and `s1'`, `σ1⁻¹` (undercrossing).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let token = s.trim();
+        let rest = token
+            .strip_prefix(['s', 'S', 'σ'])
+            .ok_or_else(|| ParseGeneratorError::Malformed(token.to_string()))?;
+
+        let digits_end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (digits, suffix) = rest.split_at(digits_end);
+        if digits.is_empty() {
+            return Err(ParseGeneratorError::MissingIndex(token.to_string()));
+        }
+
+        let index: usize = digits
+            .parse()
+            .map_err(|_| ParseGeneratorError::Malformed(token.to_string()))?;
+        if index == 0 {
+            return Err(ParseGeneratorError::ZeroIndex(token.to_string()));
+        }
+
+        match suffix {
+            "" => Ok(Generator::Sigma(index)),
+            "'" | "⁻¹" => Ok(Generator::InverseSigma(index)),
+            _ => Err(ParseGeneratorError::Malformed(token.to_string())),
+        }
+    }
+}
+
+/// Error returned by `BraidWord::try_from_generators` and
+/// `FingerprintState::try_update` for a generator that does not exist on the
+/// declared number of strands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidGenerator {
+    /// The first offending generator
+    pub generator: Generator,
+    /// Strand count the word was checked against
+    pub strands: usize,
+}
+
+impl fmt::Display for InvalidGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Generator {} is out of range for {} strands (valid: σ1..σ{})",
+            self.generator,
+            self.strands,
+            self.strands.saturating_sub(1)
+        )
+    }
+}
+
+impl std::error::Error for InvalidGenerator {}
+
+/// A braid word is a sequence of generators.
+/// Serializes as a flat array of generators, e.g. `[{"Sigma":1},{"InverseSigma":2}]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct BraidWord {
     generators: Vec<Generator>,
 }
@@ -100,16 +296,54 @@ impl BraidWord {
         BraidWord { generators }
     }
 
+    /// Creates a braid word, checking every generator index is in `[1, strands-1]`.
+    ///
+    /// Returns the first offending generator. Prefer this over `from_generators`
+    /// for user-supplied input, since out-of-range generators are silently
+    /// ignored by the invariants.
+    pub fn try_from_generators(
+        generators: Vec<Generator>,
+        strands: usize,
+    ) -> Result<Self, InvalidGenerator> {
+        if let Some(&generator) = generators
+            .iter()
+            .find(|g| g.index() == 0 || g.index() >= strands)
+        {
+            return Err(InvalidGenerator { generator, strands });
+        }
+        Ok(BraidWord { generators })
+    }
+
     /// Appends a generator to the braid word.
     pub fn push(&mut self, gen: Generator) {
         self.generators.push(gen);
     }
 
+    /// Removes and returns the last generator, if any.
+    pub fn pop(&mut self) -> Option<Generator> {
+        self.generators.pop()
+    }
+
     /// Extends the braid word with generators from another braid word.
     pub fn extend(&mut self, other: &BraidWord) {
         self.generators.extend_from_slice(&other.generators);
     }
 
+    /// Returns the inverse braid word.
+    ///
+    /// The generators are reversed and each one is flipped, so that
+    /// `w · w.inverse()` freely reduces to the empty word.
+    pub fn inverse(&self) -> BraidWord {
+        BraidWord {
+            generators: self
+                .generators
+                .iter()
+                .rev()
+                .map(Generator::inverse)
+                .collect(),
+        }
+    }
+
     /// Returns an iterator over the generators.
     pub fn iter(&self) -> impl Iterator<Item = &Generator> {
         self.generators.iter()
@@ -125,6 +359,92 @@ impl BraidWord {
         self.generators.is_empty()
     }
 
+    /// Returns true if the word represents the identity braid.
+    ///
+    /// Unlike `is_empty`, this sees through the braid relations: it runs
+    /// Dehornoy handle reduction on a copy of the word.
+    pub fn is_trivial(&self) -> bool {
+        let mut word = self.clone();
+        crate::normalization::dehornoy_reduce(&mut word);
+        word.is_empty()
+    }
+
+    /// Returns true if both words represent the same braid, i.e. if
+    /// `self · other⁻¹` is trivial. Words that differ only by braid
+    /// relations (`σ1 σ2 σ1` vs `σ2 σ1 σ2`) compare equal.
+    pub fn reduced_eq(&self, other: &BraidWord) -> bool {
+        let mut quotient = self.clone();
+        quotient.extend(&other.inverse());
+        quotient.is_trivial()
+    }
+
+    /// Returns the exponent sum for each generator index.
+    ///
+    /// Entry `k - 1` holds the number of σ_k minus the number of σ_k^{-1}
+    /// in the word (the abelianization of the braid, split by index).
+    /// Generators outside `[1, strands-1]` are ignored.
+    ///
+    /// # Arguments
+    /// * `strands` - Number of strands the word lives on
+    pub fn exponent_sums(&self, strands: usize) -> Vec<i32> {
+        let mut sums = vec![0; strands.saturating_sub(1)];
+        for gen in &self.generators {
+            let k = gen.index();
+            if k == 0 || k > sums.len() {
+                continue;
+            }
+            match gen {
+                Generator::Sigma(_) => sums[k - 1] += 1,
+                Generator::InverseSigma(_) => sums[k - 1] -= 1,
+            }
+        }
+        sums
+    }
+
+    /// Returns the permutation the braid induces on its strands.
+    ///
+    /// Each generator σ_k^{±1} is applied as the adjacent transposition of
+    /// positions `k - 1` and `k` (over/under is ignored). Entry `i` of the
+    /// result is the final 0-based position of the strand that started at `i`.
+    /// Generators outside `[1, strands-1]` are ignored.
+    ///
+    /// # Arguments
+    /// * `strands` - Number of strands the word lives on
+    pub fn permutation(&self, strands: usize) -> Vec<usize> {
+        // strand_at[p] = strand currently occupying position p
+        let mut strand_at: Vec<usize> = (0..strands).collect();
+        for gen in &self.generators {
+            let k = gen.index();
+            if k == 0 || k >= strands {
+                continue;
+            }
+            strand_at.swap(k - 1, k);
+        }
+
+        let mut final_position = vec![0; strands];
+        for (position, &strand) in strand_at.iter().enumerate() {
+            final_position[strand] = position;
+        }
+        final_position
+    }
+
+    /// Returns `(offset, active)`: the smallest block of strands the word
+    /// actually touches, as the 0-based index of its first strand and its size.
+    /// Generators outside `[1, strands-1]` are ignored; a word touching
+    /// nothing returns `None`.
+    pub(crate) fn active_span(&self, strands: usize) -> Option<(usize, usize)> {
+        let indices = self
+            .generators
+            .iter()
+            .map(Generator::index)
+            .filter(|&k| k >= 1 && k < strands);
+        let (lo, hi) = indices.fold(None, |span, k| match span {
+            None => Some((k, k)),
+            Some((lo, hi)) => Some((k.min(lo), k.max(hi))),
+        })?;
+        Some((lo - 1, hi - lo + 2))
+    }
+
     /// Replaces the generators in this braid word.
     /// Used internally by normalization.
     pub(crate) fn replace_generators(&mut self, generators: Vec<Generator>) {
@@ -138,9 +458,305 @@ impl Default for BraidWord {
     }
 }
 
+/// Run-length encoded braid word.
+///
+/// Each run `(i, p)` stands for σ_i^p, so long power-runs like σ_1^{40} take
+/// a single entry. A negative power is a run of undercrossings.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CompactBraidWord {
+    runs: Vec<(usize, i32)>,
+}
+
+impl CompactBraidWord {
+    /// Creates a new empty compact braid word.
+    pub fn new() -> Self {
+        CompactBraidWord { runs: Vec::new() }
+    }
+
+    /// Creates a compact braid word from `(index, power)` runs, stored as given.
+    pub fn from_runs(runs: Vec<(usize, i32)>) -> Self {
+        CompactBraidWord { runs }
+    }
+
+    /// Returns the `(index, power)` runs.
+    pub fn runs(&self) -> &[(usize, i32)] {
+        &self.runs
+    }
+
+    /// Returns the number of runs (not generators).
+    pub fn len(&self) -> usize {
+        self.runs.len()
+    }
+
+    /// Returns true if there are no runs.
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Expands the runs into a full braid word.
+    pub fn to_braid_word(&self) -> BraidWord {
+        let mut word = BraidWord::new();
+        for &(index, power) in &self.runs {
+            let gen = if power > 0 {
+                Generator::Sigma(index)
+            } else {
+                Generator::InverseSigma(index)
+            };
+            for _ in 0..power.unsigned_abs() {
+                word.push(gen);
+            }
+        }
+        word
+    }
+
+    /// Replaces the runs in this word.
+    /// Used internally by normalization.
+    pub(crate) fn replace_runs(&mut self, runs: Vec<(usize, i32)>) {
+        self.runs = runs;
+    }
+}
+
+impl From<&BraidWord> for CompactBraidWord {
+    /// Collapses consecutive generators with the same index into one run.
+    fn from(word: &BraidWord) -> Self {
+        let mut runs: Vec<(usize, i32)> = Vec::new();
+        for gen in word.iter() {
+            let step = if gen.is_overcrossing() { 1 } else { -1 };
+            match runs.last_mut() {
+                Some((index, power)) if *index == gen.index() => *power += step,
+                _ => runs.push((gen.index(), step)),
+            }
+        }
+        CompactBraidWord { runs }
+    }
+}
+
+impl fmt::Display for BraidWord {
+    /// Renders the generators separated by spaces, e.g. `σ1 σ2 σ2⁻¹`.
+    /// The empty word renders as an empty string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, gen) in self.generators.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", gen)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for BraidWord {
+    type Err = ParseGeneratorError;
+
+    /// Parses whitespace-separated generators, e.g. `s1 s2' σ3⁻¹`.
+    /// An empty (or all-whitespace) string yields the empty word.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let generators = s
+            .split_whitespace()
+            .map(Generator::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(BraidWord { generators })
+    }
+}
+
 impl From<Vec<Generator>> for BraidWord {
     fn from(generators: Vec<Generator>) -> Self {
         BraidWord::from_generators(generators)
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seat_try_new() {
+        assert_eq!(Seat::try_new(0), Err(SeatError::Zero));
+        assert_eq!(Seat::try_new(5), Ok(Seat(5)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Seat must be 1-based")]
+    fn test_seat_new_panics_on_zero() {
+        Seat::new(0);
+    }
+
+    #[test]
+    fn test_seat_neighbors() {
+        assert_eq!(Seat::new(1).neighbors(6), (Seat::new(6), Seat::new(2)));
+        assert_eq!(Seat::new(3).neighbors(6), (Seat::new(2), Seat::new(4)));
+        assert_eq!(Seat::new(6).neighbors(6), (Seat::new(5), Seat::new(1)));
+        // Seats past the table wrap first (8 is seat 2 on 6 seats)
+        assert_eq!(Seat::new(8).neighbors(6), (Seat::new(1), Seat::new(3)));
+    }
+
+    #[test]
+    fn test_exponent_sums() {
+        let word = BraidWord::from_generators(vec![
+            Generator::Sigma(1),
+            Generator::Sigma(1),
+            Generator::InverseSigma(2),
+        ]);
+        assert_eq!(word.exponent_sums(3), vec![2, -1]);
+    }
+
+    #[test]
+    fn test_exponent_sums_ignores_out_of_range() {
+        let word = BraidWord::from_generators(vec![
+            Generator::Sigma(0),
+            Generator::Sigma(1),
+            Generator::InverseSigma(5),
+        ]);
+        assert_eq!(word.exponent_sums(3), vec![1, 0]);
+        assert!(BraidWord::new().exponent_sums(1).is_empty());
+    }
+
+    #[test]
+    fn test_permutation() {
+        // σ1 σ2⁻¹ moves strand 0 to position 2, shifting the others left (the
+        // permutation ignores crossing signs)
+        let word =
+            BraidWord::from_generators(vec![Generator::Sigma(1), Generator::InverseSigma(2)]);
+        assert_eq!(word.permutation(4), vec![2, 0, 1, 3]);
+        assert_eq!(BraidWord::new().permutation(3), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_generator_display() {
+        assert_eq!(Generator::Sigma(1).to_string(), "σ1");
+        assert_eq!(Generator::InverseSigma(2).to_string(), "σ2⁻¹");
+        assert_eq!(Generator::Sigma(11).to_string(), "σ11");
+    }
+
+    #[test]
+    fn test_braid_word_display() {
+        let word = BraidWord::from_generators(vec![
+            Generator::Sigma(1),
+            Generator::Sigma(2),
+            Generator::InverseSigma(2),
+        ]);
+        assert_eq!(word.to_string(), "σ1 σ2 σ2⁻¹");
+        assert_eq!(
+            BraidWord::from_generators(vec![Generator::InverseSigma(3)]).to_string(),
+            "σ3⁻¹"
+        );
+        assert_eq!(BraidWord::new().to_string(), "");
+    }
+
+    #[test]
+    fn test_generator_from_str_notations() {
+        assert_eq!("s1".parse(), Ok(Generator::Sigma(1)));
+        assert_eq!("S2".parse(), Ok(Generator::Sigma(2)));
+        assert_eq!("s3'".parse(), Ok(Generator::InverseSigma(3)));
+        assert_eq!("σ1".parse(), Ok(Generator::Sigma(1)));
+        assert_eq!("σ12⁻¹".parse(), Ok(Generator::InverseSigma(12)));
+    }
+
+    #[test]
+    fn test_generator_from_str_errors() {
+        assert!(matches!(
+            "s0".parse::<Generator>(),
+            Err(ParseGeneratorError::ZeroIndex(_))
+        ));
+        assert!(matches!(
+            "s".parse::<Generator>(),
+            Err(ParseGeneratorError::MissingIndex(_))
+        ));
+        assert!(matches!(
+            "x1".parse::<Generator>(),
+            Err(ParseGeneratorError::Malformed(_))
+        ));
+        assert!(matches!(
+            "s1x".parse::<Generator>(),
+            Err(ParseGeneratorError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_braid_word_from_str() {
+        let word: BraidWord = "  s1 S2 s2'\tσ3⁻¹  ".parse().unwrap();
+        assert_eq!(
+            word,
+            BraidWord::from_generators(vec![
+                Generator::Sigma(1),
+                Generator::Sigma(2),
+                Generator::InverseSigma(2),
+                Generator::InverseSigma(3),
+            ])
+        );
+
+        // Display output parses back to the same word
+        assert_eq!(word.to_string().parse::<BraidWord>().unwrap(), word);
+
+        assert_eq!("".parse::<BraidWord>().unwrap(), BraidWord::new());
+        assert_eq!("   ".parse::<BraidWord>().unwrap(), BraidWord::new());
+        assert!("s1 s0 s2".parse::<BraidWord>().is_err());
+    }
+
+    #[test]
+    fn test_reduced_eq_sees_braid_relations() {
+        let a: BraidWord = "s1 s2 s1".parse().unwrap();
+        let b: BraidWord = "s2 s1 s2".parse().unwrap();
+        let c: BraidWord = "s1 s2 s2".parse().unwrap();
+        assert!(a.reduced_eq(&b));
+        assert!(a.reduced_eq(&a));
+        assert!(!a.reduced_eq(&c));
+        assert!(BraidWord::new().reduced_eq(&"s1 s1'".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_try_from_generators_valid() {
+        let gens = vec![
+            Generator::Sigma(1),
+            Generator::InverseSigma(3),
+            Generator::Sigma(2),
+        ];
+        let word = BraidWord::try_from_generators(gens.clone(), 4).unwrap();
+        assert_eq!(word, BraidWord::from_generators(gens));
+    }
+
+    #[test]
+    fn test_try_from_generators_rejects_sigma_zero() {
+        let gens = vec![Generator::Sigma(1), Generator::InverseSigma(0)];
+        assert_eq!(
+            BraidWord::try_from_generators(gens, 4),
+            Err(InvalidGenerator {
+                generator: Generator::InverseSigma(0),
+                strands: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_from_generators_rejects_sigma_strands() {
+        let gens = vec![Generator::Sigma(4), Generator::Sigma(5)];
+        let err = BraidWord::try_from_generators(gens, 4).unwrap_err();
+        assert_eq!(
+            err.generator,
+            Generator::Sigma(4),
+            "Reports the first bad generator"
+        );
+        assert!(err.to_string().contains("σ4"));
+    }
+
+    #[test]
+    fn test_compact_round_trip() {
+        let word: BraidWord = "s1 s1 s1 s2' s2' s1".parse().unwrap();
+        let compact = CompactBraidWord::from(&word);
+        assert_eq!(compact.runs(), &[(1, 3), (2, -2), (1, 1)]);
+        assert_eq!(compact.to_braid_word(), word);
+    }
+
+    #[test]
+    fn test_braid_word_json_round_trip() {
+        let word =
+            BraidWord::from_generators(vec![Generator::Sigma(1), Generator::InverseSigma(2)]);
+
+        let json = serde_json::to_string(&word).unwrap();
+        assert_eq!(json, r#"[{"Sigma":1},{"InverseSigma":2}]"#);
+
+        let decoded: BraidWord = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, word);
+    }
+}
@@ -1,6 +1,6 @@
 use braid_engine::{
-    expand_action, normalize, Action, ActionType, BraidWord, FingerprintState, Generator,
-    IncrementalUpdate, Seat,
+    expand_action, normalize, validate_invariants, Action, ActionType, BraidWord, FingerprintState,
+    Generator, HandRecord, IncrementalUpdate, Seat, Street,
 };
 use nalgebra::DMatrix;
 
@@ -18,7 +18,7 @@ fn test_toy_hand() {
     const TOTAL_SEATS: usize = 4;
 
     // Define the action sequence
-    let actions = vec![
+    let actions = [
         Action::new(Seat::new(1), ActionType::Raise, 100),
         Action::new(Seat::new(3), ActionType::Call, 100),
         Action::new(Seat::new(2), ActionType::Raise, 200),
@@ -92,12 +92,72 @@ fn test_toy_hand() {
         "Burau matrix should not be identity after processing a hand"
     );
 
+    // Every crossing is undone by the end of the hand, so each strand is back
+    // where it started
+    assert_eq!(
+        braid_word.permutation(TOTAL_SEATS),
+        vec![0, 1, 2, 3],
+        "Balanced toy hand should induce the identity permutation"
+    );
+
+    // Internal consistency self-check (as run by --validate-invariants)
+    for check in validate_invariants(&fingerprint, &braid_word) {
+        assert!(
+            check.passed,
+            "Invariant check '{}' failed: {}",
+            check.name, check.detail
+        );
+    }
+
     println!("✓ Toy Hand test passed!");
     println!("  Braid word length: {}", braid_word.len());
     println!("  Writhe: {}", fingerprint.writhe);
     println!("  Crossing count: {}", fingerprint.crossing_count);
 }
 
+/// The toy hand exported as a single `HandRecord` document.
+#[test]
+fn test_toy_hand_record() {
+    let actions = [
+        Action::new(Seat::new(1), ActionType::Raise, 100),
+        Action::new(Seat::new(3), ActionType::Call, 100),
+        Action::new(Seat::new(2), ActionType::Raise, 200),
+        Action::new(Seat::new(4), ActionType::Call, 200),
+        Action::new(Seat::new(1), ActionType::ReRaise, 400),
+    ];
+
+    let hands = HandRecord::split_hands(&actions, 4, |seat| format!("Player {}", seat.value()));
+    assert_eq!(hands.len(), 1);
+    let record = &hands[0];
+
+    assert_eq!(record.actions.len(), 5);
+    assert_eq!(
+        record.seats,
+        vec![
+            (1, "Player 1".to_string()),
+            (2, "Player 2".to_string()),
+            (3, "Player 3".to_string()),
+            (4, "Player 4".to_string()),
+        ]
+    );
+    assert_eq!(record.final_word.len(), 8);
+    assert_eq!(record.metrics.writhe, 0);
+    assert_eq!(record.metrics.crossing_count, 8);
+
+    // Round-trips through JSON
+    let json = serde_json::to_string(record).unwrap();
+    assert!(json.contains(r#""action_type":"re_raise""#), "{}", json);
+    let parsed: HandRecord = serde_json::from_str(&json).unwrap();
+    assert_eq!(&parsed, record);
+
+    // Replaying the loaded record recomputes the stored metrics
+    let replayed = parsed.replay(4);
+    assert_eq!(replayed.writhe, 0);
+    assert_eq!(replayed.crossing_count, 8);
+    assert_eq!(replayed.word, parsed.final_word);
+    assert_eq!(replayed.into_summary(), parsed.metrics);
+}
+
 /// Additional test to verify the action flow logic more explicitly.
 #[test]
 fn test_action_flow_sequence() {
@@ -178,3 +238,112 @@ fn test_normalization() {
     );
 }
 
+/// A heads-up (dimension 2) fingerprint only has one generator, σ1: every
+/// action braids σ1 or its inverse, and σ2 is rejected as a braid word.
+#[test]
+fn test_heads_up_dimension_uses_only_sigma1() {
+    let mut fingerprint = FingerprintState::new(2);
+    let mut current = Seat::new(1);
+    // Seat 3 wraps back onto a heads-up table's two strands
+    for seat in [2, 1, 3, 2] {
+        let next = Seat::new(seat);
+        for gen in expand_action(current, next, fingerprint.dimension()) {
+            assert_eq!(gen.index(), 1, "Only σ1 exists on 2 strands, got {}", gen);
+            fingerprint.update(&gen);
+        }
+        current = next;
+    }
+    assert_eq!(
+        fingerprint.generator_histogram,
+        [fingerprint.crossing_count]
+    );
+
+    assert!(BraidWord::try_from_generators(
+        vec![Generator::Sigma(1), Generator::InverseSigma(1)],
+        2
+    )
+    .is_ok());
+    let err = BraidWord::try_from_generators(vec![Generator::Sigma(2)], 2).unwrap_err();
+    assert_eq!(err.generator, Generator::Sigma(2));
+}
+
+/// Crossings are attributed to the street they were braided on: street
+/// boundaries (flop, turn) split the hand, and a reset starts over at preflop.
+#[test]
+fn test_street_metrics() {
+    let mut fingerprint = FingerprintState::new(4);
+    let mut current_seat = None;
+    let hand = [
+        // Preflop: 1 -> 3 (σ1 σ2), 3 -> 2 (σ2⁻¹)
+        Action::new(Seat::new(1), ActionType::Raise, 100),
+        Action::new(Seat::new(3), ActionType::Call, 100),
+        Action::new(Seat::new(2), ActionType::Fold, 0),
+        Action::new(Seat::new(1), ActionType::StreetBoundary, 0),
+        // Flop: 2 -> 4 (σ2 σ3)
+        Action::new(Seat::new(4), ActionType::Bet, 200),
+        Action::new(Seat::new(1), ActionType::StreetBoundary, 0),
+        // Turn: 4 -> 3 (σ3⁻¹)
+        Action::new(Seat::new(3), ActionType::Check, 0),
+    ];
+    for action in &hand {
+        fingerprint.process_action(action, current_seat);
+        if action.action_type != ActionType::StreetBoundary {
+            current_seat = Some(action.seat);
+        }
+    }
+
+    let streets = fingerprint.street_metrics();
+    assert_eq!(fingerprint.street(), Street::Turn);
+    assert_eq!(streets[&Street::Preflop], (1, 3));
+    assert_eq!(streets[&Street::Flop], (2, 2));
+    assert_eq!(streets[&Street::Turn], (-1, 1));
+    assert!(!streets.contains_key(&Street::River));
+    let total: usize = streets.values().map(|(_, crossings)| crossings).sum();
+    assert_eq!(total, fingerprint.crossing_count);
+
+    fingerprint.process_action(
+        &Action::new(Seat::new(1), ActionType::Reset, 0),
+        current_seat,
+    );
+    assert_eq!(fingerprint.street(), Street::Preflop);
+    assert!(fingerprint.street_metrics().is_empty());
+}
+
+/// The linking matrix counts signed crossings per pair of adjacent strands
+/// and stays symmetric throughout the toy hand.
+#[test]
+fn test_toy_hand_linking_matrix() {
+    let generators = [
+        Generator::Sigma(1),
+        Generator::Sigma(2),
+        Generator::InverseSigma(2),
+        Generator::Sigma(2),
+        Generator::Sigma(3),
+        Generator::InverseSigma(3),
+        Generator::InverseSigma(2),
+        Generator::InverseSigma(1),
+    ];
+    let mut fingerprint = FingerprintState::new(4);
+
+    // After 1 -> 3 -> 2 -> 3: σ1 once, σ2 net once, σ3 not yet
+    for gen in &generators[..4] {
+        fingerprint.update(gen);
+    }
+    let linking = fingerprint.linking_matrix();
+    assert_eq!(
+        linking,
+        &linking.transpose(),
+        "Linking matrix should be symmetric"
+    );
+    assert_eq!(linking[(0, 1)], 1);
+    assert_eq!(linking[(1, 2)], 1);
+    assert_eq!(linking[(2, 3)], 0);
+    assert_eq!(linking[(0, 2)], 0, "Only adjacent strands cross");
+    assert!((0..4).all(|i| linking[(i, i)] == 0));
+
+    // The rest of the hand undoes every pair's crossings
+    for gen in &generators[4..] {
+        fingerprint.update(gen);
+    }
+    assert_eq!(fingerprint.linking_matrix(), &DMatrix::zeros(4, 4));
+}
@@ -1,136 +1,505 @@
-use braid_engine::{expand_action, Action, ActionType, FingerprintState, IncrementalUpdate};
-use csv::ReaderBuilder;
-use poker_parser::{parse_record, pokernow, SeatResolver};
-use std::fs::File;
-use std::io::BufReader;
+use crate::config::{Config, InputEncoding, OutputFormat};
+use crate::pacing::Pacer;
+use crate::report::{round_metric, PlayerReport};
+use braid_engine::{
+    expand_action, validate_invariants, Action, ActionType, FingerprintState, Generator,
+    HandRecord, IncrementalUpdate, Seat,
+};
+use poker_parser::{parse_stream, StreamOptions};
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::io::{BufWriter, Read, Write};
 
 /// JSON output structure for each step
-#[derive(serde::Serialize)]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct StepOutput {
     step: usize,
     action: String,
     writhe: i32,
     burau_trace_magnitude: f64,
+    /// `--explain`: the seat movement and generators behind this step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explain: Option<String>,
 }
 
-/// Runs the CLI mode
-pub fn run_cli() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() < 2 {
-        eprintln!("Usage: {} [--format pokernow] [--reset-on-fold] <csv_file_path>", args[0]);
-        std::process::exit(1);
+/// End-of-run statistics, printed to stderr as one JSON object unless
+/// `--quiet` is set.
+#[derive(Debug, Default, serde::Serialize)]
+struct RunSummary {
+    /// Hand resets (headers, plus folds with `--reset-on-fold`)
+    hands: usize,
+    /// Steps written (braided actions, not markers)
+    actions: usize,
+    final_writhe: i32,
+    /// Writhe furthest from 0 at any step
+    peak_writhe: i32,
+    peak_burau_trace_magnitude: f64,
+    /// Distinct players (distinct seats with `--seats-are-numeric`)
+    players: usize,
+}
+
+impl RunSummary {
+    /// Accounts for one processed action and what it emitted.
+    fn record(
+        &mut self,
+        action_type: ActionType,
+        emitted: &Option<Emitted>,
+        fingerprint: &FingerprintState,
+        options: StepOptions,
+    ) {
+        match emitted {
+            Some(Emitted::Marker(_)) if action_type == ActionType::Reset => self.hands += 1,
+            Some(Emitted::Step(step)) => {
+                self.actions += 1;
+                if options.reset_on_fold && action_type == ActionType::Fold {
+                    self.hands += 1;
+                }
+                if step.writhe.abs() > self.peak_writhe.abs() {
+                    self.peak_writhe = step.writhe;
+                }
+                self.peak_burau_trace_magnitude = self
+                    .peak_burau_trace_magnitude
+                    .max(fingerprint.burau_trace_magnitude());
+            }
+            _ => {}
+        }
     }
+}
 
-    // Check for flags
-    let mut format_pokernow = false;
-    let mut reset_on_fold = false;
-    let mut csv_path = None;
-    
-    let mut i = 1;
-    while i < args.len() {
-        if args[i] == "--format" && i + 1 < args.len() {
-            if args[i + 1] == "pokernow" {
-                format_pokernow = true;
+/// What a processed action contributes to the output.
+enum Emitted {
+    /// Plain-text hand/street marker (JSON lines output only)
+    Marker(&'static str),
+    Step(StepOutput),
+}
+
+/// Writes step results as JSON lines or as one JSON array.
+///
+/// The array is streamed (brackets and commas written by hand) so long logs
+/// aren't buffered; call `finish` to close it.
+struct StepWriter<W: Write> {
+    out: W,
+    format: OutputFormat,
+    /// Send hand/street markers to stderr so `out` holds only JSON
+    markers_to_stderr: bool,
+    steps_written: usize,
+}
+
+impl<W: Write> StepWriter<W> {
+    fn new(out: W, format: OutputFormat) -> Self {
+        StepWriter {
+            out,
+            format,
+            markers_to_stderr: false,
+            steps_written: 0,
+        }
+    }
+
+    fn emit(&mut self, emitted: Emitted) -> Result<(), Box<dyn std::error::Error>> {
+        match (self.format, emitted) {
+            (_, Emitted::Marker(marker)) if self.markers_to_stderr => eprintln!("{}", marker),
+            (OutputFormat::Jsonl, Emitted::Marker(marker)) => writeln!(self.out, "{}", marker)?,
+            (OutputFormat::Jsonl, Emitted::Step(step)) => {
+                writeln!(self.out, "{}", serde_json::to_string(&step)?)?
             }
-            i += 2;
-        } else if args[i] == "--reset-on-fold" {
-            reset_on_fold = true;
-            i += 1;
-        } else if csv_path.is_none() {
-            csv_path = Some(&args[i]);
-            i += 1;
-        } else {
-            i += 1;
+            // Markers aren't JSON; hands still show up as `step` restarting at 1
+            (OutputFormat::Array, Emitted::Marker(_)) => {}
+            (OutputFormat::Array, Emitted::Step(step)) => {
+                let separator = if self.steps_written == 0 { "[" } else { "," };
+                writeln!(self.out, "{}", separator)?;
+                write!(self.out, "{}", serde_json::to_string(&step)?)?;
+                self.steps_written += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if self.format == OutputFormat::Array {
+            if self.steps_written == 0 {
+                write!(self.out, "[")?;
+            }
+            writeln!(self.out, "\n]")?;
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// How each action is processed and its step written out.
+#[derive(Debug, Clone, Copy, Default)]
+struct StepOptions {
+    /// Reset the fingerprint on every fold
+    reset_on_fold: bool,
+    /// Betting actions below this amount move the flow but braid nothing
+    min_amount: Option<i64>,
+    /// Round float metrics to this many decimals (full precision if unset)
+    round_digits: Option<u32>,
+    /// Annotate each step with the seat movement it braided
+    explain: bool,
+}
+
+impl StepOptions {
+    fn from_config(config: &Config) -> Self {
+        StepOptions {
+            reset_on_fold: config.reset_on_fold,
+            min_amount: config.min_amount,
+            round_digits: config.round_digits,
+            explain: config.explain,
+        }
+    }
+}
+
+/// `--export-hands`: the run's hands, each recorded from the live fingerprint
+/// when it resets, so a record braids exactly like the steps that were
+/// written (`--min-amount`, `--reset-on-fold`, `--player`, ...).
+#[derive(Debug, Default)]
+struct HandExport {
+    hands: Vec<HandRecord>,
+    /// Actions of the hand in progress (street markers included, resets not)
+    actions: Vec<Action>,
+    /// Seats that moved the flow in the hand in progress
+    seats: BTreeSet<usize>,
+}
+
+impl HandExport {
+    /// Adds a processed action to the hand in progress.
+    fn push(&mut self, action: Action, fingerprint: &FingerprintState) {
+        // Markers and seats sitting out don't move the flow
+        let is_marker = matches!(
+            action.action_type,
+            ActionType::StreetBoundary | ActionType::StandUp | ActionType::SitBack
+        );
+        if !is_marker && fingerprint.is_seat_active(action.seat.value()) {
+            self.seats.insert(action.seat.value());
+        }
+        self.actions.push(action);
+    }
+
+    /// Closes the hand in progress with the fingerprint's braid, unless it
+    /// has no actions (e.g. back-to-back resets).
+    fn finish_hand(&mut self, fingerprint: &FingerprintState) {
+        if self.actions.is_empty() {
+            return;
         }
+        let seats = std::mem::take(&mut self.seats)
+            .into_iter()
+            .map(|seat| (seat, String::new()))
+            .collect();
+        self.hands.push(HandRecord::from_state(
+            std::mem::take(&mut self.actions),
+            seats,
+            fingerprint,
+        ));
     }
 
-    let csv_path = csv_path.ok_or("Missing CSV file path")?;
+    /// The finished hands, with their seats named once the whole log is read.
+    fn into_records(mut self, name: impl Fn(Seat) -> String) -> Vec<HandRecord> {
+        for hand in &mut self.hands {
+            for (seat, seat_name) in &mut hand.seats {
+                *seat_name = name(Seat::new(*seat));
+            }
+        }
+        self.hands
+    }
+}
+
+/// Parser options for the CLI's input log.
+fn stream_options(config: &Config) -> StreamOptions {
+    StreamOptions {
+        button_relative: config.button_relative,
+        total_seats: config.dimension,
+        reset_sentinel: config.reset_sentinel.clone(),
+        seats_are_numeric: config.seats_are_numeric,
+        disambiguate_names: config.disambiguate_names,
+    }
+}
+
+/// `--compare` verdict: how the second hand differs from the first.
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct CompareOutput {
+    /// Writhe of the second hand minus the first
+    writhe_delta: i32,
+    /// Crossing count of the second hand minus the first
+    crossing_delta: i64,
+    /// Both hands braid the same element of the braid group
+    reduced_equal: bool,
+}
 
-    // Open the CSV file
-    let file = File::open(csv_path)?;
-    let reader = BufReader::new(file);
+/// Runs `--compare`: processes both logs like the CLI would and prints the
+/// difference of their final fingerprints as one JSON object.
+pub fn run_compare(
+    config: &Config,
+    first: &str,
+    second: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let verdict = compare_logs(config, first, second)?;
+    println!("{}", serde_json::to_string(&verdict)?);
+    Ok(())
+}
+
+fn compare_logs(
+    config: &Config,
+    first: &str,
+    second: &str,
+) -> Result<CompareOutput, Box<dyn std::error::Error>> {
+    let a = fingerprint_log(config, first)?;
+    let b = fingerprint_log(config, second)?;
+    Ok(CompareOutput {
+        writhe_delta: b.writhe - a.writhe,
+        crossing_delta: b.crossing_count as i64 - a.crossing_count as i64,
+        reduced_equal: a.word.reduced_eq(&b.word),
+    })
+}
+
+/// Braids a whole log and returns the fingerprint it ends on (the last hand
+/// if the log holds several).
+fn fingerprint_log(
+    config: &Config,
+    path: &str,
+) -> Result<FingerprintState, Box<dyn std::error::Error>> {
+    let contents = read_input(path, config.input_encoding)?;
+    let mut fingerprint = FingerprintState::new(config.dimension);
+    let mut current_seat = None;
+    let mut step = 0;
+    let mut report = PlayerReport::new();
+    let step_options = StepOptions {
+        explain: false,
+        ..StepOptions::from_config(config)
+    };
+    for action in parse_stream(
+        contents.as_bytes(),
+        config.format.into(),
+        &stream_options(config),
+    ) {
+        process_action(
+            action?,
+            &mut fingerprint,
+            &mut current_seat,
+            &mut step,
+            &mut report,
+            step_options,
+            None,
+        )?;
+    }
+    Ok(fingerprint)
+}
+
+/// Runs the CLI mode
+pub fn run_cli(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    let csv_path = match &config.input {
+        Some(path) => path,
+        None => {
+            let program = std::env::args()
+                .next()
+                .unwrap_or_else(|| "poker-braids".to_string());
+            eprintln!(
+                "Usage: {} [--config <file.json>] [--format <generic|pokernow|pokerstars>] [--reset-on-fold] [--reset-sentinel <value>] [--seats-are-numeric] [--disambiguate-names] [--dimension <N>] [--input-encoding <utf8|latin1|windows1252>] [--validate-invariants] [--player-report <path.csv>] [--export-hands <path.json>] [--round-digits <N>] [--tps-limit <N>] [--min-amount <N>] [--explain] [--output-format <jsonl|array>] [--out <path>] [--compare <log1> <log2>] [--player <name>]... [--quiet] <log_file_path | ->",
+                program
+            );
+            std::process::exit(1);
+        }
+    };
+
+    let mut summary = match &config.out {
+        Some(path) => {
+            let file = fs::File::create(path).map_err(|e| {
+                std::io::Error::new(e.kind(), format!("Failed to create '{}': {}", path, e))
+            })?;
+            run_log(config, csv_path, BufWriter::new(file))
+        }
+        None => run_log(config, csv_path, std::io::stdout().lock()),
+    }?;
+
+    if !config.quiet {
+        summary.peak_burau_trace_magnitude =
+            round_metric(summary.peak_burau_trace_magnitude, config.round_digits);
+        eprintln!("{}", serde_json::to_string(&summary)?);
+    }
+    Ok(())
+}
+
+/// Processes the log at `csv_path` (`-` for stdin), writing the step results
+/// to `out` and any reports to their configured paths.
+fn run_log(
+    config: &Config,
+    csv_path: &str,
+    out: impl Write,
+) -> Result<RunSummary, Box<dyn std::error::Error>> {
+    // Read the log (file or stdin), decoding it to UTF-8 for the csv crate
+    let contents = read_input(csv_path, config.input_encoding)?;
 
     // Initialize components
-    let mut seat_resolver = SeatResolver::new();
-    let mut fingerprint = FingerprintState::new(12); // Use 12 to handle player churn safely (modulo problem gave me absolute hell)
+    let mut fingerprint = FingerprintState::new(config.dimension); // Defaults to 12 to handle player churn safely (modulo problem gave me absolute hell)
+    fingerprint.set_player_filter(config.players.clone());
     let mut current_seat = None;
     let mut step = 0;
+    let mut report = PlayerReport::new();
 
-    if format_pokernow {
-        // Process PokerNow format
-        let mut csv_reader = ReaderBuilder::new()
-            .has_headers(true)
-            .from_reader(reader);
-
-        // Deserialize into PokerNowRow
-        for result in csv_reader.deserialize() {
-            let row: pokernow::PokerNowRow = result?;
-            
-            // Parse the row to extract action
-            if let Some((player_id, action_type, amount)) = pokernow::parse_row(&row) {
-                // Resolve player_id to Seat
-                let seat = seat_resolver.get_or_assign_seat(&player_id);
-                
-                // Create Action
-                let action = Action::new(seat, action_type, amount);
-                
-                // Process the action (same logic as generic parser)
-                process_action(
-                    action,
-                    &mut fingerprint,
-                    &mut current_seat,
-                    &mut step,
-                    reset_on_fold,
-                )?;
+    let options = stream_options(config);
+    let step_options = StepOptions::from_config(config);
+    let mut writer = StepWriter::new(out, config.output_format);
+    writer.markers_to_stderr = config.out.is_some();
+    let mut pacer = config.tps_limit.map(Pacer::new);
+    let mut export = config.export_hands.as_ref().map(|_| HandExport::default());
+    let mut summary = RunSummary::default();
+    let mut seats = HashSet::new();
+    let mut actions = parse_stream(contents.as_bytes(), config.format.into(), &options);
+    for action in &mut actions {
+        let action = action?;
+        if let Some(pacer) = &mut pacer {
+            pacer.wait();
+        }
+        let action_type = action.action_type;
+        seats.insert(action.seat);
+        let emitted = process_action(
+            action.clone(),
+            &mut fingerprint,
+            &mut current_seat,
+            &mut step,
+            &mut report,
+            step_options,
+            export.as_mut(),
+        )?;
+        if let Some(export) = &mut export {
+            if action_type != ActionType::Reset {
+                export.push(action, &fingerprint);
             }
-            // If parse_row returns None, skip this row (filtered out)
         }
+        summary.record(action_type, &emitted, &fingerprint, step_options);
+        if let Some(emitted) = emitted {
+            writer.emit(emitted)?;
+        }
+    }
+    writer.finish()?;
+    let seat_resolver = actions.resolver();
+    summary.final_writhe = fingerprint.writhe;
+    summary.players = if config.seats_are_numeric {
+        seats.len()
     } else {
-        // Process generic format
-        let mut csv_reader = ReaderBuilder::new()
-            .has_headers(true)
-            .from_reader(reader);
-
-        // Process each record
-        for result in csv_reader.records() {
-            let record = result?;
-            
-            // Parse the action
-            let action = parse_record(&record, &mut seat_resolver)?;
-            
-            // Process the action
-            process_action(
-                action,
-                &mut fingerprint,
-                &mut current_seat,
-                &mut step,
-                reset_on_fold,
-            )?;
+        seat_resolver.player_count()
+    };
+
+    if fingerprint.collisions > 0 {
+        eprintln!(
+            "Warning: {} action(s) moved between seats sharing a strand; increase --dimension (currently {})",
+            fingerprint.collisions,
+            config.dimension
+        );
+    }
+
+    if config.validate_invariants {
+        report_invariant_checks(&fingerprint)?;
+    }
+
+    // Relative seats are positions, held by different players each hand
+    let seat_name = |seat: braid_engine::Seat| {
+        if config.button_relative {
+            format!("Position {}", seat.value())
+        } else {
+            seat_resolver.get_player_name(seat)
+        }
+    };
+
+    if let Some(path) = &config.player_report {
+        // --player keeps the report to the selected players
+        if !config.button_relative {
+            report.retain(|seat| fingerprint.tracks_player(&seat_resolver.get_player_name(seat)));
         }
+        report.write_csv(path, config.round_digits, seat_name)?;
     }
 
-    Ok(())
+    if let (Some(path), Some(mut export)) = (&config.export_hands, export) {
+        export.finish_hand(&fingerprint);
+        let hands = export.into_records(seat_name);
+        fs::write(path, serde_json::to_string_pretty(&hands)?).map_err(|e| {
+            std::io::Error::new(
+                e.kind(),
+                format!("Failed to write hand export '{}': {}", path, e),
+            )
+        })?;
+    }
+
+    Ok(summary)
 }
 
-/// Processes an action and updates the fingerprint state.
+/// Runs the invariant self-check and reports pass/fail per check on stderr
+/// (stdout carries the JSON stream). Returns an error if any check fails.
+fn report_invariant_checks(
+    fingerprint: &FingerprintState,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let checks = validate_invariants(fingerprint, &fingerprint.word);
+    for check in &checks {
+        let status = if check.passed { "PASS" } else { "FAIL" };
+        eprintln!("[{}] {}: {}", status, check.name, check.detail);
+    }
+
+    if checks.iter().all(|check| check.passed) {
+        Ok(())
+    } else {
+        Err("Invariant validation failed".into())
+    }
+}
+
+/// Reads a log file (or stdin for `-`) and decodes it from the given
+/// encoding into UTF-8.
+fn read_input(path: &str, encoding: InputEncoding) -> Result<String, Box<dyn std::error::Error>> {
+    let bytes = if path == "-" {
+        let mut bytes = Vec::new();
+        std::io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        fs::read(path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Failed to read '{}': {}", path, e))
+        })?
+    };
+    encoding.decode(&bytes)
+}
+
+/// Processes an action and updates the fingerprint state, returning what
+/// it writes to the output (nothing for seats that stood up).
 fn process_action(
     action: Action,
     fingerprint: &mut FingerprintState,
     current_seat: &mut Option<braid_engine::Seat>,
     step: &mut usize,
-    reset_on_fold: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
+    report: &mut PlayerReport,
+    options: StepOptions,
+    export: Option<&mut HandExport>,
+) -> Result<Option<Emitted>, Box<dyn std::error::Error>> {
     // Handle Reset action (hand delimiter detected)
     if action.action_type == ActionType::Reset {
+        if let Some(export) = export {
+            export.finish_hand(fingerprint);
+        }
         fingerprint.reset();
         *current_seat = None;
         *step = 0;
-        println!("--- HAND RESET ---");
-        return Ok(());
+        return Ok(Some(Emitted::Marker("--- HAND RESET ---")));
+    }
+
+    // Street boundaries don't braid (per-street checkpoints are up to the consumer)
+    if action.action_type == ActionType::StreetBoundary {
+        fingerprint.next_street();
+        return Ok(Some(Emitted::Marker("--- NEW STREET ---")));
+    }
+
+    // Seats whose player stood up are left out of the braid until they sit back
+    match action.action_type {
+        ActionType::StandUp | ActionType::SitBack => {
+            let active = action.action_type == ActionType::SitBack;
+            fingerprint.set_seat_active(action.seat.value(), active);
+            return Ok(None);
+        }
+        _ if !fingerprint.is_seat_active(action.seat.value()) => return Ok(None),
+        _ => {}
     }
     
     // Reset on fold if flag is set (heuristic for end of hand)
-    if reset_on_fold && action.action_type == ActionType::Fold {
+    if options.reset_on_fold && action.action_type == ActionType::Fold {
+        if let Some(export) = export {
+            export.finish_hand(fingerprint);
+        }
         fingerprint.reset();
         *current_seat = None;
         // Don't increment step, as this is a reset marker
@@ -141,15 +510,35 @@ fn process_action(
     // If this is the first action, we start from the action's seat
     // Otherwise, we move from the previous seat to the current action's seat
     let from_seat = current_seat.unwrap_or(action.seat);
-    let generators = expand_action(from_seat, action.seat, fingerprint.dimension());
+    // Bets under --min-amount still move the flow, but braid nothing
+    let generators = if options
+        .min_amount
+        .is_some_and(|min| action.is_below_min_amount(min))
+    {
+        Vec::new()
+    } else {
+        let generators = tracing::info_span!("expand_action")
+            .in_scope(|| expand_action(from_seat, action.seat, fingerprint.dimension()));
+        fingerprint.check_collision(from_seat, action.seat, generators.len());
+        generators
+    };
     
     // Update current seat
     *current_seat = Some(action.seat);
 
     // Process each generator
-    for gen in &generators {
-        fingerprint.update(gen);
-    }
+    tracing::info_span!("update_matrices", generators = generators.len()).in_scope(|| {
+        for gen in &generators {
+            fingerprint.update(gen);
+        }
+    });
+    let seat_index = action.seat.value() - 1;
+    let complexity = if seat_index < fingerprint.dimension() {
+        fingerprint.burau_matrix[(seat_index, seat_index)].norm()
+    } else {
+        0.0
+    };
+    report.record(action.seat, action.action_type, &generators, complexity);
 
     *step += 1;
 
@@ -164,17 +553,43 @@ fn process_action(
     // Calculate Burau trace magnitude
     let trace_magnitude = fingerprint.burau_trace_magnitude();
 
-    // Output JSON line
-    let output = StepOutput {
+    let step_output = StepOutput {
         step: *step,
         action: action_desc,
         writhe: fingerprint.writhe,
-        burau_trace_magnitude: trace_magnitude,
+        burau_trace_magnitude: round_metric(trace_magnitude, options.round_digits),
+        explain: options
+            .explain
+            .then(|| explain_step(from_seat, action.seat, &generators)),
     };
 
-    println!("{}", serde_json::to_string(&output)?);
-    
-    Ok(())
+    Ok(Some(Emitted::Step(step_output)))
+}
+
+/// Describes one step of the braid construction, e.g.
+/// `seat 1 -> seat 3 : σ1 σ2 (2 crossings)`.
+fn explain_step(from: Seat, to: Seat, generators: &[Generator]) -> String {
+    let word: Vec<String> = generators.iter().map(|gen| gen.to_string()).collect();
+    let crossings = match generators.len() {
+        1 => "1 crossing".to_string(),
+        n => format!("{} crossings", n),
+    };
+    if word.is_empty() {
+        format!(
+            "seat {} -> seat {} : ({})",
+            from.value(),
+            to.value(),
+            crossings
+        )
+    } else {
+        format!(
+            "seat {} -> seat {} : {} ({})",
+            from.value(),
+            to.value(),
+            word.join(" "),
+            crossings
+        )
+    }
 }
 
 /// Formats an ActionType as a string for display.
@@ -188,6 +603,351 @@ fn format_action_type(action_type: ActionType) -> &'static str {
         ActionType::ReRaise => "reraise",
         ActionType::AllIn => "allin",
         ActionType::Reset => "reset",
+        ActionType::StreetBoundary => "street",
+        ActionType::StandUp => "stands up",
+        ActionType::SitBack => "sits back",
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use csv::ReaderBuilder;
+    use poker_parser::{pokernow, SeatResolver};
+
+    #[test]
+    fn test_read_windows1252_input() {
+        let path =
+            std::env::temp_dir().join(format!("poker-braids-cp1252-{}.csv", std::process::id()));
+        // "Zoé" with é encoded as the single Windows-1252 byte 0xE9
+        let mut bytes = b"entry,at,order\n\"Zo".to_vec();
+        bytes.push(0xE9);
+        bytes.extend_from_slice(b" @ p1 calls 50\",2025-01-01T12:00:00,1\n");
+        fs::write(&path, &bytes).unwrap();
+
+        let path_str = path.to_str().unwrap();
+        assert!(read_input(path_str, InputEncoding::Utf8).is_err());
+        let contents = read_input(path_str, InputEncoding::Windows1252).unwrap();
+        fs::remove_file(&path).ok();
+
+        let mut csv_reader = ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(contents.as_bytes());
+        let row: pokernow::PokerNowRow = csv_reader.deserialize().next().unwrap().unwrap();
+        let parsed = pokernow::parse_row(&row).unwrap().unwrap();
+        assert_eq!(parsed.player_id, "Zoé_p1");
+        assert_eq!(parsed.action_type, ActionType::Call);
+        assert_eq!(parsed.amount, 50);
+    }
+
+    #[test]
+    fn test_array_output_is_one_json_array() {
+        let path =
+            std::env::temp_dir().join(format!("poker-braids-array-{}.csv", std::process::id()));
+        fs::write(
+            &path,
+            "player_id,action,amount\nAlice,raise,100\nBob,call,100\nreset,,\nBob,bet,50\n",
+        )
+        .unwrap();
+        let config = Config {
+            dimension: 4,
+            output_format: OutputFormat::Array,
+            ..Config::default()
+        };
+
+        let mut out = Vec::new();
+        run_log(&config, path.to_str().unwrap(), &mut out).unwrap();
+        fs::remove_file(&path).ok();
+
+        let steps: Vec<StepOutput> = serde_json::from_slice(&out).unwrap();
+        let numbers: Vec<usize> = steps.iter().map(|s| s.step).collect();
+        assert_eq!(numbers, [1, 2, 1], "The reset marker is left out");
+        assert_eq!(steps[1].action, "Seat 2 call ($100)");
+        assert_eq!(steps[1].writhe, 1);
+
+        // No steps is still valid JSON
+        let mut out = Vec::new();
+        let writer = StepWriter::new(&mut out, OutputFormat::Array);
+        writer.finish().unwrap();
+        let steps: Vec<StepOutput> = serde_json::from_slice(&out).unwrap();
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn test_out_writes_jsonl_to_file() {
+        let dir = std::env::temp_dir();
+        let log = dir.join(format!("poker-braids-out-log-{}.csv", std::process::id()));
+        let out = dir.join(format!("poker-braids-out-{}.jsonl", std::process::id()));
+        fs::write(
+            &log,
+            "player_id,action,amount\nAlice,raise,100\nreset,,\nBob,call,100\nAlice,check,0\n",
+        )
+        .unwrap();
+        let config = Config {
+            dimension: 4,
+            input: Some(log.to_str().unwrap().to_string()),
+            out: Some(out.to_str().unwrap().to_string()),
+            ..Config::default()
+        };
+
+        run_cli(&config).unwrap();
+        let written = fs::read_to_string(&out).unwrap();
+        fs::remove_file(&log).ok();
+        fs::remove_file(&out).ok();
+
+        // Only JSON lines: the reset marker went to stderr
+        let steps: Vec<StepOutput> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let numbers: Vec<usize> = steps.iter().map(|s| s.step).collect();
+        assert_eq!(numbers, [1, 1, 2]);
+        assert_eq!(steps[2].action, "Seat 1 check ($0)");
+    }
+
+    #[test]
+    fn test_run_summary_counts_hands_and_players() {
+        let path =
+            std::env::temp_dir().join(format!("poker-braids-summary-{}.csv", std::process::id()));
+        fs::write(
+            &path,
+            "player_id,action,amount\n\
+             reset,,\nAlice,raise,100\nBob,call,100\nCarol,fold,0\n\
+             reset,,\nBob,bet,50\nDave,raise,150\nBob,fold,0\n\
+             reset,,\nAlice,check,0\n",
+        )
+        .unwrap();
+        let config = Config {
+            dimension: 6,
+            ..Config::default()
+        };
+
+        let summary = run_log(&config, path.to_str().unwrap(), std::io::sink()).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(summary.hands, 3);
+        assert_eq!(summary.players, 4);
+        assert_eq!(summary.actions, 7);
+        // Hand 1: σ1 σ2 peaks at writhe 2; the last hand hasn't braided yet
+        assert_eq!(summary.peak_writhe, 2);
+        assert_eq!(summary.final_writhe, 0);
+        assert!(summary.peak_burau_trace_magnitude > 0.0);
+    }
+
+    #[test]
+    fn test_export_hands_follows_step_options() {
+        let dir = std::env::temp_dir();
+        let log = dir.join(format!(
+            "poker-braids-export-log-{}.csv",
+            std::process::id()
+        ));
+        let export = dir.join(format!("poker-braids-export-{}.json", std::process::id()));
+        fs::write(&log, "player_id,action,amount\nAlice,raise,100\nBob,call,100\nCarol,call,5\nBob,fold,0\nAlice,bet,50\n")
+            .unwrap();
+        let config = Config {
+            dimension: 4,
+            min_amount: Some(20),
+            reset_on_fold: true,
+            export_hands: Some(export.to_str().unwrap().to_string()),
+            ..Config::default()
+        };
+
+        run_log(&config, log.to_str().unwrap(), std::io::sink()).unwrap();
+        let hands: Vec<HandRecord> =
+            serde_json::from_str(&fs::read_to_string(&export).unwrap()).unwrap();
+        fs::remove_file(&log).ok();
+        fs::remove_file(&export).ok();
+
+        // Bob's fold starts the second hand; Carol's call of 5 braids nothing
+        assert_eq!(hands.len(), 2);
+        assert_eq!(hands[0].actions.len(), 3);
+        assert_eq!(hands[0].final_word.to_string(), "σ1");
+        assert_eq!(hands[0].metrics.crossing_count, 1);
+        let seats: Vec<usize> = hands[0].seats.iter().map(|(seat, _)| *seat).collect();
+        assert_eq!(seats, [1, 2, 3]);
+        assert_eq!(hands[0].seats[2].1, "Carol");
+        assert_eq!(hands[1].actions[0].action_type, ActionType::Fold);
+        assert_eq!(hands[1].final_word.to_string(), "σ1⁻¹");
+    }
+
+    #[test]
+    fn test_compare_logs() {
+        let dir = std::env::temp_dir();
+        let write = |name: &str, contents: &str| {
+            let path = dir.join(format!(
+                "poker-braids-compare-{}-{}.csv",
+                name,
+                std::process::id()
+            ));
+            fs::write(&path, contents).unwrap();
+            path.to_str().unwrap().to_string()
+        };
+        let hand = write(
+            "hand",
+            "player_id,action,amount\nAlice,raise,100\nBob,call,100\nCarol,fold,0\n",
+        );
+        // The action comes back around to the opener: a different braid
+        let other = write("other", "player_id,action,amount\nAlice,raise,100\nCarol,call,100\nBob,raise,300\nAlice,call,300\n");
+        let config = Config {
+            dimension: 4,
+            ..Config::default()
+        };
+
+        let same = compare_logs(&config, &hand, &hand).unwrap();
+        let different = compare_logs(&config, &hand, &other).unwrap();
+        fs::remove_file(&hand).ok();
+        fs::remove_file(&other).ok();
+
+        assert_eq!(
+            same,
+            CompareOutput {
+                writhe_delta: 0,
+                crossing_delta: 0,
+                reduced_equal: true
+            }
+        );
+        // hand: σ1 σ2 (writhe 2, 2 crossings); other: σ1 σ2 σ2⁻¹ σ1⁻¹ (writhe 0, 4 crossings)
+        assert_eq!(
+            different,
+            CompareOutput {
+                writhe_delta: -2,
+                crossing_delta: 2,
+                reduced_equal: false
+            }
+        );
+    }
+
+    #[test]
+    fn test_explain_step_names_seats_and_crossings() {
+        let generators = expand_action(Seat::new(1), Seat::new(3), 4);
+        let explanation = explain_step(Seat::new(1), Seat::new(3), &generators);
+        assert_eq!(explanation, "seat 1 -> seat 3 : σ1 σ2 (2 crossings)");
+
+        assert_eq!(
+            explain_step(Seat::new(2), Seat::new(2), &[]),
+            "seat 2 -> seat 2 : (0 crossings)"
+        );
+    }
+
+    #[test]
+    fn test_min_amount_skips_small_bets() {
+        let mut fingerprint = FingerprintState::new(4);
+        let mut current_seat = None;
+        let mut step = 0;
+        let mut report = PlayerReport::new();
+        let options = StepOptions {
+            min_amount: Some(20),
+            ..StepOptions::default()
+        };
+
+        let mut process = |seat, action_type, amount| {
+            let action = Action::new(Seat::new(seat), action_type, amount);
+            process_action(
+                action,
+                &mut fingerprint,
+                &mut current_seat,
+                &mut step,
+                &mut report,
+                options,
+                None,
+            )
+            .unwrap();
+        };
+        process(1, ActionType::Check, 0);
+        process(2, ActionType::Bet, 10);
+        process(3, ActionType::Call, 50);
+
+        // The bet of 10 moved the flow to seat 2 without crossing; 2 -> 3 is σ2
+        assert_eq!(fingerprint.crossing_count, 1);
+        assert_eq!(fingerprint.word.to_string(), "σ2");
+        assert_eq!(current_seat, Some(Seat::new(3)));
+    }
+
+    #[test]
+    fn test_invariant_checks_pass_for_processed_actions() {
+        let mut fingerprint = FingerprintState::new(4);
+        let mut current_seat = None;
+        let mut step = 0;
+        let mut report = PlayerReport::new();
+
+        for (seat, action_type) in [
+            (1, ActionType::Raise),
+            (3, ActionType::Call),
+            (2, ActionType::Fold),
+        ] {
+            let action = Action::new(braid_engine::Seat::new(seat), action_type, 100);
+            process_action(
+                action,
+                &mut fingerprint,
+                &mut current_seat,
+                &mut step,
+                &mut report,
+                StepOptions::default(),
+                None,
+            )
+            .unwrap();
+        }
+
+        assert_eq!(fingerprint.word.len(), 3);
+        assert!(report_invariant_checks(&fingerprint).is_ok());
+    }
+
+    #[test]
+    fn test_player_report_has_row_per_seat() {
+        let mut fingerprint = FingerprintState::new(4);
+        let mut current_seat = None;
+        let mut step = 0;
+        let mut report = PlayerReport::new();
+        let mut resolver = SeatResolver::new();
+
+        for (player, action_type) in [
+            ("Alice", ActionType::Raise),
+            ("Carol", ActionType::Call),
+            ("Bob", ActionType::Fold),
+            ("Alice", ActionType::Raise),
+            ("Carol", ActionType::Call),
+        ] {
+            let seat = resolver.get_or_assign_seat(player);
+            let action = Action::new(seat, action_type, 100);
+            process_action(
+                action,
+                &mut fingerprint,
+                &mut current_seat,
+                &mut step,
+                &mut report,
+                StepOptions::default(),
+                None,
+            )
+            .unwrap();
+        }
+
+        let path =
+            std::env::temp_dir().join(format!("poker-braids-report-{}.csv", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        report
+            .write_csv(path_str, None, |seat| resolver.get_player_name(seat))
+            .unwrap();
+        let mut reader = ReaderBuilder::new()
+            .has_headers(true)
+            .from_path(&path)
+            .unwrap();
+        let headers = reader.headers().unwrap().clone();
+        let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(
+            headers.iter().take(4).collect::<Vec<_>>(),
+            ["seat", "name", "writhe", "complexity"]
+        );
+        assert_eq!(rows.len(), 3, "One row per seated player");
+        // Seats are assigned Alice 1, Carol 2, Bob 3. Alice opens, Carol calls
+        // (σ1), Bob folds (σ2), Alice raises (σ2⁻¹ σ1⁻¹), Carol calls (σ1)
+        let writhe: Vec<&str> = rows.iter().map(|row| &row[2]).collect();
+        assert_eq!(writhe, ["-2", "2", "1"]);
+        assert_eq!(&rows[0][1], "Alice");
+        let raises = headers.iter().position(|h| h == "raises").unwrap();
+        assert_eq!(&rows[0][raises], "2");
+        let calls = headers.iter().position(|h| h == "calls").unwrap();
+        assert_eq!(&rows[1][calls], "2");
+    }
+}
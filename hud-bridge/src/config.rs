@@ -0,0 +1,853 @@
+use braid_engine::FlowModel;
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::net::{SocketAddr, ToSocketAddrs};
+
+/// Input log format for the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputFormat {
+    /// Generic `player_id,action,amount` CSV
+    #[default]
+    Generic,
+    /// PokerNow `entry,at,order` export
+    Pokernow,
+    /// PokerStars `.txt` hand history
+    Pokerstars,
+}
+
+impl InputFormat {
+    /// Parses a `--format` value.
+    fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+        match value.to_lowercase().as_str() {
+            "generic" => Ok(InputFormat::Generic),
+            "pokernow" => Ok(InputFormat::Pokernow),
+            "pokerstars" => Ok(InputFormat::Pokerstars),
+            _ => Err(format!("Unknown format: '{}'", value).into()),
+        }
+    }
+}
+
+impl From<InputFormat> for poker_parser::Format {
+    fn from(format: InputFormat) -> Self {
+        match format {
+            InputFormat::Generic => poker_parser::Format::Generic,
+            InputFormat::Pokernow => poker_parser::Format::PokerNow,
+            InputFormat::Pokerstars => poker_parser::Format::PokerStars,
+        }
+    }
+}
+
+/// How the CLI writes its per-step results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// One JSON object per line, plus plain-text hand/street markers
+    #[default]
+    Jsonl,
+    /// A single JSON array of step objects (markers are left out)
+    Array,
+}
+
+impl OutputFormat {
+    /// Parses an `--output-format` value.
+    fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+        match value.to_lowercase().as_str() {
+            "jsonl" => Ok(OutputFormat::Jsonl),
+            "array" => Ok(OutputFormat::Array),
+            _ => Err(format!("Unknown output format: '{}'", value).into()),
+        }
+    }
+}
+
+/// Character encoding of the CLI input log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputEncoding {
+    #[default]
+    Utf8,
+    /// ISO-8859-1 (decoded as its Windows-1252 superset)
+    Latin1,
+    Windows1252,
+}
+
+impl InputEncoding {
+    /// Parses an `--input-encoding` value.
+    fn parse(value: &str) -> Result<Self, Box<dyn Error>> {
+        match value.to_lowercase().replace('-', "").as_str() {
+            "utf8" => Ok(InputEncoding::Utf8),
+            "latin1" => Ok(InputEncoding::Latin1),
+            "windows1252" => Ok(InputEncoding::Windows1252),
+            _ => Err(format!("Unknown input encoding: '{}'", value).into()),
+        }
+    }
+
+    /// Decodes raw bytes into UTF-8 text.
+    pub fn decode(self, bytes: &[u8]) -> Result<String, Box<dyn Error>> {
+        match self {
+            InputEncoding::Utf8 => Ok(String::from_utf8(bytes.to_vec())?),
+            InputEncoding::Latin1 | InputEncoding::Windows1252 => {
+                let (text, _, _) = encoding_rs::WINDOWS_1252.decode(bytes);
+                Ok(text.into_owned())
+            }
+        }
+    }
+}
+
+/// Settings shared by the CLI and server modes.
+///
+/// Values are first loaded from an optional `--config <file.json>` and then
+/// overridden by any flags given on the command line. Fields missing from the
+/// file keep their defaults.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Run the web server instead of the CLI
+    pub server: bool,
+    /// Server: IP address to bind (e.g. `0.0.0.0` inside a container)
+    pub host: String,
+    /// Server: port to listen on
+    pub port: u16,
+    /// Number of strands/seats for the fingerprint
+    pub dimension: usize,
+    /// Reset the fingerprint on every fold (heuristic for end of hand)
+    pub reset_on_fold: bool,
+    /// Calls/bets/raises below this amount move the flow but braid nothing
+    pub min_amount: Option<i64>,
+    /// Log format for the CLI
+    pub format: InputFormat,
+    /// Path of the log file to process (CLI mode, `-` for stdin)
+    pub input: Option<String>,
+    /// Character encoding of the input log (CLI mode)
+    pub input_encoding: InputEncoding,
+    /// Per-step decay factor for seat activity in the HUD
+    pub activity_decay: f64,
+    /// CLI: run internal consistency checks after processing
+    pub validate_invariants: bool,
+    /// CLI: write a per-seat summary CSV to this path after processing
+    pub player_report: Option<String>,
+    /// CLI: write every hand as a JSON `HandRecord` to this path after processing
+    pub export_hands: Option<String>,
+    /// Generic format: player_id or action value that marks a new hand
+    pub reset_sentinel: String,
+    /// Generic format: the first column is already a seat number
+    pub seats_are_numeric: bool,
+    /// PokerNow: split ID-less players who share a display name (heuristic)
+    pub disambiguate_names: bool,
+    /// Number seats from the dealer button (taken from PokerNow hand headers)
+    pub button_relative: bool,
+    /// CLI: submit at most this many actions per second (unthrottled if unset)
+    pub tps_limit: Option<u32>,
+    /// Round reported float metrics to this many decimals (full precision if unset)
+    pub round_digits: Option<u32>,
+    /// CLI: annotate each step with the seat movement and generators it braided
+    pub explain: bool,
+    /// CLI: JSON lines (default) or one JSON array
+    pub output_format: OutputFormat,
+    /// CLI: write the step output to this file instead of stdout
+    pub out: Option<String>,
+    /// CLI: don't print the end-of-run summary to stderr
+    pub quiet: bool,
+    /// Log timing spans for each processing phase to stderr
+    pub trace: bool,
+    /// CLI: compare the fingerprints of these two logs instead of streaming one
+    pub compare: Option<(String, String)>,
+    /// Server: how calls against an open bet are braided
+    pub flow_model: FlowModel,
+    /// Server: also read newline-delimited actions from this Unix socket
+    pub socket: Option<String>,
+    /// Server: append the running braid word to this file after each action
+    pub debug_word: Option<String>,
+    /// Server: write a snapshot every N actions (disabled when unset)
+    pub autosave: Option<usize>,
+    /// Server: path of the autosave snapshot
+    pub autosave_path: String,
+    /// Server: restore from the autosave snapshot on startup
+    pub load: bool,
+    /// Server: drop an action identical to the previous one if it arrives
+    /// within `dedup_window_ms` (scrapers can emit the same line twice)
+    pub dedup_consecutive: bool,
+    /// Server: time window for `dedup_consecutive`, in milliseconds
+    pub dedup_window_ms: u64,
+    /// Server: freely reduce the stored braid word once it exceeds this length
+    pub normalization_window: Option<usize>,
+    /// Server: broadcast an idle event after this many seconds without actions
+    pub idle_after: Option<u64>,
+    /// Server: number of recent step responses kept for `GET /history`
+    pub history: usize,
+    /// Server: origins allowed to make cross-origin requests (any if empty)
+    pub allow_origins: Vec<String>,
+    /// Only keep per-seat metrics for these players (everyone if empty)
+    pub players: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            server: false,
+            host: "127.0.0.1".to_string(),
+            port: 3030,
+            // Use dimension 12 to provide buffer for player churn
+            // Even on 9-handed tables, this reduces hash collisions before modulo mapping kicks in, as I've found out the hard way xd
+            dimension: 12,
+            reset_on_fold: false,
+            min_amount: None,
+            format: InputFormat::Generic,
+            input: None,
+            input_encoding: InputEncoding::Utf8,
+            activity_decay: 0.8,
+            validate_invariants: false,
+            player_report: None,
+            export_hands: None,
+            reset_sentinel: poker_parser::DEFAULT_RESET_SENTINEL.to_string(),
+            seats_are_numeric: false,
+            disambiguate_names: false,
+            button_relative: false,
+            round_digits: None,
+            explain: false,
+            output_format: OutputFormat::Jsonl,
+            out: None,
+            compare: None,
+            quiet: false,
+            trace: false,
+            tps_limit: None,
+            flow_model: FlowModel::Sequential,
+            socket: None,
+            debug_word: None,
+            autosave: None,
+            autosave_path: "poker-braids-autosave.json".to_string(),
+            load: false,
+            dedup_consecutive: false,
+            dedup_window_ms: 1000,
+            normalization_window: None,
+            idle_after: None,
+            history: 100,
+            allow_origins: Vec::new(),
+            players: Vec::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Loads a config from a JSON file.
+    pub fn from_file(path: &str) -> Result<Self, Box<dyn Error>> {
+        // Kept as an io::Error so main reports it with the file/IO exit code
+        let contents = fs::read_to_string(path).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Failed to read config '{}': {}", path, e))
+        })?;
+        let config = serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid config '{}': {}", path, e))?;
+        Ok(config)
+    }
+
+    /// Builds a config from command-line arguments (without the program name).
+    ///
+    /// If `--config <file.json>` is present the file is loaded first, then every
+    /// other flag overrides the corresponding file value.
+    pub fn from_args(args: &[String]) -> Result<Self, Box<dyn Error>> {
+        let mut config = match args.iter().position(|arg| arg == "--config") {
+            Some(i) => Config::from_file(flag_value(args, i)?)?,
+            None => Config::default(),
+        };
+
+        let mut seen_input = false;
+        let mut seen_origin = false;
+        let mut seen_player = false;
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--config" => {
+                    i += 2;
+                }
+                "--server" => {
+                    config.server = true;
+                    i += 1;
+                }
+                "--host" => {
+                    config.host = flag_value(args, i)?.to_string();
+                    i += 2;
+                }
+                "--port" => {
+                    let value = flag_value(args, i)?;
+                    config.port = value
+                        .parse()
+                        .map_err(|e| format!("Invalid port '{}': {}", value, e))?;
+                    i += 2;
+                }
+                "--reset-on-fold" => {
+                    config.reset_on_fold = true;
+                    i += 1;
+                }
+                "--min-amount" => {
+                    let value = flag_value(args, i)?;
+                    config.min_amount = Some(
+                        value
+                            .parse()
+                            .map_err(|e| format!("Invalid min amount '{}': {}", value, e))?,
+                    );
+                    i += 2;
+                }
+                "--format" => {
+                    config.format = InputFormat::parse(flag_value(args, i)?)?;
+                    i += 2;
+                }
+                "--input-encoding" => {
+                    config.input_encoding = InputEncoding::parse(flag_value(args, i)?)?;
+                    i += 2;
+                }
+                "--dimension" => {
+                    let value = flag_value(args, i)?;
+                    config.dimension = value
+                        .parse()
+                        .map_err(|e| format!("Invalid dimension '{}': {}", value, e))?;
+                    i += 2;
+                }
+                "--activity-decay" => {
+                    let value = flag_value(args, i)?;
+                    config.activity_decay = value
+                        .parse()
+                        .map_err(|e| format!("Invalid activity decay '{}': {}", value, e))?;
+                    i += 2;
+                }
+                "--validate-invariants" => {
+                    config.validate_invariants = true;
+                    i += 1;
+                }
+                "--round-digits" => {
+                    let value = flag_value(args, i)?;
+                    config.round_digits = Some(
+                        value
+                            .parse()
+                            .map_err(|e| format!("Invalid round digits '{}': {}", value, e))?,
+                    );
+                    i += 2;
+                }
+                "--reset-sentinel" => {
+                    config.reset_sentinel = flag_value(args, i)?.to_string();
+                    i += 2;
+                }
+                "--disambiguate-names" => {
+                    config.disambiguate_names = true;
+                    i += 1;
+                }
+                "--seats-are-numeric" => {
+                    config.seats_are_numeric = true;
+                    i += 1;
+                }
+                "--tps-limit" => {
+                    let value = flag_value(args, i)?;
+                    let limit: u32 = value
+                        .parse()
+                        .map_err(|e| format!("Invalid tps limit '{}': {}", value, e))?;
+                    if limit == 0 {
+                        return Err("Invalid tps limit '0': must be at least 1".into());
+                    }
+                    config.tps_limit = Some(limit);
+                    i += 2;
+                }
+                "--explain" => {
+                    config.explain = true;
+                    i += 1;
+                }
+                "--trace" => {
+                    config.trace = true;
+                    i += 1;
+                }
+                "--quiet" => {
+                    config.quiet = true;
+                    i += 1;
+                }
+                "--compare" => {
+                    let first = flag_value(args, i)?.to_string();
+                    let second = flag_value(args, i + 1)?.to_string();
+                    config.compare = Some((first, second));
+                    i += 3;
+                }
+                "--out" => {
+                    config.out = Some(flag_value(args, i)?.to_string());
+                    i += 2;
+                }
+                "--output-format" => {
+                    config.output_format = OutputFormat::parse(flag_value(args, i)?)?;
+                    i += 2;
+                }
+                "--button-relative" => {
+                    config.button_relative = true;
+                    i += 1;
+                }
+                "--player-report" => {
+                    config.player_report = Some(flag_value(args, i)?.to_string());
+                    i += 2;
+                }
+                "--export-hands" => {
+                    config.export_hands = Some(flag_value(args, i)?.to_string());
+                    i += 2;
+                }
+                "--flow-model" => {
+                    config.flow_model = parse_flow_model(flag_value(args, i)?)?;
+                    i += 2;
+                }
+                "--socket" => {
+                    config.socket = Some(flag_value(args, i)?.to_string());
+                    i += 2;
+                }
+                "--debug-word" => {
+                    config.debug_word = Some(flag_value(args, i)?.to_string());
+                    i += 2;
+                }
+                "--autosave" => {
+                    let value = flag_value(args, i)?;
+                    config.autosave =
+                        Some(value.parse().map_err(|e| {
+                            format!("Invalid autosave interval '{}': {}", value, e)
+                        })?);
+                    i += 2;
+                }
+                "--autosave-path" => {
+                    config.autosave_path = flag_value(args, i)?.to_string();
+                    i += 2;
+                }
+                "--load" => {
+                    config.load = true;
+                    i += 1;
+                }
+                "--dedup-consecutive" => {
+                    config.dedup_consecutive = true;
+                    i += 1;
+                }
+                "--dedup-window-ms" => {
+                    let value = flag_value(args, i)?;
+                    config.dedup_window_ms = value
+                        .parse()
+                        .map_err(|e| format!("Invalid dedup window '{}': {}", value, e))?;
+                    i += 2;
+                }
+                "--normalization-window" => {
+                    let value = flag_value(args, i)?;
+                    config.normalization_window =
+                        Some(value.parse().map_err(|e| {
+                            format!("Invalid normalization window '{}': {}", value, e)
+                        })?);
+                    i += 2;
+                }
+                "--history" => {
+                    let value = flag_value(args, i)?;
+                    config.history = value
+                        .parse()
+                        .map_err(|e| format!("Invalid history size '{}': {}", value, e))?;
+                    i += 2;
+                }
+                "--idle-after" => {
+                    let value = flag_value(args, i)?;
+                    let secs: u64 = value
+                        .parse()
+                        .map_err(|e| format!("Invalid idle threshold '{}': {}", value, e))?;
+                    if secs == 0 {
+                        return Err("Invalid idle threshold '0': must be at least 1 second".into());
+                    }
+                    config.idle_after = Some(secs);
+                    i += 2;
+                }
+                "--player" => {
+                    // Repeatable, like --allow-origin
+                    if !seen_player {
+                        config.players.clear();
+                        seen_player = true;
+                    }
+                    config.players.push(flag_value(args, i)?.to_string());
+                    i += 2;
+                }
+                "--allow-origin" => {
+                    // Repeatable; the command-line list replaces the file's
+                    if !seen_origin {
+                        config.allow_origins.clear();
+                        seen_origin = true;
+                    }
+                    config.allow_origins.push(flag_value(args, i)?.to_string());
+                    i += 2;
+                }
+                arg if arg.starts_with("--") => {
+                    return Err(format!("Unknown flag: '{}'", arg).into());
+                }
+                arg => {
+                    // The first positional argument is the input path
+                    if !seen_input {
+                        config.input = Some(arg.to_string());
+                        seen_input = true;
+                    }
+                    i += 1;
+                }
+            }
+        }
+
+        // Fewer than 2 strands can't braid anything
+        if config.dimension < 2 {
+            return Err(format!(
+                "Invalid dimension '{}': must be at least 2",
+                config.dimension
+            )
+            .into());
+        }
+        // Activity is multiplied by the decay every step, so it must not grow
+        if !(0.0..=1.0).contains(&config.activity_decay) {
+            return Err(format!(
+                "Invalid activity decay '{}': must be between 0 and 1",
+                config.activity_decay
+            )
+            .into());
+        }
+        for origin in &config.allow_origins {
+            check_origin(origin)?;
+        }
+
+        Ok(config)
+    }
+
+    /// Address the server binds to, built from `host` and `port`. The host
+    /// may be an IP address or a name such as `localhost`, which is resolved
+    /// and the first address used.
+    pub fn bind_addr(&self) -> Result<SocketAddr, Box<dyn Error>> {
+        (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|e| format!("Invalid host '{}': {}", self.host, e))?
+            .next()
+            .ok_or_else(|| format!("Invalid host '{}': no addresses found", self.host).into())
+    }
+}
+
+/// Parses a `--flow-model` value.
+fn parse_flow_model(value: &str) -> Result<FlowModel, Box<dyn Error>> {
+    match value.to_lowercase().as_str() {
+        "sequential" => Ok(FlowModel::Sequential),
+        "wrap-aggressor" => Ok(FlowModel::WrapAggressor),
+        _ => Err(format!("Unknown flow model: '{}'", value).into()),
+    }
+}
+
+/// Checks that an `--allow-origin` value is a bare `scheme://host[:port]`,
+/// which is all a browser ever sends in the `Origin` header.
+fn check_origin(origin: &str) -> Result<(), Box<dyn Error>> {
+    let valid = match origin.split_once("://") {
+        Some((scheme, host)) => !scheme.is_empty() && !host.is_empty() && !host.contains('/'),
+        None => false,
+    };
+    if !valid {
+        return Err(format!(
+            "Invalid origin '{}': expected e.g. https://www.pokernow.club",
+            origin
+        )
+        .into());
+    }
+    Ok(())
+}
+
+/// Returns the value following the flag at `index`, or an error if it is missing.
+fn flag_value(args: &[String], index: usize) -> Result<&str, Box<dyn Error>> {
+    args.get(index + 1)
+        .map(|s| s.as_str())
+        .ok_or_else(|| format!("Missing value for {}", args[index]).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn write_temp_config(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("poker-braids-{}-{}.json", name, std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_defaults() {
+        let config = Config::from_args(&[]).unwrap();
+        assert_eq!(config.dimension, 12);
+        assert!(!config.reset_on_fold);
+        assert_eq!(config.format, InputFormat::Generic);
+        assert!(config.input.is_none());
+    }
+
+    #[test]
+    fn test_config_file_is_honored() {
+        let path = write_temp_config("file", r#"{"dimension": 6, "format": "pokernow"}"#);
+        let config = Config::from_args(&args(&["--config", path.to_str().unwrap()])).unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(config.dimension, 6);
+        assert_eq!(config.format, InputFormat::Pokernow);
+        assert!(!config.reset_on_fold, "Unset fields keep their defaults");
+    }
+
+    #[test]
+    fn test_flag_overrides_config_file() {
+        let path = write_temp_config("override", r#"{"dimension": 6}"#);
+        let config = Config::from_args(&args(&[
+            "--config",
+            path.to_str().unwrap(),
+            "--dimension",
+            "9",
+        ]))
+        .unwrap();
+        fs::remove_file(&path).ok();
+
+        assert_eq!(config.dimension, 9);
+    }
+
+    #[test]
+    fn test_config_flag_needs_a_value() {
+        let err = Config::from_args(&args(&["log.csv", "--config"])).unwrap_err();
+        assert_eq!(err.to_string(), "Missing value for --config");
+    }
+
+    #[test]
+    fn test_positional_input_and_flags() {
+        let config = Config::from_args(&args(&[
+            "--format",
+            "pokernow",
+            "--reset-on-fold",
+            "log.csv",
+        ]))
+        .unwrap();
+        assert_eq!(config.format, InputFormat::Pokernow);
+        assert!(config.reset_on_fold);
+        assert_eq!(config.input.as_deref(), Some("log.csv"));
+    }
+
+    #[test]
+    fn test_reset_sentinel_flag() {
+        assert_eq!(Config::default().reset_sentinel, "reset");
+        let config =
+            Config::from_args(&args(&["--reset-sentinel", "new_hand", "log.csv"])).unwrap();
+        assert_eq!(config.reset_sentinel, "new_hand");
+    }
+
+    #[test]
+    fn test_tps_limit_flag() {
+        let config = Config::from_args(&args(&["--tps-limit", "5", "log.csv"])).unwrap();
+        assert_eq!(config.tps_limit, Some(5));
+        assert!(Config::from_args(&args(&["--tps-limit", "0"])).is_err());
+        assert!(Config::from_args(&args(&["--tps-limit", "fast"])).is_err());
+    }
+
+    #[test]
+    fn test_pokerstars_format() {
+        let config = Config::from_args(&args(&["--format", "PokerStars", "hands.txt"])).unwrap();
+        assert_eq!(config.format, InputFormat::Pokerstars);
+        assert_eq!(
+            poker_parser::Format::from(config.format),
+            poker_parser::Format::PokerStars
+        );
+    }
+
+    #[test]
+    fn test_input_encoding_flag() {
+        let config = Config::from_args(&args(&["--input-encoding", "windows1252"])).unwrap();
+        assert_eq!(config.input_encoding, InputEncoding::Windows1252);
+        assert!(Config::from_args(&args(&["--input-encoding", "ebcdic"])).is_err());
+    }
+
+    #[test]
+    fn test_dedup_flags() {
+        let config =
+            Config::from_args(&args(&["--dedup-consecutive", "--dedup-window-ms", "250"])).unwrap();
+        assert!(config.dedup_consecutive);
+        assert_eq!(config.dedup_window_ms, 250);
+        assert!(!Config::from_args(&[]).unwrap().dedup_consecutive);
+    }
+
+    #[test]
+    fn test_round_digits_flag() {
+        assert_eq!(Config::from_args(&[]).unwrap().round_digits, None);
+        let config = Config::from_args(&args(&["--round-digits", "3"])).unwrap();
+        assert_eq!(config.round_digits, Some(3));
+        assert!(Config::from_args(&args(&["--round-digits", "-1"])).is_err());
+    }
+
+    #[test]
+    fn test_bind_address_flags() {
+        let default_addr = Config::from_args(&args(&["--server"]))
+            .unwrap()
+            .bind_addr()
+            .unwrap();
+        assert_eq!(default_addr, "127.0.0.1:3030".parse().unwrap());
+
+        let config =
+            Config::from_args(&args(&["--server", "--host", "0.0.0.0", "--port", "8080"])).unwrap();
+        assert_eq!(config.bind_addr().unwrap(), "0.0.0.0:8080".parse().unwrap());
+
+        let config = Config::from_args(&args(&["--server", "--host", "localhost"])).unwrap();
+        assert!(config.bind_addr().unwrap().ip().is_loopback());
+
+        assert!(Config::from_args(&args(&["--port", "99999"])).is_err());
+        let bad_host = Config::from_args(&args(&["--host", "localhost:1"])).unwrap();
+        assert!(bad_host.bind_addr().is_err());
+    }
+
+    #[test]
+    fn test_idle_after_flag() {
+        assert_eq!(Config::default().idle_after, None);
+        let config = Config::from_args(&args(&["--server", "--idle-after", "120"])).unwrap();
+        assert_eq!(config.idle_after, Some(120));
+        assert!(Config::from_args(&args(&["--idle-after", "0"])).is_err());
+    }
+
+    #[test]
+    fn test_history_flag() {
+        assert_eq!(Config::default().history, 100);
+        let config = Config::from_args(&args(&["--server", "--history", "500"])).unwrap();
+        assert_eq!(config.history, 500);
+    }
+
+    #[test]
+    fn test_explain_flag() {
+        assert!(!Config::default().explain);
+        let config = Config::from_args(&args(&["--explain", "log.csv"])).unwrap();
+        assert!(config.explain);
+        assert_eq!(config.input.as_deref(), Some("log.csv"));
+    }
+
+    #[test]
+    fn test_dimension_must_be_at_least_two() {
+        let config = Config::from_args(&args(&["--dimension", "2", "log.csv"])).unwrap();
+        assert_eq!(config.dimension, 2);
+        assert!(Config::from_args(&args(&["--dimension", "1"])).is_err());
+        assert!(Config::from_args(&args(&["--dimension", "0"])).is_err());
+
+        let path = write_temp_config("dimension", r#"{"dimension": 1}"#);
+        let result = Config::from_args(&args(&["--config", path.to_str().unwrap()]));
+        fs::remove_file(&path).ok();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_activity_decay_must_be_a_fraction() {
+        let config = Config::from_args(&args(&["--activity-decay", "0.5", "log.csv"])).unwrap();
+        assert_eq!(config.activity_decay, 0.5);
+        assert!(Config::from_args(&args(&["--activity-decay", "1"])).is_ok());
+        assert!(Config::from_args(&args(&["--activity-decay", "1.5"])).is_err());
+        assert!(Config::from_args(&args(&["--activity-decay", "-0.1"])).is_err());
+        assert!(Config::from_args(&args(&["--activity-decay", "NaN"])).is_err());
+
+        let path = write_temp_config("decay", r#"{"activity_decay": 2.0}"#);
+        let result = Config::from_args(&args(&["--config", path.to_str().unwrap()]));
+        fs::remove_file(&path).ok();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_output_format_flag() {
+        assert_eq!(Config::default().output_format, OutputFormat::Jsonl);
+        let config = Config::from_args(&args(&["--output-format", "array", "log.csv"])).unwrap();
+        assert_eq!(config.output_format, OutputFormat::Array);
+        assert!(Config::from_args(&args(&["--output-format", "xml"])).is_err());
+    }
+
+    #[test]
+    fn test_compare_takes_two_paths() {
+        let config = Config::from_args(&args(&["--compare", "a.csv", "b.csv"])).unwrap();
+        assert_eq!(
+            config.compare,
+            Some(("a.csv".to_string(), "b.csv".to_string()))
+        );
+        assert_eq!(config.input, None);
+        assert!(Config::from_args(&args(&["--compare", "a.csv"])).is_err());
+    }
+
+    #[test]
+    fn test_out_flag() {
+        assert_eq!(Config::default().out, None);
+        let config = Config::from_args(&args(&["--out", "steps.jsonl", "log.csv"])).unwrap();
+        assert_eq!(config.out.as_deref(), Some("steps.jsonl"));
+        assert_eq!(config.input.as_deref(), Some("log.csv"));
+    }
+
+    #[test]
+    fn test_quiet_flag() {
+        assert!(!Config::default().quiet);
+        let config = Config::from_args(&args(&["--quiet", "log.csv"])).unwrap();
+        assert!(config.quiet);
+        assert_eq!(config.input.as_deref(), Some("log.csv"));
+    }
+
+    #[test]
+    fn test_trace_flag() {
+        assert!(!Config::default().trace);
+        let config = Config::from_args(&args(&["--server", "--trace"])).unwrap();
+        assert!(config.trace);
+        assert!(config.server);
+    }
+
+    #[test]
+    fn test_min_amount_flag() {
+        assert_eq!(Config::default().min_amount, None);
+        let config = Config::from_args(&args(&["--min-amount", "20", "log.csv"])).unwrap();
+        assert_eq!(config.min_amount, Some(20));
+        assert!(Config::from_args(&args(&["--min-amount", "lots"])).is_err());
+    }
+
+    #[test]
+    fn test_seats_are_numeric_flag() {
+        assert!(!Config::default().seats_are_numeric);
+        let config = Config::from_args(&args(&["--seats-are-numeric", "seats.csv"])).unwrap();
+        assert!(config.seats_are_numeric);
+        assert_eq!(config.input.as_deref(), Some("seats.csv"));
+    }
+
+    #[test]
+    fn test_disambiguate_names_flag() {
+        assert!(!Config::default().disambiguate_names);
+        let config = Config::from_args(&args(&["--disambiguate-names", "log.csv"])).unwrap();
+        assert!(config.disambiguate_names);
+        assert_eq!(config.input.as_deref(), Some("log.csv"));
+        assert!(
+            Config::from_args(&args(&["--server", "--disambiguate-names"]))
+                .unwrap()
+                .disambiguate_names
+        );
+    }
+
+    #[test]
+    fn test_player_is_repeatable() {
+        assert!(Config::default().players.is_empty());
+        let config = Config::from_args(&args(&[
+            "--player", "Bob", "--server", "--player", "Carol_p3",
+        ]))
+        .unwrap();
+        assert_eq!(config.players, ["Bob", "Carol_p3"]);
+    }
+
+    #[test]
+    fn test_allow_origin_is_repeatable() {
+        assert!(Config::default().allow_origins.is_empty());
+        let config = Config::from_args(&args(&[
+            "--server",
+            "--allow-origin",
+            "https://www.pokernow.club",
+            "--allow-origin",
+            "http://localhost:8000",
+        ]))
+        .unwrap();
+        assert_eq!(
+            config.allow_origins,
+            ["https://www.pokernow.club", "http://localhost:8000"]
+        );
+        assert!(Config::from_args(&args(&[
+            "--allow-origin",
+            "https://www.pokernow.club/games"
+        ]))
+        .is_err());
+        assert!(Config::from_args(&args(&["--allow-origin", "pokernow.club"])).is_err());
+    }
+
+    #[test]
+    fn test_unknown_flag_is_rejected() {
+        assert!(Config::from_args(&args(&["--bogus"])).is_err());
+    }
+}
@@ -1,21 +1,62 @@
 mod cli;
+mod config;
+mod pacing;
+mod report;
 mod server;
 
+use config::Config;
+use poker_parser::ParseError;
 use std::env;
+use std::error::Error;
+use std::io::IsTerminal;
+
+/// Exit codes, so scripts can tell failures apart:
+///
+/// - 0: success
+/// - 1: usage error (bad flag, missing input) or any other failure
+/// - 2: a file could not be read or written
+/// - 3: the log could not be decoded or parsed
+fn exit_code(err: &(dyn Error + 'static)) -> i32 {
+    if err.is::<std::io::Error>() || matches!(err.downcast_ref(), Some(ParseError::Io(_))) {
+        2
+    } else if err.is::<ParseError>() || err.is::<std::string::FromUtf8Error>() {
+        3
+    } else {
+        1
+    }
+}
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() {
     let args: Vec<String> = env::args().collect();
-    
+    if let Err(err) = run(&args[1..]).await {
+        eprintln!("Error: {}", err);
+        std::process::exit(exit_code(err.as_ref()));
+    }
+}
+
+async fn run(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let config = Config::from_args(args)?;
+
+    // Span timings per phase (parse, expand, matrix update) on stderr
+    if config.trace {
+        tracing_subscriber::fmt()
+            .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
+            .with_writer(std::io::stderr)
+            .with_ansi(std::io::stderr().is_terminal())
+            .init();
+    }
+
     // Check for --server flag (debug slop)
-    if args.iter().any(|arg| arg == "--server") {
+    if config.server {
         // Start the web server
-        let reset_on_fold = args.iter().any(|arg| arg == "--reset-on-fold");
-        server::start_server(reset_on_fold).await?;
+        server::start_server(&config).await?;
+    } else if let Some((first, second)) = &config.compare {
+        cli::run_compare(&config, first, second)?;
     } else {
         // Run CLI mode
-        cli::run_cli()?;
+        cli::run_cli(&config)?;
     }
-    
+
     Ok(())
 }
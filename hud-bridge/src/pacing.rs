@@ -0,0 +1,73 @@
+use std::time::{Duration, Instant};
+
+/// Client-side pacing for `--tps-limit`: spaces actions at least
+/// `1 / tps_limit` seconds apart so a slow consumer (e.g. a browser HUD)
+/// can keep up with a replay.
+#[derive(Debug, Clone)]
+pub struct Pacer {
+    interval: Duration,
+    /// Earliest instant the next action may go out
+    next_slot: Option<Instant>,
+}
+
+impl Pacer {
+    /// Creates a pacer allowing at most `tps_limit` actions per second.
+    pub fn new(tps_limit: u32) -> Self {
+        Pacer {
+            interval: Duration::from_secs(1) / tps_limit.max(1),
+            next_slot: None,
+        }
+    }
+
+    /// Reserves the next slot and returns when the action should be
+    /// submitted, given the current time `now`.
+    ///
+    /// Slots never bunch up: if the caller falls behind, the next slot starts
+    /// from `now` rather than bursting to catch up.
+    pub fn schedule(&mut self, now: Instant) -> Instant {
+        let slot = self.next_slot.map_or(now, |next| next.max(now));
+        self.next_slot = Some(slot + self.interval);
+        slot
+    }
+
+    /// Blocks until the next action may be submitted.
+    pub fn wait(&mut self) {
+        let now = Instant::now();
+        let slot = self.schedule(now);
+        if slot > now {
+            std::thread::sleep(slot - now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ten_actions_at_five_tps_span_two_seconds() {
+        // Mock clock: the submitter never blocks, so `now` stays put
+        let start = Instant::now();
+        let mut pacer = Pacer::new(5);
+        let slots: Vec<Instant> = (0..10).map(|_| pacer.schedule(start)).collect();
+
+        assert_eq!(slots[0], start);
+        assert_eq!(slots[9] - start, Duration::from_millis(1800));
+        // The 10 actions occupy two seconds before the next slot opens
+        assert_eq!(pacer.schedule(start) - start, Duration::from_secs(2));
+        for pair in slots.windows(2) {
+            assert_eq!(pair[1] - pair[0], Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_slow_submitter_is_not_delayed() {
+        let start = Instant::now();
+        let mut pacer = Pacer::new(5);
+        pacer.schedule(start);
+
+        let later = start + Duration::from_secs(3);
+        assert_eq!(pacer.schedule(later), later);
+        assert_eq!(pacer.schedule(later), later + Duration::from_millis(200));
+    }
+}
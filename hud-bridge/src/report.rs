@@ -0,0 +1,134 @@
+use braid_engine::{ActionType, Generator, Seat};
+use std::collections::BTreeMap;
+
+/// Rounds a reported metric to `digits` decimal places (`None` keeps full
+/// precision). Only applied when building output, never to internal state.
+pub fn round_metric(value: f64, digits: Option<u32>) -> f64 {
+    match digits {
+        Some(digits) => {
+            let scale = 10f64.powi(digits as i32);
+            (value * scale).round() / scale
+        }
+        None => value,
+    }
+}
+
+/// Action types counted per seat, in report column order.
+pub const COUNTED_ACTIONS: [ActionType; 7] = [
+    ActionType::Fold,
+    ActionType::Check,
+    ActionType::Call,
+    ActionType::Bet,
+    ActionType::Raise,
+    ActionType::ReRaise,
+    ActionType::AllIn,
+];
+
+/// Running totals for one seat across the whole session (never reset per hand).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeatSummary {
+    /// Net crossings initiated by this seat
+    pub writhe: i32,
+    /// |Burau diagonal| for this seat after its most recent action
+    pub complexity: f64,
+    /// Counts in `COUNTED_ACTIONS` order
+    pub action_counts: [usize; COUNTED_ACTIONS.len()],
+}
+
+/// Per-seat summary of a processed log, written by `--player-report`.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerReport {
+    seats: BTreeMap<usize, SeatSummary>,
+}
+
+impl PlayerReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        PlayerReport::default()
+    }
+
+    /// Records one action by `seat` and the generators it produced.
+    pub fn record(
+        &mut self,
+        seat: Seat,
+        action_type: ActionType,
+        generators: &[Generator],
+        complexity: f64,
+    ) {
+        let summary = self.seats.entry(seat.value()).or_default();
+        summary.writhe += generators
+            .iter()
+            .map(|gen| if gen.is_overcrossing() { 1 } else { -1 })
+            .sum::<i32>();
+        summary.complexity = complexity;
+        if let Some(i) = COUNTED_ACTIONS
+            .iter()
+            .position(|&counted| counted == action_type)
+        {
+            summary.action_counts[i] += 1;
+        }
+    }
+
+    /// Drops the seats for which `keep` returns false.
+    pub fn retain(&mut self, mut keep: impl FnMut(Seat) -> bool) {
+        self.seats.retain(|&seat, _| keep(Seat::new(seat)));
+    }
+
+    /// Writes one CSV row per seat, ordered by seat number.
+    ///
+    /// Columns: `seat, name, writhe, complexity` followed by one count column
+    /// per action type. `name` resolves a seat to its display name and
+    /// complexity is rounded to `round_digits` decimals if set.
+    pub fn write_csv(
+        &self,
+        path: &str,
+        round_digits: Option<u32>,
+        name: impl Fn(Seat) -> String,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let file = std::fs::File::create(path).map_err(|e| {
+            std::io::Error::new(
+                e.kind(),
+                format!("Failed to write player report '{}': {}", path, e),
+            )
+        })?;
+        let mut writer = csv::Writer::from_writer(file);
+
+        let mut header = vec!["seat", "name", "writhe", "complexity"];
+        header.extend(
+            COUNTED_ACTIONS
+                .iter()
+                .map(|&action_type| column_name(action_type)),
+        );
+        writer.write_record(&header)?;
+
+        for (&seat, summary) in &self.seats {
+            let mut row = vec![
+                seat.to_string(),
+                name(Seat::new(seat)),
+                summary.writhe.to_string(),
+                round_metric(summary.complexity, round_digits).to_string(),
+            ];
+            row.extend(summary.action_counts.iter().map(|count| count.to_string()));
+            writer.write_record(&row)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// CSV column name for an action type count.
+fn column_name(action_type: ActionType) -> &'static str {
+    match action_type {
+        ActionType::Fold => "folds",
+        ActionType::Check => "checks",
+        ActionType::Call => "calls",
+        ActionType::Bet => "bets",
+        ActionType::Raise => "raises",
+        ActionType::ReRaise => "reraises",
+        ActionType::AllIn => "allins",
+        ActionType::Reset => "resets",
+        ActionType::StreetBoundary => "streets",
+        ActionType::StandUp => "stand_ups",
+        ActionType::SitBack => "sit_backs",
+    }
+}
@@ -1,10 +1,19 @@
-use braid_engine::{expand_action, Action, ActionType, FingerprintState, Seat};
+use crate::config::Config;
+use crate::report::round_metric;
+use braid_engine::{
+    expand_action, expand_wrap_aggressor, Action, ActionType, BraidWord, FingerprintState,
+    FlowModel, Seat,
+};
 use futures::{SinkExt, StreamExt};
-use poker_parser::{pokernow, SeatResolver};
-use std::sync::Arc;
-use std::collections::HashMap;
+use poker_parser::{pokernow, NameDisambiguator, SeatResolver};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::sync::{Arc, Mutex};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, RwLock};
-use warp::Filter;
+use warp::{Filter, Reply};
 
 /// Shared state for the server
 pub type SharedState = Arc<RwLock<ServerState>>;
@@ -14,25 +23,948 @@ pub type SharedState = Arc<RwLock<ServerState>>;
 pub struct ServerState {
     pub fingerprint: FingerprintState,
     pub seat_resolver: SeatResolver,
+    /// Splits ID-less players sharing a name (`--disambiguate-names`)
+    pub name_disambiguator: Option<NameDisambiguator>,
     pub current_seat: Option<Seat>,
+    /// Seat that made the last bet/raise in the current hand
+    pub aggressor: Option<Seat>,
+    pub flow_model: FlowModel,
     pub step: usize,
+    /// Actions in the current hand (0 right after a hand reset)
+    pub hand_action_index: usize,
+    /// Actions processed over the whole session, never reset
+    pub total_actions: usize,
+    /// Hands ended by an explicit reset ("-- starting hand")
+    pub explicit_resets: usize,
+    /// Hands ended by a fold with `reset_on_fold`
+    pub fold_resets: usize,
+    /// Chips committed to the pot in the current hand
+    pub pot: u64,
+    /// Chips each seat has put in on the current street, so a "raises to"
+    /// total only adds the difference
+    pub street_contributions: HashMap<usize, i64>,
+    /// Highest-complexity moment of the current hand
+    pub peak: Option<Peak>,
     pub reset_on_fold: bool,
+    /// Betting actions below this amount move the flow but braid nothing
+    pub min_amount: Option<i64>,
+    /// Number seats from the dealer button named in PokerNow hand headers
+    pub button_relative: bool,
+    /// Round float metrics in responses to this many decimals
+    pub round_digits: Option<u32>,
+    /// Raw action strings accepted since the last hand boundary, replayed
+    /// to restore a snapshot
+    pub action_log: Vec<String>,
+    /// What the hands already dropped from `action_log` built up (`None`
+    /// until the first hand boundary)
+    log_base: Option<SessionBase>,
+    /// `logged_entries` of the newest autosave written, shared with the
+    /// threads writing them
+    autosave_written: Arc<Mutex<usize>>,
+    /// Write a snapshot every N accepted actions (disabled when `None`)
+    pub autosave_every: Option<usize>,
+    /// Where autosave snapshots are written
+    pub autosave_path: String,
+    /// Optional trace of the running braid word (see `enable_debug_word`)
+    pub debug_word: Option<Arc<Mutex<DebugWordLog>>>,
+    /// Drop an action identical to the previous one within this window
+    pub dedup_window: Option<Duration>,
+    /// The last accepted raw action string and when it arrived
+    last_ingested: Option<(String, Instant)>,
+    /// Arrival times of recent actions, for `tempo`
+    action_times: VecDeque<Instant>,
+    /// Broadcast an idle event once no action has arrived for this long
+    pub idle_after: Option<Duration>,
+    /// When the last action arrived
+    last_action_at: Option<Instant>,
+    /// True once the idle event was sent, until the next action
+    idle: bool,
+    /// The most recent responses, oldest first, for `GET /history`
+    pub history: VecDeque<FingerprintResponse>,
+    /// Maximum number of responses kept in `history` (0 disables it)
+    pub history_capacity: usize,
+}
+
+/// The step in a hand at which the Burau trace magnitude peaked.
+///
+/// Only actions that moved the flow are considered.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Peak {
+    pub step: usize,
+    pub action: String,
+    pub burau: f64,
+}
+
+/// Flush the debug word file after this many buffered lines.
+const DEBUG_WORD_FLUSH_EVERY: usize = 16;
+
+/// Action log entry recorded for a `POST /reset`, so snapshots replay it as
+/// a hand boundary.
+const FORCED_RESET_ENTRY: &str = "-- starting hand (forced reset) --";
+
+/// Sliding window over which the table tempo is measured.
+const TEMPO_WINDOW: Duration = Duration::from_secs(60);
+
+/// Buffered append-only trace of the braid word after each action.
+///
+/// Each line is `<step>\t<generators>` holding only the generators the
+/// action appended, in σ notation. When the word no longer extends the
+/// previous line's (first line, hand reset, undo, normalization) the line is
+/// `<step>\t= <word>` with the whole word instead, and later lines build on
+/// it. Writes are buffered and flushed every `DEBUG_WORD_FLUSH_EVERY` lines
+/// (and on drop).
+#[derive(Debug)]
+pub struct DebugWordLog {
+    writer: BufWriter<File>,
+    pending: usize,
+    /// The word as of the last line written
+    last_word: Option<BraidWord>,
+}
+
+impl DebugWordLog {
+    /// Opens `path` for appending, creating it if needed.
+    pub fn open(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open debug word file '{}': {}", path, e))?;
+        Ok(DebugWordLog {
+            writer: BufWriter::new(file),
+            pending: 0,
+            last_word: None,
+        })
+    }
+
+    /// Appends the line for `word` at `step`, flushing if enough lines are
+    /// buffered.
+    pub fn append(&mut self, step: usize, word: &BraidWord) -> std::io::Result<()> {
+        match &self.last_word {
+            Some(last)
+                if word.len() >= last.len()
+                    && word.iter().zip(last.iter()).all(|(a, b)| a == b) =>
+            {
+                let added: Vec<String> = word
+                    .iter()
+                    .skip(last.len())
+                    .map(|g| g.to_string())
+                    .collect();
+                writeln!(self.writer, "{}\t{}", step, added.join(" "))?;
+            }
+            _ if word.is_empty() => writeln!(self.writer, "{}\t=", step)?,
+            _ => writeln!(self.writer, "{}\t= {}", step, word)?,
+        }
+        self.last_word = Some(word.clone());
+        self.pending += 1;
+        if self.pending >= DEBUG_WORD_FLUSH_EVERY {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Flushes any buffered lines to disk.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.pending = 0;
+        self.writer.flush()
+    }
+}
+
+/// Replayable snapshot of a `ServerState`.
+///
+/// Only the configuration, the session totals of earlier hands and the
+/// current hand's raw action log are stored; the derived fingerprint (Burau
+/// matrix, per-seat stats) is rebuilt by replaying.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub reset_on_fold: bool,
+    #[serde(default)]
+    pub flow_model: FlowModel,
+    #[serde(default)]
+    pub button_relative: bool,
+    #[serde(default)]
+    pub min_amount: Option<i64>,
+    #[serde(default)]
+    pub players: Vec<String>,
+    #[serde(default)]
+    pub disambiguate_names: bool,
+    pub dimension: usize,
+    pub activity_decay: f64,
+    #[serde(default)]
+    pub normalization_window: Option<usize>,
+    /// Session totals to start from (snapshots without one replay the whole
+    /// session from `actions`)
+    #[serde(default)]
+    pub base: Option<SessionBase>,
+    pub actions: Vec<String>,
+}
+
+/// Session state built up by actions already dropped from the action log.
+///
+/// The log is cut at every hand boundary so it doesn't grow for as long as
+/// the server runs; whatever carries over from one hand to the next is kept
+/// here instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBase {
+    /// Log entries dropped so far, hand boundaries included
+    pub entries: usize,
+    pub seat_resolver: SeatResolver,
+    #[serde(default)]
+    pub name_disambiguator: Option<NameDisambiguator>,
+    pub total_actions: usize,
+    pub explicit_resets: usize,
+    pub fold_resets: usize,
+    pub generator_histogram: Vec<usize>,
+    pub collisions: usize,
+    pub dropped_generators: usize,
+    /// Seats whose player is standing up
+    pub inactive_seats: Vec<usize>,
+}
+
+/// A snapshot serialized for the autosave file. It is taken under the state
+/// lock and written once the handler is done with it (see `spawn`).
+struct Autosave {
+    /// `logged_entries` when the snapshot was taken
+    entries: usize,
+    path: String,
+    json: String,
+    /// `entries` of the newest autosave written so far
+    written: Arc<Mutex<usize>>,
+}
+
+impl Autosave {
+    /// Writes the snapshot through a temporary file, so the autosave is never
+    /// left half-written, unless a newer one made it to disk first (writes
+    /// spawned by different requests can finish out of order).
+    fn write(self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut written = self.written.lock().unwrap_or_else(|e| e.into_inner());
+        if *written > self.entries {
+            return Ok(());
+        }
+        let tmp_path = format!("{}.tmp", self.path);
+        fs::write(&tmp_path, &self.json)
+            .and_then(|()| fs::rename(&tmp_path, &self.path))
+            .map_err(|e| format!("Failed to write snapshot '{}': {}", self.path, e))?;
+        *written = self.entries;
+        Ok(())
+    }
+
+    /// Writes the snapshot on a blocking thread, off the async runtime.
+    fn spawn(self) {
+        tokio::task::spawn_blocking(move || {
+            if let Err(e) = self.write() {
+                eprintln!("Autosave failed: {}", e);
+            }
+        });
+    }
 }
 
 impl ServerState {
-    pub fn new(reset_on_fold: bool) -> Self {
-        // Use dimension 12 to provide buffer for player churn
-        // Even on 9-handed tables, this reduces hash collisions before modulo mapping kicks in, as I've found out the hard way xd
+    /// Returns a builder with the default configuration.
+    pub fn builder() -> ServerStateBuilder {
+        ServerStateBuilder::default()
+    }
+
+    /// Creates a server state from the shared CLI/server config.
+    pub fn from_config(config: &Config) -> Self {
+        ServerState::builder()
+            .reset_on_fold(config.reset_on_fold)
+            .dimension(config.dimension)
+            .activity_decay(config.activity_decay)
+            .flow_model(config.flow_model)
+            .autosave(config.autosave, config.autosave_path.clone())
+            .dedup_consecutive(
+                config
+                    .dedup_consecutive
+                    .then(|| Duration::from_millis(config.dedup_window_ms)),
+            )
+            .normalization_window(config.normalization_window)
+            .button_relative(config.button_relative)
+            .round_digits(config.round_digits)
+            .idle_after(config.idle_after.map(Duration::from_secs))
+            .history_capacity(config.history)
+            .min_amount(config.min_amount)
+            .players(config.players.clone())
+            .disambiguate_names(config.disambiguate_names)
+            .build()
+    }
+
+    /// A fresh state with this one's settings, for another table. Autosave
+    /// and debug word tracing stay with the original.
+    fn new_table(&self) -> ServerState {
+        ServerState::builder()
+            .reset_on_fold(self.reset_on_fold)
+            .min_amount(self.min_amount)
+            .dimension(self.fingerprint.dimension())
+            .activity_decay(self.fingerprint.activity_decay)
+            .flow_model(self.flow_model)
+            .dedup_consecutive(self.dedup_window)
+            .normalization_window(self.fingerprint.normalization_window())
+            .button_relative(self.button_relative)
+            .round_digits(self.round_digits)
+            .idle_after(self.idle_after)
+            .history_capacity(self.history_capacity)
+            .players(self.fingerprint.player_filter().to_vec())
+            .disambiguate_names(self.name_disambiguator.is_some())
+            .build()
+    }
+
+    /// Captures the configuration and action log needed to rebuild this state.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            reset_on_fold: self.reset_on_fold,
+            flow_model: self.flow_model,
+            button_relative: self.button_relative,
+            min_amount: self.min_amount,
+            players: self.fingerprint.player_filter().to_vec(),
+            disambiguate_names: self.name_disambiguator.is_some(),
+            dimension: self.fingerprint.dimension(),
+            activity_decay: self.fingerprint.activity_decay,
+            normalization_window: self.fingerprint.normalization_window(),
+            base: self.log_base.clone(),
+            actions: self.action_log.clone(),
+        }
+    }
+
+    /// Rebuilds a state by replaying a snapshot under `config`.
+    ///
+    /// Everything that shapes the braid (dimension, flow model, reset on
+    /// fold, normalization window, ...) comes from the snapshot, so the
+    /// session picks up with the word it had; serving options (autosave,
+    /// dedup, rounding, history, idle events) come from `config`.
+    pub fn restore(
+        snapshot: &Snapshot,
+        config: &Config,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = config.clone();
+        config.reset_on_fold = snapshot.reset_on_fold;
+        config.dimension = snapshot.dimension;
+        config.activity_decay = snapshot.activity_decay;
+        config.flow_model = snapshot.flow_model;
+        config.normalization_window = snapshot.normalization_window;
+        config.button_relative = snapshot.button_relative;
+        config.min_amount = snapshot.min_amount;
+        config.players = snapshot.players.clone();
+        config.disambiguate_names = snapshot.disambiguate_names;
+
+        let mut state = ServerState::from_config(&config);
+        state.replay(snapshot.base.clone(), &snapshot.actions)?;
+        Ok(state)
+    }
+
+    /// Replays a log onto this fresh state: the session totals of `base`
+    /// first, then every action string.
+    fn replay(
+        &mut self,
+        base: Option<SessionBase>,
+        actions: &[String],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(base) = base {
+            self.apply_base(base);
+        }
+        for action_string in actions {
+            let action = parse_action_string(action_string, self)?;
+            let hand_boundary = action.action_type == ActionType::Reset;
+            process_action(action, self)?;
+            self.log_action(action_string.clone(), hand_boundary);
+        }
+        Ok(())
+    }
+
+    /// Rebuilds the seating and braid from the log, e.g. after a processing
+    /// step panicked halfway and left them inconsistent. The log only holds
+    /// actions that went through, so the failed one is dropped. Settings,
+    /// history, tempo, autosave and the debug word trace are kept as they
+    /// are; registered `Invariant` plugins are not part of the log and are
+    /// dropped too.
+    fn rebuild_from_log(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut rebuilt = self.new_table();
+        rebuilt.replay(self.log_base.clone(), &self.action_log)?;
+        self.fingerprint = rebuilt.fingerprint;
+        self.seat_resolver = rebuilt.seat_resolver;
+        self.name_disambiguator = rebuilt.name_disambiguator;
+        self.current_seat = rebuilt.current_seat;
+        self.aggressor = rebuilt.aggressor;
+        self.step = rebuilt.step;
+        self.hand_action_index = rebuilt.hand_action_index;
+        self.total_actions = rebuilt.total_actions;
+        self.explicit_resets = rebuilt.explicit_resets;
+        self.fold_resets = rebuilt.fold_resets;
+        self.pot = rebuilt.pot;
+        self.street_contributions = rebuilt.street_contributions;
+        self.peak = rebuilt.peak;
+        Ok(())
+    }
+
+    /// Appends an accepted action to the log. A hand boundary closes the
+    /// log: what the session has built up moves to the log base and only the
+    /// next hand's actions are kept.
+    fn log_action(&mut self, action_string: String, hand_boundary: bool) {
+        self.action_log.push(action_string);
+        if !hand_boundary {
+            return;
+        }
+        self.log_base = Some(SessionBase {
+            entries: self.logged_entries(),
+            seat_resolver: self.seat_resolver.clone(),
+            name_disambiguator: self.name_disambiguator.clone(),
+            total_actions: self.total_actions,
+            explicit_resets: self.explicit_resets,
+            fold_resets: self.fold_resets,
+            generator_histogram: self.fingerprint.generator_histogram.clone(),
+            collisions: self.fingerprint.collisions,
+            dropped_generators: self.fingerprint.dropped_generators,
+            inactive_seats: self.fingerprint.inactive_seats(),
+        });
+        self.action_log.clear();
+    }
+
+    /// Log entries accepted this session, the ones already dropped included.
+    fn logged_entries(&self) -> usize {
+        self.log_base.as_ref().map_or(0, |base| base.entries) + self.action_log.len()
+    }
+
+    /// Starts a fresh state from the session totals of a snapshot.
+    fn apply_base(&mut self, base: SessionBase) {
+        self.seat_resolver = base.seat_resolver.clone();
+        if self.name_disambiguator.is_some() {
+            self.name_disambiguator = base.name_disambiguator.clone();
+        }
+        self.total_actions = base.total_actions;
+        self.explicit_resets = base.explicit_resets;
+        self.fold_resets = base.fold_resets;
+        self.fingerprint.generator_histogram = base.generator_histogram.clone();
+        self.fingerprint.collisions = base.collisions;
+        self.fingerprint.dropped_generators = base.dropped_generators;
+        for &seat in &base.inactive_seats {
+            self.fingerprint.set_seat_active(seat, false);
+        }
+        self.log_base = Some(base);
+    }
+
+    /// Restores a state from a JSON snapshot file (see `restore`).
+    pub fn load_snapshot(path: &str, config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read snapshot '{}': {}", path, e))?;
+        let snapshot: Snapshot = serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid snapshot '{}': {}", path, e))?;
+        ServerState::restore(&snapshot, config)
+    }
+
+    /// Starts appending the running braid word to `path` after each action.
+    pub fn enable_debug_word(&mut self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.debug_word = Some(Arc::new(Mutex::new(DebugWordLog::open(path)?)));
+        Ok(())
+    }
+
+    /// Appends the current braid word to the debug file, if enabled.
+    fn trace_debug_word(&self) {
+        let Some(log) = &self.debug_word else {
+            return;
+        };
+        let result = match log.lock() {
+            Ok(mut log) => log.append(self.step, &self.fingerprint.word),
+            Err(_) => return,
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to write debug word: {}", e);
+        }
+    }
+
+    /// Returns true if `action_string` repeats the previously accepted action
+    /// within the dedup window, and remembers it as the latest otherwise.
+    ///
+    /// The whole line is compared, so the same action by two different
+    /// players (e.g. consecutive checks) is never treated as a duplicate.
+    fn is_duplicate(&mut self, action_string: &str, now: Instant) -> bool {
+        let Some(window) = self.dedup_window else {
+            return false;
+        };
+        if let Some((last, at)) = &self.last_ingested {
+            if last == action_string && now.duration_since(*at) <= window {
+                return true;
+            }
+        }
+        self.last_ingested = Some((action_string.to_string(), now));
+        false
+    }
+
+    /// Remembers that an action arrived at `now`, forgetting those that
+    /// have fallen out of the tempo window.
+    fn record_tempo(&mut self, now: Instant) {
+        self.action_times.push_back(now);
+        while self
+            .action_times
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > TEMPO_WINDOW)
+        {
+            self.action_times.pop_front();
+        }
+    }
+
+    /// Actions per minute over the last `TEMPO_WINDOW`, measured from the
+    /// oldest action in the window up to `now`.
+    ///
+    /// Every accepted action counts, folds and resets included. `None` until
+    /// there are two actions in the window to measure between.
+    pub fn tempo(&self, now: Instant) -> Option<f64> {
+        let recent: Vec<&Instant> = self
+            .action_times
+            .iter()
+            .filter(|at| now.duration_since(**at) <= TEMPO_WINDOW)
+            .collect();
+        let elapsed = now.duration_since(**recent.first()?).as_secs_f64();
+        if recent.len() < 2 || elapsed <= 0.0 {
+            return None;
+        }
+        Some(recent.len() as f64 * 60.0 / elapsed)
+    }
+
+    /// Returns the idle event once the table has been quiet for `idle_after`.
+    ///
+    /// Only fires once per quiet spell; `mark_active` re-arms it. Nothing is
+    /// reported before the first action.
+    pub fn check_idle(&mut self, now: Instant) -> Option<TableEvent> {
+        let threshold = self.idle_after?;
+        let last = self.last_action_at?;
+        let quiet = now.duration_since(last);
+        if self.idle || quiet < threshold {
+            return None;
+        }
+        self.idle = true;
+        Some(TableEvent::Idle {
+            secs: quiet.as_secs(),
+        })
+    }
+
+    /// Records that an action arrived at `now`, returning the active event if
+    /// the table had been reported idle.
+    pub fn mark_active(&mut self, now: Instant) -> Option<TableEvent> {
+        self.last_action_at = Some(now);
+        std::mem::take(&mut self.idle).then_some(TableEvent::Active)
+    }
+
+    /// Appends a processed response to the history, evicting the oldest
+    /// entries past `history_capacity`.
+    fn record_history(&mut self, response: &FingerprintResponse) {
+        if self.history_capacity == 0 {
+            return;
+        }
+        while self.history.len() >= self.history_capacity {
+            self.history.pop_front();
+        }
+        self.history.push_back(response.clone());
+    }
+
+    /// The last `limit` responses (all of them if `None`), oldest first.
+    pub fn recent_history(&self, limit: Option<usize>) -> Vec<FingerprintResponse> {
+        let skip = limit.map_or(0, |limit| self.history.len().saturating_sub(limit));
+        self.history.iter().skip(skip).cloned().collect()
+    }
+
+    /// Hands played this session, however they ended.
+    pub fn hand_count(&self) -> usize {
+        self.explicit_resets + self.fold_resets
+    }
+
+    /// Global metrics for a response, rounded as configured.
+    fn global_metrics(&self) -> GlobalMetrics {
+        let round = |value| round_metric(value, self.round_digits);
+        GlobalMetrics {
+            writhe: self.fingerprint.writhe,
+            burau: round(self.fingerprint.burau_trace_magnitude()),
+            invariants: self
+                .fingerprint
+                .invariant_values()
+                .into_iter()
+                .map(|(name, value)| (name, round(value)))
+                .collect(),
+        }
+    }
+
+    /// Per-seat metrics for a response, keyed by seat number.
+    fn player_metrics(&self) -> HashMap<String, PlayerMetrics> {
+        let ranking = self.fingerprint.activity_ranking();
+        self.fingerprint
+            .player_stats
+            .iter()
+            .map(|(seat_num, metrics)| {
+                (
+                    seat_num.to_string(),
+                    PlayerMetrics {
+                        name: metrics.name.clone(),
+                        writhe: metrics.writhe,
+                        complexity: round_metric(metrics.complexity, self.round_digits),
+                        activity: round_metric(metrics.activity, self.round_digits),
+                        active: self.fingerprint.is_seat_active(*seat_num),
+                        activity_rank: ranking
+                            .iter()
+                            .position(|seat| seat == seat_num)
+                            .map(|i| i + 1),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// The current fingerprint as an update, for clients that just connected.
+    fn current_response(&self) -> FingerprintResponse {
+        FingerprintResponse {
+            step: self.step,
+            hand_action_index: self.hand_action_index,
+            total_actions: self.total_actions,
+            hand_count: self.hand_count(),
+            action: "--- CURRENT STATE ---".to_string(),
+            pot: self.pot,
+            pot_odds: None,
+            global_metrics: self.global_metrics(),
+            player_metrics: self.player_metrics(),
+        }
+    }
+
+    /// Serializes a snapshot if autosave is enabled and the cadence is due.
+    /// Only the serialization needs the state; write the file with
+    /// `Autosave::spawn`.
+    fn autosave_if_due(&self) -> Option<Autosave> {
+        let every = self.autosave_every?;
+        if every == 0 || !self.logged_entries().is_multiple_of(every) {
+            return None;
+        }
+        self.autosave()
+            .map_err(|e| eprintln!("Autosave failed: {}", e))
+            .ok()
+    }
+
+    /// Serializes a snapshot for the autosave path.
+    fn autosave(&self) -> Result<Autosave, serde_json::Error> {
+        Ok(Autosave {
+            entries: self.logged_entries(),
+            path: self.autosave_path.clone(),
+            json: serde_json::to_string(&self.snapshot())?,
+            written: Arc::clone(&self.autosave_written),
+        })
+    }
+}
+
+/// Builder exposing every configuration knob of `ServerState`.
+#[derive(Debug, Clone)]
+pub struct ServerStateBuilder {
+    reset_on_fold: bool,
+    min_amount: Option<i64>,
+    dimension: usize,
+    activity_decay: f64,
+    flow_model: FlowModel,
+    autosave_every: Option<usize>,
+    autosave_path: String,
+    dedup_window: Option<Duration>,
+    normalization_window: Option<usize>,
+    button_relative: bool,
+    round_digits: Option<u32>,
+    idle_after: Option<Duration>,
+    history_capacity: usize,
+    players: Vec<String>,
+    disambiguate_names: bool,
+}
+
+impl Default for ServerStateBuilder {
+    fn default() -> Self {
+        let config = Config::default();
+        ServerStateBuilder {
+            reset_on_fold: config.reset_on_fold,
+            min_amount: config.min_amount,
+            dimension: config.dimension,
+            activity_decay: config.activity_decay,
+            flow_model: config.flow_model,
+            autosave_every: config.autosave,
+            autosave_path: config.autosave_path,
+            dedup_window: None,
+            normalization_window: config.normalization_window,
+            button_relative: config.button_relative,
+            round_digits: config.round_digits,
+            idle_after: None,
+            history_capacity: config.history,
+            players: config.players,
+            disambiguate_names: config.disambiguate_names,
+        }
+    }
+}
+
+impl ServerStateBuilder {
+    /// Resets the fingerprint on every fold.
+    pub fn reset_on_fold(mut self, reset_on_fold: bool) -> Self {
+        self.reset_on_fold = reset_on_fold;
+        self
+    }
+
+    /// Lets betting actions below `min_amount` move the flow without braiding.
+    pub fn min_amount(mut self, min_amount: Option<i64>) -> Self {
+        self.min_amount = min_amount;
+        self
+    }
+
+    /// Sets the number of strands/seats for the fingerprint.
+    pub fn dimension(mut self, dimension: usize) -> Self {
+        self.dimension = dimension;
+        self
+    }
+
+    /// Sets the per-step activity decay factor.
+    pub fn activity_decay(mut self, activity_decay: f64) -> Self {
+        self.activity_decay = activity_decay;
+        self
+    }
+
+    /// Sets how calls against an open bet are braided.
+    pub fn flow_model(mut self, flow_model: FlowModel) -> Self {
+        self.flow_model = flow_model;
+        self
+    }
+
+    /// Writes a snapshot to `path` every `every` accepted actions.
+    pub fn autosave(mut self, every: Option<usize>, path: String) -> Self {
+        self.autosave_every = every;
+        self.autosave_path = path;
+        self
+    }
+
+    /// Drops an action identical to the previous one arriving within `window`.
+    pub fn dedup_consecutive(mut self, window: Option<Duration>) -> Self {
+        self.dedup_window = window;
+        self
+    }
+
+    /// Keeps the fingerprint's braid word freely reduced past `window` generators.
+    pub fn normalization_window(mut self, window: Option<usize>) -> Self {
+        self.normalization_window = window;
+        self
+    }
+
+    /// Feeds button-relative seats (button = seat 1) into the braid.
+    pub fn button_relative(mut self, button_relative: bool) -> Self {
+        self.button_relative = button_relative;
+        self
+    }
+
+    /// Rounds float metrics in responses to `digits` decimals (`None` = full precision).
+    pub fn round_digits(mut self, digits: Option<u32>) -> Self {
+        self.round_digits = digits;
+        self
+    }
+
+    /// Broadcasts an idle event after `after` without actions (`None` = never).
+    pub fn idle_after(mut self, after: Option<Duration>) -> Self {
+        self.idle_after = after;
+        self
+    }
+
+    /// Keeps the last `capacity` responses for `GET /history` (0 = none).
+    pub fn history_capacity(mut self, capacity: usize) -> Self {
+        self.history_capacity = capacity;
+        self
+    }
+
+    /// Keeps per-seat metrics only for these players (everyone if empty).
+    pub fn players(mut self, players: Vec<String>) -> Self {
+        self.players = players;
+        self
+    }
+
+    /// Tells apart ID-less players who share a display name (heuristic).
+    pub fn disambiguate_names(mut self, disambiguate_names: bool) -> Self {
+        self.disambiguate_names = disambiguate_names;
+        self
+    }
+
+    /// Builds the server state.
+    pub fn build(self) -> ServerState {
+        let mut fingerprint = FingerprintState::new(self.dimension);
+        fingerprint.activity_decay = self.activity_decay;
+        fingerprint.set_normalization_window(self.normalization_window);
+        fingerprint.set_player_filter(self.players);
+
         ServerState {
-            fingerprint: FingerprintState::new(12),
+            fingerprint,
             seat_resolver: SeatResolver::new(),
+            name_disambiguator: self.disambiguate_names.then(NameDisambiguator::new),
             current_seat: None,
+            aggressor: None,
+            flow_model: self.flow_model,
             step: 0,
-            reset_on_fold,
+            hand_action_index: 0,
+            total_actions: 0,
+            explicit_resets: 0,
+            fold_resets: 0,
+            pot: 0,
+            street_contributions: HashMap::new(),
+            peak: None,
+            reset_on_fold: self.reset_on_fold,
+            min_amount: self.min_amount,
+            button_relative: self.button_relative,
+            round_digits: self.round_digits,
+            action_log: Vec::new(),
+            log_base: None,
+            autosave_written: Arc::new(Mutex::new(0)),
+            autosave_every: self.autosave_every,
+            autosave_path: self.autosave_path,
+            debug_word: None,
+            dedup_window: self.dedup_window,
+            last_ingested: None,
+            action_times: VecDeque::new(),
+            idle_after: self.idle_after,
+            last_action_at: None,
+            idle: false,
+            history: VecDeque::new(),
+            history_capacity: self.history_capacity,
+        }
+    }
+}
+
+/// Wraps a server state for sharing between handlers and creates the
+/// broadcast channel used to fan out events to WebSocket clients.
+pub fn shared_state(state: ServerState) -> (SharedState, broadcast::Sender<ServerEvent>) {
+    let (tx, _rx) = broadcast::channel::<ServerEvent>(100);
+    (Arc::new(RwLock::new(state)), tx)
+}
+
+/// Query parameter selecting a table, e.g. `/action?table=7`.
+#[derive(serde::Deserialize)]
+pub struct TableQuery {
+    pub table: Option<String>,
+}
+
+/// Most tables besides the default one a server will hold.
+pub const MAX_TABLES: usize = 64;
+
+/// Independent sessions for running several tables through one server.
+///
+/// Requests without `?table=` go to the default table, the one the server
+/// started with (which also owns autosave and the Unix socket). Other tables
+/// are created by their first POST `/action` or `/reset` with the default
+/// table's settings (at most `MAX_TABLES`), each with its own state and
+/// broadcast channel so subscribers only see their table.
+#[derive(Clone)]
+pub struct Tables {
+    default: Table,
+    tables: Arc<RwLock<HashMap<String, Table>>>,
+}
+
+/// One table's state and the channel its updates are broadcast on.
+type Table = (SharedState, broadcast::Sender<ServerEvent>);
+
+impl Tables {
+    /// Wraps the default table.
+    pub fn new(state: SharedState, tx: broadcast::Sender<ServerEvent>) -> Self {
+        Tables {
+            default: (state, tx),
+            tables: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// The state and broadcast channel of `table` (the default table for
+    /// `None`), or `UnknownTable` if no such table has been created.
+    pub async fn get(&self, table: Option<&str>) -> Result<Table, UnknownTable> {
+        let Some(id) = table else {
+            return Ok(self.default.clone());
+        };
+        self.tables
+            .read()
+            .await
+            .get(id)
+            .cloned()
+            .ok_or_else(|| UnknownTable(id.to_string()))
+    }
+
+    /// Like `get`, but creates the table if it doesn't exist yet, unless
+    /// `MAX_TABLES` are already open.
+    pub async fn get_or_create(&self, table: Option<&str>) -> Result<Table, TableLimit> {
+        let Some(id) = table else {
+            return Ok(self.default.clone());
+        };
+        if let Some(existing) = self.tables.read().await.get(id) {
+            return Ok(existing.clone());
+        }
+
+        let fresh = self.default.0.read().await.new_table();
+        let mut tables = self.tables.write().await;
+        if let Some(existing) = tables.get(id) {
+            return Ok(existing.clone());
         }
+        if tables.len() >= MAX_TABLES {
+            return Err(TableLimit(MAX_TABLES));
+        }
+        let watch_idle = fresh.idle_after.is_some();
+        let (state, tx) = shared_state(fresh);
+        if watch_idle {
+            spawn_idle_watch(&state, tx.clone());
+        }
+        tables.insert(id.to_string(), (state.clone(), tx.clone()));
+        Ok((state, tx))
+    }
+
+    /// Closes a table. Its idle watcher stops once the last request using
+    /// it finishes. Returns false if there was no such table.
+    pub async fn remove(&self, table: &str) -> bool {
+        self.tables.write().await.remove(table).is_some()
     }
 }
 
+/// JSON response for GET /state
+#[derive(serde::Serialize, Clone)]
+pub struct StateResponse {
+    pub step: usize,
+    pub total_actions: usize,
+    /// Hands played this session (`explicit_resets + fold_resets`)
+    pub hand_count: usize,
+    pub explicit_resets: usize,
+    pub fold_resets: usize,
+    /// Moves lost to seats wrapping onto the same strand (dimension too small)
+    pub collisions: usize,
+    pub pot: u64,
+    #[serde(rename = "global")]
+    pub global_metrics: GlobalMetrics,
+    #[serde(rename = "players")]
+    pub player_metrics: HashMap<String, PlayerMetrics>,
+    pub peak: Option<Peak>,
+    /// Actions per minute over the last minute (`null` until measurable)
+    pub tempo: Option<f64>,
+    /// Session-long generator count per index (`[0]` is σ1)
+    pub generator_histogram: Vec<usize>,
+    /// Estimated floating-point error in the hand's Burau matrix
+    pub numerical_drift: f64,
+}
+
+/// JSON response for GET /metrics: session bookkeeping for a HUD header,
+/// without the per-hand metrics of `/state`
+#[derive(serde::Serialize)]
+pub struct MetricsResponse {
+    /// Hands played this session (`explicit_resets + fold_resets`)
+    pub hand_count: usize,
+    /// Hands ended by a hand header or POST /reset
+    pub explicit_resets: usize,
+    /// Hands ended by a fold with `--reset-on-fold`
+    pub fold_resets: usize,
+    pub total_actions: usize,
+}
+
+/// JSON response for GET /jones
+#[derive(serde::Serialize)]
+pub struct JonesResponse {
+    /// Step of the hand the polynomial was computed at
+    pub step: usize,
+    /// Jones polynomial of the braid closure, e.g. `t + t^3 - t^4`
+    pub polynomial: String,
+    /// Strands in the braid (the table dimension)
+    pub strands: usize,
+    /// Crossings in the braid word the polynomial was computed from
+    pub crossings: usize,
+}
+
+/// Query string for GET /history
+#[derive(serde::Deserialize)]
+pub struct HistoryQuery {
+    /// Return at most this many of the most recent steps
+    pub limit: Option<usize>,
+}
+
 /// JSON request for POST /action
 #[derive(serde::Deserialize)]
 pub struct ActionRequest {
@@ -43,18 +975,88 @@ pub struct ActionRequest {
 #[derive(serde::Serialize, Clone)]
 pub struct FingerprintResponse {
     pub step: usize,
+    /// 1-based index of this action within the hand; 0 for the reset event itself
+    pub hand_action_index: usize,
+    /// Lifetime count of processed actions (resets excluded)
+    pub total_actions: usize,
+    /// Hands played this session
+    pub hand_count: usize,
     pub action: String,
+    /// Pot size after this action
+    pub pot: u64,
+    /// For calls: amount / (pot + amount), the price the caller is getting
+    pub pot_odds: Option<f64>,
     #[serde(rename = "global")]
     pub global_metrics: GlobalMetrics,
     #[serde(rename = "players")]
     pub player_metrics: std::collections::HashMap<String, PlayerMetrics>,
 }
 
+/// Message pushed to WebSocket clients.
+///
+/// Untagged, so fingerprint updates keep their original shape; other events
+/// carry an `"event"` field naming them.
+#[derive(serde::Serialize, Clone)]
+#[serde(untagged)]
+pub enum ServerEvent {
+    Fingerprint(FingerprintResponse),
+    Seating(SeatingEvent),
+    Table(TableEvent),
+}
+
+/// Table activity notifications: `{"event": "idle", "secs": N}` once the
+/// table has gone quiet for `--idle-after`, then `{"event": "active"}` with
+/// the next action. Purely informational, the braid is left alone.
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum TableEvent {
+    Idle { secs: u64 },
+    Active,
+}
+
+/// `{"event": "seating", "seats": [...]}`, sent whenever a seat is assigned
+/// or a player is renamed.
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "event", rename = "seating")]
+pub struct SeatingEvent {
+    pub seats: Vec<SeatName>,
+}
+
+/// One entry of the seat -> name mapping.
+#[derive(serde::Serialize, Clone, Debug, PartialEq)]
+pub struct SeatName {
+    pub seat: usize,
+    /// Display name, without the PokerNow ID
+    pub name: String,
+    /// Player ID the seat is keyed by ("Alice_p1")
+    pub id: String,
+}
+
+impl SeatingEvent {
+    /// Captures the resolver's current seating.
+    fn from_resolver(resolver: &SeatResolver) -> Self {
+        SeatingEvent {
+            seats: resolver
+                .seating()
+                .into_iter()
+                .map(|(seat, id)| SeatName {
+                    seat: seat.value(),
+                    name: pokernow::display_name(&id).to_string(),
+                    id,
+                })
+                .collect(),
+        }
+    }
+}
+
 /// Global topological metrics
 #[derive(serde::Serialize, Clone)]
 pub struct GlobalMetrics {
     pub writhe: i32,
     pub burau: f64,
+    /// Values of any custom invariants registered on the fingerprint
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub invariants: HashMap<String, f64>,
 }
 
 /// Player-specific metrics (simplified for JSON)
@@ -63,9 +1065,15 @@ pub struct PlayerMetrics {
     pub name: String,
     pub writhe: i32,
     pub complexity: f64,
+    pub activity: f64,
+    /// False while the player is standing up
+    pub active: bool,
+    /// 1 for the most recently active seated player (`null` while standing up)
+    pub activity_rank: Option<usize>,
 }
 
 /// Processes an action and updates the shared state
+#[tracing::instrument(skip_all)]
 pub fn process_action(
     action: Action,
     state: &mut ServerState,
@@ -74,43 +1082,161 @@ pub fn process_action(
     if action.action_type == ActionType::Reset {
         state.fingerprint.reset();
         state.current_seat = None;
+        state.aggressor = None;
         state.step = 0; // Reset step counter
+        state.hand_action_index = 0;
+        state.pot = 0;
+        state.street_contributions.clear();
+        state.peak = None;
+        state.explicit_resets += 1;
         
         println!("--- HAND RESET ---");
         
         return Ok(FingerprintResponse {
             step: 0,
+            hand_action_index: 0,
+            total_actions: state.total_actions,
+            hand_count: state.hand_count(),
             action: "--- HAND RESET ---".to_string(),
-            global_metrics: GlobalMetrics {
-                writhe: 0,
-                burau: state.fingerprint.burau_trace_magnitude(),
-            },
+            pot: 0,
+            pot_odds: None,
+            global_metrics: state.global_metrics(),
             player_metrics: HashMap::new(),
         });
     }
     
+    // Standing up / sitting back only toggles the seat
+    if matches!(
+        action.action_type,
+        ActionType::StandUp | ActionType::SitBack
+    ) {
+        let active = action.action_type == ActionType::SitBack;
+        state
+            .fingerprint
+            .set_seat_active(action.seat.value(), active);
+    }
+    if action.action_type == ActionType::StreetBoundary {
+        state.fingerprint.next_street();
+        state.street_contributions.clear();
+    }
+
+    // New street, seat changes and actions by seats that stood up: nothing to
+    // braid, report the state as it stands
+    let marker = match action.action_type {
+        ActionType::StreetBoundary => Some("--- NEW STREET ---".to_string()),
+        ActionType::StandUp | ActionType::SitBack => Some(format!(
+            "Seat {} {}",
+            action.seat.value(),
+            format_action_type(action.action_type)
+        )),
+        _ if !state.fingerprint.is_seat_active(action.seat.value()) => {
+            Some(format!("Seat {} is sitting out", action.seat.value()))
+        }
+        _ => None,
+    };
+    if let Some(action_desc) = marker {
+        return Ok(FingerprintResponse {
+            step: state.step,
+            hand_action_index: state.hand_action_index,
+            total_actions: state.total_actions,
+            hand_count: state.hand_count(),
+            action: action_desc,
+            pot: state.pot,
+            pot_odds: None,
+            global_metrics: state.global_metrics(),
+            player_metrics: HashMap::new(),
+        });
+    }
+
     // Reset on fold if flag is set
     if state.reset_on_fold && action.action_type == ActionType::Fold {
         state.fingerprint.reset();
         state.current_seat = None;
+        state.aggressor = None;
+        state.pot = 0;
+        state.street_contributions.clear();
+        state.peak = None;
+        state.fold_resets += 1;
     }
 
+    // Pot odds are measured against the pot before the call goes in
+    let pot_odds = pot_odds(action.action_type, action.amount, state.pot);
+    // Negative amounts (refunds, corrections) come back out of the pot
+    state.pot = state.pot.saturating_add_signed(action.amount);
+    *state
+        .street_contributions
+        .entry(action.seat.value())
+        .or_default() += action.amount;
+
     // Expand the action to generators
     let from_seat = state.current_seat.unwrap_or(action.seat);
-    let generators = expand_action(from_seat, action.seat, state.fingerprint.dimension());
+    // Bets under --min-amount are noise: they move the flow but braid nothing
+    let minor = state
+        .min_amount
+        .is_some_and(|min| action.is_below_min_amount(min));
+    let (origin, generators) = tracing::info_span!("expand_action").in_scope(|| {
+        match (state.flow_model, action.action_type, state.aggressor) {
+            _ if minor => (from_seat, Vec::new()),
+            // Calls wrap around the aggressor's strand instead of moving the flow
+            (FlowModel::WrapAggressor, ActionType::Call, Some(aggressor)) => (
+                aggressor,
+                expand_wrap_aggressor(aggressor, action.seat, state.fingerprint.dimension()),
+            ),
+            _ => (
+                from_seat,
+                expand_action(from_seat, action.seat, state.fingerprint.dimension()),
+            ),
+        }
+    });
+    if !minor
+        && state
+            .fingerprint
+            .check_collision(origin, action.seat, generators.len())
+    {
+        eprintln!(
+            "Warning: seats {} and {} share a strand at dimension {}; increase --dimension",
+            origin.value(),
+            action.seat.value(),
+            state.fingerprint.dimension()
+        );
+    }
 
-    // Get player name for this seat
-    let player_name = state.seat_resolver.get_player_name(action.seat);
+    if !minor
+        && matches!(
+            action.action_type,
+            ActionType::Bet | ActionType::Raise | ActionType::ReRaise | ActionType::AllIn
+        )
+    {
+        state.aggressor = Some(action.seat);
+    }
+
+    // Get player name for this seat (whoever holds the position right now)
+    let name_seat = if state.button_relative {
+        let dimension = state.fingerprint.dimension();
+        state
+            .seat_resolver
+            .from_button_relative(action.seat, dimension)
+    } else {
+        action.seat
+    };
+    let player_name = state.seat_resolver.get_player_name(name_seat);
 
     // Update current seat
     state.current_seat = Some(action.seat);
 
     // Process each generator with per-seat tracking
-    for gen in &generators {
-        state.fingerprint.update_for_seat(gen, action.seat.value(), player_name.clone());
-    }
+    tracing::info_span!("update_matrices", generators = generators.len()).in_scope(|| {
+        for gen in &generators {
+            state
+                .fingerprint
+                .update_for_seat(gen, action.seat.value(), player_name.clone());
+        }
+    });
+    state.fingerprint.tick_activity(action.seat.value());
 
     state.step += 1;
+    state.hand_action_index += 1;
+    state.total_actions += 1;
 
     // Format action description
     let action_desc = format!(
@@ -123,30 +1249,43 @@ pub fn process_action(
     // Calculate Burau trace magnitude
     let trace_magnitude = state.fingerprint.burau_trace_magnitude();
 
-    // Build player metrics map
-    let mut player_metrics_map = HashMap::new();
-    for (seat_num, metrics) in &state.fingerprint.player_stats {
-        player_metrics_map.insert(
-            seat_num.to_string(),
-            PlayerMetrics {
-                name: metrics.name.clone(),
-                writhe: metrics.writhe,
-                complexity: metrics.complexity,
-            },
-        );
+    // Track the hand's biggest moment (first step wins ties). Actions that
+    // don't braid anything leave the matrix untouched, so they can't peak.
+    if !generators.is_empty()
+        && state
+            .peak
+            .as_ref()
+            .is_none_or(|peak| trace_magnitude > peak.burau)
+    {
+        state.peak = Some(Peak {
+            step: state.step,
+            action: action_desc.clone(),
+            burau: trace_magnitude,
+        });
     }
 
     Ok(FingerprintResponse {
         step: state.step,
+        hand_action_index: state.hand_action_index,
+        total_actions: state.total_actions,
+        hand_count: state.hand_count(),
         action: action_desc,
-        global_metrics: GlobalMetrics {
-            writhe: state.fingerprint.writhe,
-            burau: trace_magnitude,
-        },
-        player_metrics: player_metrics_map,
+        pot: state.pot,
+        pot_odds,
+        global_metrics: state.global_metrics(),
+        player_metrics: state.player_metrics(),
     })
 }
 
+/// Implied pot odds of a call: the share of the final pot the caller puts in.
+/// Returns `None` for non-call actions or calls without an amount.
+fn pot_odds(action_type: ActionType, amount: i64, pot: u64) -> Option<f64> {
+    if action_type != ActionType::Call || amount <= 0 {
+        return None;
+    }
+    Some(amount as f64 / (pot as f64 + amount as f64))
+}
+
 /// Formats an ActionType as a string for display
 fn format_action_type(action_type: ActionType) -> &'static str {
     match action_type {
@@ -158,79 +1297,459 @@ fn format_action_type(action_type: ActionType) -> &'static str {
         ActionType::ReRaise => "reraise",
         ActionType::AllIn => "allin",
         ActionType::Reset => "reset",
+        ActionType::StreetBoundary => "street",
+        ActionType::StandUp => "stands up",
+        ActionType::SitBack => "sits back",
     }
 }
 
-/// Parses an action string into an Action
-pub fn parse_action_string(
-    action_string: &str,
-    state: &mut ServerState,
-) -> Result<Action, Box<dyn std::error::Error>> {
-    // Try to parse as PokerNow format first
-    // Create a dummy PokerNowRow for parsing
-    let row = pokernow::PokerNowRow {
-        entry: action_string.to_string(),
-        at: String::new(),
-        order: 0,
-    };
+/// An action string that could not be parsed (HTTP 400).
+#[derive(Debug)]
+pub struct ActionParseRejection(pub String);
 
-    if let Some((player_id, action_type, amount)) = pokernow::parse_row(&row) {
-        let seat = state.seat_resolver.get_or_assign_seat(&player_id);
-        Ok(Action::new(seat, action_type, amount))
-    } else {
-        Err("Failed to parse action string".into())
+/// An action that parsed but failed while updating the state (HTTP 500).
+#[derive(Debug)]
+pub struct ProcessingError(pub String);
+
+/// A `?table=` that no POST has created (HTTP 404).
+#[derive(Debug)]
+pub struct UnknownTable(pub String);
+
+/// A new table requested while `MAX_TABLES` are open (HTTP 429).
+#[derive(Debug)]
+pub struct TableLimit(pub usize);
+
+impl std::fmt::Display for UnknownTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown table '{}'", self.0)
     }
 }
 
-/// POST /action endpoint handler
-pub async fn handle_action(
-    req: ActionRequest,
-    state: SharedState,
-    tx: broadcast::Sender<FingerprintResponse>,
-) -> Result<impl warp::Reply, warp::Rejection> {
-    // Parse the action
-    let mut state_guard = state.write().await;
-    let action = match parse_action_string(&req.action_string, &mut *state_guard) {
-        Ok(a) => a,
-        Err(e) => {
-            return Ok(warp::reply::with_status(
-                warp::reply::json(&serde_json::json!({"error": e.to_string()})),
-                warp::http::StatusCode::BAD_REQUEST,
-            ));
-        }
-    };
+impl std::fmt::Display for TableLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Too many tables (at most {})", self.0)
+    }
+}
 
-    // Process the action
-    let response = match process_action(action, &mut *state_guard) {
-        Ok(r) => r,
-        Err(e) => {
-            return Ok(warp::reply::with_status(
-                warp::reply::json(&serde_json::json!({"error": e.to_string()})),
-                warp::http::StatusCode::INTERNAL_SERVER_ERROR,
-            ));
-        }
-    };
+impl std::fmt::Display for ActionParseRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
-    // Broadcast to WebSocket clients
-    let _ = tx.send(response.clone());
+impl std::fmt::Display for ProcessingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ActionParseRejection {}
+impl std::error::Error for ProcessingError {}
+// Implementing `Reject` gives `From<_> for warp::Rejection`, so handlers can use `?`
+impl warp::reject::Reject for ActionParseRejection {}
+impl warp::reject::Reject for ProcessingError {}
+impl warp::reject::Reject for UnknownTable {}
+impl warp::reject::Reject for TableLimit {}
+
+/// Runs one processing step on `state`, turning both errors and panics into
+/// a `ProcessingError` (HTTP 500) so a single bad action can't take the
+/// connection down with it.
+///
+/// A panic can leave the state half-updated, so it is then rebuilt from the
+/// action log (see `ServerState::rebuild_from_log`).
+fn run_guarded<T>(
+    state: &mut ServerState,
+    step: impl FnOnce(&mut ServerState) -> Result<T, Box<dyn std::error::Error>>,
+) -> Result<T, ProcessingError> {
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| step(state))) {
+        Ok(result) => result.map_err(|e| ProcessingError(e.to_string())),
+        Err(payload) => {
+            let reason = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            if let Err(e) = state.rebuild_from_log() {
+                eprintln!("Failed to rebuild the state after a panic: {}", e);
+            }
+            Err(ProcessingError(format!(
+                "Internal error while processing action: {}",
+                reason
+            )))
+        }
+    }
+}
+
+/// Returns the message of one of our custom rejections, if it is one.
+fn rejection_message(err: &warp::Rejection) -> Option<String> {
+    err.find::<ActionParseRejection>()
+        .map(|e| e.to_string())
+        .or_else(|| err.find::<ProcessingError>().map(|e| e.to_string()))
+        .or_else(|| err.find::<UnknownTable>().map(|e| e.to_string()))
+        .or_else(|| err.find::<TableLimit>().map(|e| e.to_string()))
+}
+
+/// Maps our custom rejections to JSON error replies.
+///
+/// Any other rejection (unknown path, bad method, ...) is passed through so
+/// warp's default handling still applies.
+pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply, warp::Rejection> {
+    let Some(message) = rejection_message(&err) else {
+        return Err(err);
+    };
+    let status = if err.find::<ActionParseRejection>().is_some() {
+        warp::http::StatusCode::BAD_REQUEST
+    } else if err.find::<UnknownTable>().is_some() {
+        warp::http::StatusCode::NOT_FOUND
+    } else if err.find::<TableLimit>().is_some() {
+        warp::http::StatusCode::TOO_MANY_REQUESTS
+    } else {
+        warp::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&serde_json::json!({"error": message})),
+        status,
+    ))
+}
+
+/// Parses an action string into an Action
+#[tracing::instrument(skip_all)]
+pub fn parse_action_string(
+    action_string: &str,
+    state: &mut ServerState,
+) -> Result<Action, ActionParseRejection> {
+    // Try to parse as PokerNow format first
+    // Create a dummy PokerNowRow for parsing
+    let row = pokernow::PokerNowRow {
+        entry: action_string.to_string(),
+        at: String::new(),
+        order: 0,
+    };
+
+    if state.button_relative {
+        if let Some(dealer) = pokernow::parse_dealer(&row) {
+            let button = state.seat_resolver.get_or_assign_seat(&dealer);
+            state.seat_resolver.set_button(button);
+        }
+    }
+
+    let parsed = pokernow::parse_row(&row).map_err(|e| ActionParseRejection(e.to_string()))?;
+    if let Some(mut parsed) = parsed {
+        if let Some(names) = &mut state.name_disambiguator {
+            names.disambiguate(&mut parsed);
+        }
+        // Street markers aren't players, don't give them a seat
+        let mut seat = if parsed.action_type == ActionType::StreetBoundary {
+            braid_engine::Seat::new(1)
+        } else {
+            state.seat_resolver.get_or_assign_seat(&parsed.player_id)
+        };
+        if state.button_relative {
+            let dimension = state.fingerprint.dimension();
+            seat = state.seat_resolver.to_button_relative(seat, dimension);
+        }
+        let mut amount = parsed.amount as i64;
+        if parsed.amount_is_total {
+            // "raises to" names the seat's total for the street: only the
+            // chips on top of what it already put in are new
+            let contributed = state
+                .street_contributions
+                .get(&seat.value())
+                .copied()
+                .unwrap_or(0);
+            amount = (amount - contributed).max(0);
+        }
+        Ok(Action::new(seat, parsed.action_type, amount))
+    } else {
+        Err(ActionParseRejection(
+            "Failed to parse action string".to_string(),
+        ))
+    }
+}
+
+/// Parses, processes and records one raw action string, then broadcasts the
+/// result. Shared by every ingestion channel (HTTP, Unix socket).
+///
+/// Returns `Ok(None)` when the action was dropped as a consecutive duplicate.
+pub async fn ingest_action(
+    action_string: String,
+    state: &SharedState,
+    tx: &broadcast::Sender<ServerEvent>,
+) -> Result<Option<FingerprintResponse>, warp::Rejection> {
+    let now = Instant::now();
+    let mut state_guard = state.write().await;
+    if state_guard.is_duplicate(action_string.trim(), now) {
+        return Ok(None);
+    }
+    let seating_before = SeatingEvent::from_resolver(&state_guard.seat_resolver);
+    let action = parse_action_string(&action_string, &mut state_guard)?;
+
+    // Let HUDs lay out the table as soon as a seat is assigned or renamed
+    let seating = SeatingEvent::from_resolver(&state_guard.seat_resolver);
+    if seating != seating_before {
+        let _ = tx.send(ServerEvent::Seating(seating));
+    }
+
+    let hand_boundary = action.action_type == ActionType::Reset;
+    let response = run_guarded(&mut state_guard, |state| process_action(action, state))?;
+
+    if let Some(event) = state_guard.mark_active(now) {
+        let _ = tx.send(ServerEvent::Table(event));
+    }
+
+    // Record the action for snapshots and autosave when due
+    state_guard.log_action(action_string, hand_boundary);
+    state_guard.record_tempo(now);
+    state_guard.record_history(&response);
+    if let Some(autosave) = state_guard.autosave_if_due() {
+        autosave.spawn();
+    }
+    state_guard.trace_debug_word();
+
+    // Broadcast to WebSocket clients
+    let _ = tx.send(ServerEvent::Fingerprint(response.clone()));
+
+    Ok(Some(response))
+}
+
+/// Reply body for an action dropped by `--dedup-consecutive`.
+fn duplicate_reply() -> serde_json::Value {
+    serde_json::json!({"duplicate": true})
+}
+
+/// POST /action endpoint handler
+pub async fn handle_action(
+    req: ActionRequest,
+    state: SharedState,
+    tx: broadcast::Sender<ServerEvent>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    // Failures are turned into JSON by `handle_rejection`
+    let body = match ingest_action(req.action_string, &state, &tx).await? {
+        Some(response) => serde_json::to_value(&response).unwrap_or_default(),
+        None => duplicate_reply(),
+    };
 
     // Return the response
     Ok(warp::reply::with_status(
-        warp::reply::json(&response),
+        warp::reply::json(&body),
         warp::http::StatusCode::OK,
     ))
 }
 
+/// POST /reset endpoint handler
+///
+/// Ends the current hand on request (e.g. a "new hand" button in the HUD)
+/// and broadcasts the reset exactly like a parsed hand header would.
+pub async fn handle_reset(
+    state: SharedState,
+    tx: broadcast::Sender<ServerEvent>,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let now = Instant::now();
+    let mut state_guard = state.write().await;
+    let reset = Action::new(Seat::new(1), ActionType::Reset, 0);
+    let response = run_guarded(&mut state_guard, |state| process_action(reset, state))?;
+
+    if let Some(event) = state_guard.mark_active(now) {
+        let _ = tx.send(ServerEvent::Table(event));
+    }
+
+    state_guard.log_action(FORCED_RESET_ENTRY.to_string(), true);
+    state_guard.record_tempo(now);
+    state_guard.record_history(&response);
+    if let Some(autosave) = state_guard.autosave_if_due() {
+        autosave.spawn();
+    }
+    state_guard.trace_debug_word();
+
+    let _ = tx.send(ServerEvent::Fingerprint(response.clone()));
+
+    Ok(warp::reply::json(&response))
+}
+
+/// DELETE /table?table=ID endpoint handler
+///
+/// Closes a table created by `?table=`, freeing its slot under `MAX_TABLES`.
+/// The default table can't be closed.
+pub async fn handle_close_table(
+    query: TableQuery,
+    tables: Tables,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let Some(id) = query.table else {
+        return Err(ActionParseRejection("Closing a table needs ?table=ID".to_string()).into());
+    };
+    if !tables.remove(&id).await {
+        return Err(UnknownTable(id).into());
+    }
+    Ok(warp::reply::json(&serde_json::json!({"closed": id})))
+}
+
+/// GET /history endpoint handler: the recent timeline, oldest first
+pub async fn handle_history(
+    query: HistoryQuery,
+    state: SharedState,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let state = state.read().await;
+    Ok(warp::reply::json(&state.recent_history(query.limit)))
+}
+
+/// GET /jones endpoint handler
+///
+/// Tier 3 is exponential, so it is only ever computed here, on request, on a
+/// blocking thread with no lock held (actions keep flowing meanwhile). The
+/// result is cached on the fingerprint until the next generator arrives.
+pub async fn handle_jones(state: SharedState) -> Result<impl warp::Reply, warp::Rejection> {
+    let (step, word, strands, cached) = {
+        let state = state.read().await;
+        let fingerprint = &state.fingerprint;
+        (
+            state.step,
+            fingerprint.word.clone(),
+            fingerprint.dimension(),
+            fingerprint.jones_poly_cache.clone(),
+        )
+    };
+    let crossings = word.len();
+
+    let polynomial = match cached {
+        Some(polynomial) => polynomial,
+        None => {
+            let computed = word.clone();
+            let polynomial = tokio::task::spawn_blocking(move || {
+                FingerprintState::jones_for(&computed, strands)
+            })
+            .await
+            .map_err(|e| ProcessingError(format!("Jones computation failed: {}", e)))?;
+            // Only cache it if no generator arrived while computing
+            let mut state = state.write().await;
+            if state.fingerprint.word == word {
+                state.fingerprint.jones_poly_cache = Some(polynomial.clone());
+            }
+            polynomial
+        }
+    };
+
+    Ok(warp::reply::json(&JonesResponse {
+        step,
+        polynomial,
+        strands,
+        crossings,
+    }))
+}
+
+/// GET /state endpoint handler
+pub async fn handle_state(state: SharedState) -> Result<impl warp::Reply, warp::Rejection> {
+    let state = state.read().await;
+    Ok(warp::reply::json(&StateResponse {
+        step: state.step,
+        total_actions: state.total_actions,
+        hand_count: state.hand_count(),
+        explicit_resets: state.explicit_resets,
+        fold_resets: state.fold_resets,
+        collisions: state.fingerprint.collisions,
+        pot: state.pot,
+        global_metrics: state.global_metrics(),
+        player_metrics: state.player_metrics(),
+        peak: state.peak.clone().map(|peak| Peak {
+            burau: round_metric(peak.burau, state.round_digits),
+            ..peak
+        }),
+        tempo: state
+            .tempo(Instant::now())
+            .map(|tempo| round_metric(tempo, state.round_digits)),
+        generator_histogram: state.fingerprint.generator_histogram.clone(),
+        numerical_drift: state.fingerprint.numerical_drift(),
+    }))
+}
+
+/// GET /metrics endpoint handler
+pub async fn handle_metrics(state: SharedState) -> Result<impl warp::Reply, warp::Rejection> {
+    let state = state.read().await;
+    Ok(warp::reply::json(&MetricsResponse {
+        hand_count: state.hand_count(),
+        explicit_resets: state.explicit_resets,
+        fold_resets: state.fold_resets,
+        total_actions: state.total_actions,
+    }))
+}
+
+/// WebSocket subprotocol spoken on `/ws`. Bump the version whenever the
+/// shape of the pushed JSON changes incompatibly.
+pub const WS_PROTOCOL: &str = "pokerbraids.v1";
+
+/// Close code sent to clients that only request unsupported subprotocols
+/// (1002, protocol error)
+const WS_UNSUPPORTED_PROTOCOL: u16 = 1002;
+
+/// Picks the subprotocol for a `/ws` upgrade from the client's
+/// `Sec-WebSocket-Protocol` header.
+///
+/// Returns `Ok(None)` when the client asked for none (older clients), and
+/// `Err` when it asked only for versions this server doesn't speak.
+fn negotiate_ws_protocol(requested: Option<&str>) -> Result<Option<&'static str>, ()> {
+    match requested {
+        None => Ok(None),
+        Some(header) if header.split(',').any(|p| p.trim() == WS_PROTOCOL) => Ok(Some(WS_PROTOCOL)),
+        Some(_) => Err(()),
+    }
+}
+
+/// Upgrades a `/ws` request, rejecting unsupported subprotocols with a close
+/// frame that names the version the server does support.
+fn upgrade_ws(
+    ws: warp::ws::Ws,
+    requested: Option<String>,
+    state: SharedState,
+    tx: broadcast::Sender<ServerEvent>,
+) -> warp::reply::Response {
+    match negotiate_ws_protocol(requested.as_deref()) {
+        Ok(protocol) => {
+            let reply = ws.on_upgrade(move |socket| handle_ws(socket, state, tx));
+            match protocol {
+                Some(protocol) => {
+                    warp::reply::with_header(reply, "sec-websocket-protocol", protocol)
+                        .into_response()
+                }
+                None => reply.into_response(),
+            }
+        }
+        Err(()) => ws
+            .on_upgrade(move |mut socket| async move {
+                let reason = format!("unsupported subprotocol; server speaks {}", WS_PROTOCOL);
+                let _ = socket
+                    .send(warp::ws::Message::close_with(
+                        WS_UNSUPPORTED_PROTOCOL,
+                        reason,
+                    ))
+                    .await;
+            })
+            .into_response(),
+    }
+}
+
 /// WebSocket connection handler
 pub async fn handle_ws(
     ws: warp::ws::WebSocket,
-    tx: broadcast::Sender<FingerprintResponse>,
+    state: SharedState,
+    tx: broadcast::Sender<ServerEvent>,
 ) {
     let (mut ws_tx, _ws_rx) = ws.split();
-    let mut rx = tx.subscribe();
 
-    // Send initial state
+    // Snapshot and subscribe under one read lock. Updates are broadcast while
+    // the write lock is held, so none can fall between the two
+    let (initial, mut rx) = {
+        let state = state.read().await;
+        let initial = serde_json::to_string(&ServerEvent::Fingerprint(state.current_response()));
+        (initial, tx.subscribe())
+    };
+
+    // Send initial state, then every update
     tokio::spawn(async move {
+        if let Ok(json) = initial {
+            if ws_tx.send(warp::ws::Message::text(json)).await.is_err() {
+                return;
+            }
+        }
         while let Ok(msg) = rx.recv().await {
             let json = match serde_json::to_string(&msg) {
                 Ok(j) => j,
@@ -244,61 +1763,1706 @@ pub async fn handle_ws(
 }
 
 /// Creates the server routes
+///
+/// Cross-origin requests are only accepted from `allow_origins`
+/// (e.g. `https://www.pokernow.club`); an empty list allows any origin.
 pub fn create_routes(
     state: SharedState,
-    tx: broadcast::Sender<FingerprintResponse>,
+    tx: broadcast::Sender<ServerEvent>,
+    allow_origins: &[String],
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    let state_filter = warp::any().map(move || state.clone());
-    let tx_filter = warp::any().map(move || tx.clone());
+    // Every route takes an optional `?table=ID` and works on that table.
+    // Only writes create tables; reads of an unknown table are a 404
+    let tables = Tables::new(state, tx);
+    let create_tables = tables.clone();
+    let close_tables = tables.clone();
+    let create_filter = warp::query::<TableQuery>()
+        .and(warp::any().map(move || create_tables.clone()))
+        .and_then(|query: TableQuery, tables: Tables| async move {
+            tables
+                .get_or_create(query.table.as_deref())
+                .await
+                .map_err(warp::reject::custom)
+        })
+        .untuple_one();
+    let table_filter = warp::query::<TableQuery>()
+        .and(warp::any().map(move || tables.clone()))
+        .and_then(|query: TableQuery, tables: Tables| async move {
+            tables
+                .get(query.table.as_deref())
+                .await
+                .map_err(warp::reject::custom)
+        })
+        .untuple_one();
+    let state_filter = table_filter.clone().map(|state: SharedState, _tx| state);
 
     // POST /action
     let action_route = warp::path("action")
         .and(warp::post())
         .and(warp::body::json())
-        .and(state_filter.clone())
-        .and(tx_filter.clone())
+        .and(create_filter.clone())
         .and_then(handle_action);
 
-    // GET /ws
+    // POST /reset
+    let reset_route = warp::path("reset")
+        .and(warp::post())
+        .and(create_filter)
+        .and_then(handle_reset);
+
+    // DELETE /table?table=ID
+    let close_route = warp::path("table")
+        .and(warp::delete())
+        .and(warp::query::<TableQuery>())
+        .and(warp::any().map(move || close_tables.clone()))
+        .and_then(handle_close_table);
+
+    // GET /state
+    let state_route = warp::path("state")
+        .and(warp::get())
+        .and(state_filter.clone())
+        .and_then(handle_state);
+
+    // GET /metrics
+    let metrics_route = warp::path("metrics")
+        .and(warp::get())
+        .and(state_filter.clone())
+        .and_then(handle_metrics);
+
+    // GET /jones
+    let jones_route = warp::path("jones")
+        .and(warp::get())
+        .and(state_filter.clone())
+        .and_then(handle_jones);
+
+    // GET /history?limit=N
+    let history_route = warp::path("history")
+        .and(warp::get())
+        .and(warp::query::<HistoryQuery>())
+        .and(state_filter)
+        .and_then(handle_history);
+
+    // GET /ws (subprotocol pokerbraids.v1)
     let ws_route = warp::path("ws")
         .and(warp::ws())
-        .and(tx_filter)
-        .map(|ws: warp::ws::Ws, tx| {
-            ws.on_upgrade(move |socket| handle_ws(socket, tx))
-        });
+        .and(warp::header::optional::<String>("sec-websocket-protocol"))
+        .and(table_filter)
+        .map(upgrade_ws);
 
     // CORS headers
-    // Note: allow_any_origin() is used for development
-    // In production, restrict to: .allow_origin("https://www.pokernow.club")
     let cors = warp::cors()
-        .allow_any_origin()  // Allows requests from pokernow.club and other origins
         .allow_headers(vec!["content-type"])
-        .allow_methods(vec!["GET", "POST", "OPTIONS"])
+        .allow_methods(vec!["GET", "POST", "DELETE", "OPTIONS"])
         .allow_credentials(false);  // Set to true if cookies/auth needed
+    // Any origin is fine for development; --allow-origin locks it down
+    let cors = if allow_origins.is_empty() {
+        cors.allow_any_origin()
+    } else {
+        cors.allow_origins(allow_origins.iter().map(String::as_str))
+    };
 
-    action_route.or(ws_route).with(cors)
+    action_route
+        .or(reset_route)
+        .or(close_route)
+        .or(state_route)
+        .or(metrics_route)
+        .or(history_route)
+        .or(jones_route)
+        .or(ws_route)
+        .recover(handle_rejection)
+        .with(cors)
+}
+
+/// Removes the socket file when the listener task holding it ends.
+#[cfg(unix)]
+struct SocketFile(String);
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        fs::remove_file(&self.0).ok();
+    }
+}
+
+/// Listens on a Unix domain socket for newline-delimited action strings.
+///
+/// Each line is ingested exactly like a POST /action body and the JSON result
+/// (or `{"error": ...}`) is written back as one line. A stale socket file at
+/// `path` is removed first, and the socket file is unlinked once the task
+/// ends (including when it is aborted or the runtime shuts down).
+#[cfg(unix)]
+pub fn spawn_socket_listener(
+    path: &str,
+    state: SharedState,
+    tx: broadcast::Sender<ServerEvent>,
+) -> Result<tokio::task::JoinHandle<()>, Box<dyn std::error::Error>> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    if std::path::Path::new(path).exists() {
+        fs::remove_file(path)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)
+        .map_err(|e| format!("Failed to bind socket '{}': {}", path, e))?;
+    let socket_file = SocketFile(path.to_string());
+
+    Ok(tokio::spawn(async move {
+        let _socket_file = socket_file;
+        while let Ok((stream, _)) = listener.accept().await {
+            let state = state.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut lines = BufReader::new(reader).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    let line = line.trim().to_string();
+                    if line.is_empty() {
+                        continue;
+                    }
+                    let reply = match ingest_action(line, &state, &tx).await {
+                        Ok(Some(response)) => serde_json::to_value(&response).unwrap_or_default(),
+                        Ok(None) => duplicate_reply(),
+                        Err(err) => serde_json::json!({
+                            "error": rejection_message(&err).unwrap_or_else(|| format!("{:?}", err))
+                        }),
+                    };
+                    if writer
+                        .write_all(format!("{}\n", reply).as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            });
+        }
+    }))
+}
+
+/// Unix domain sockets are not available on this platform.
+#[cfg(not(unix))]
+pub fn spawn_socket_listener(
+    _path: &str,
+    _state: SharedState,
+    _tx: broadcast::Sender<ServerEvent>,
+) -> Result<tokio::task::JoinHandle<()>, Box<dyn std::error::Error>> {
+    Err("--socket is only supported on Unix platforms".into())
+}
+
+/// Checks once a second whether the table has gone quiet and broadcasts the
+/// idle event when it has. Stops once every other handle to `state` is gone.
+pub fn spawn_idle_watch(
+    state: &SharedState,
+    tx: broadcast::Sender<ServerEvent>,
+) -> tokio::task::JoinHandle<()> {
+    // Weak, so the watcher stops once the table is dropped
+    let state = Arc::downgrade(state);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            ticker.tick().await;
+            let Some(state) = state.upgrade() else {
+                break;
+            };
+            let event = state.write().await.check_idle(Instant::now());
+            if let Some(event) = event {
+                let _ = tx.send(ServerEvent::Table(event));
+            }
+        }
+    })
 }
 
 /// Starts the web server
-pub async fn start_server(reset_on_fold: bool) -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize shared state
-    let state: SharedState = Arc::new(RwLock::new(ServerState::new(reset_on_fold)));
-    
-    // Create broadcast channel for WebSocket clients
-    let (tx, _rx) = broadcast::channel::<FingerprintResponse>(100);
+///
+/// Runs until Ctrl-C or SIGTERM, then stops accepting connections and
+/// returns (see `start_server_until`).
+pub async fn start_server(config: &Config) -> Result<(), Box<dyn std::error::Error>> {
+    start_server_until(config, shutdown_signal()).await
+}
+
+/// Resolves on Ctrl-C, or SIGTERM on Unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Starts the web server and shuts it down gracefully once `shutdown`
+/// resolves: in-flight requests finish, the socket listener (if any) stops
+/// and removes its socket file, then the final state is written to the
+/// autosave path (if autosave is on) and the debug word file is flushed.
+pub async fn start_server_until(
+    config: &Config,
+    shutdown: impl std::future::Future<Output = ()> + Send + 'static,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Initialize shared state and the broadcast channel for WebSocket clients
+    let mut server_state = if config.load {
+        // Rebuild from the last autosave, keeping the current serving options
+        let restored = ServerState::load_snapshot(&config.autosave_path, config)?;
+        println!(
+            "Restored {} actions from {}",
+            restored.logged_entries(),
+            config.autosave_path
+        );
+        restored
+    } else {
+        ServerState::from_config(config)
+    };
+    if let Some(path) = &config.debug_word {
+        server_state.enable_debug_word(path)?;
+    }
+    let (state, tx) = shared_state(server_state);
+
+    // Optional low-overhead ingestion channel for local integrations
+    let socket_listener = match &config.socket {
+        Some(path) => {
+            let listener = spawn_socket_listener(path, state.clone(), tx.clone())?;
+            println!("Listening for actions on unix socket {}", path);
+            Some(listener)
+        }
+        None => None,
+    };
+
+    if config.idle_after.is_some() {
+        spawn_idle_watch(&state, tx.clone());
+    }
     
     // Create routes
-    let routes = create_routes(state, tx);
+    let routes = create_routes(state.clone(), tx, &config.allow_origins);
     
-    // Start server
-    let addr = ([127, 0, 0, 1], 3030);
-    println!("Server starting on http://127.0.0.1:3030/");
+    // Start server (127.0.0.1:3030 unless --host/--port say otherwise)
+    let bind_addr = config.bind_addr()?;
+    let (addr, server) = warp::serve(routes)
+        .try_bind_with_graceful_shutdown(bind_addr, shutdown)
+        .map_err(|e| format!("Failed to bind {}: {}", bind_addr, e))?;
+    println!("Server starting on http://{}/", addr);
     println!("Endpoints:");
-    println!("  POST http://127.0.0.1:3030/action");
-    println!("  GET  ws://127.0.0.1:3030/ws");
-    
-    warp::serve(routes).run(addr).await;
+    println!("  POST http://{}/action", addr);
+    println!("  POST http://{}/reset", addr);
+    println!("  DELETE http://{}/table?table=ID", addr);
+    println!("  GET  http://{}/state", addr);
+    println!("  GET  http://{}/metrics", addr);
+    println!("  GET  http://{}/history?limit=N", addr);
+    println!("  GET  http://{}/jones", addr);
+    println!("  GET  ws://{}/ws ({})", addr, WS_PROTOCOL);
     
+    server.await;
+
+    // Stop taking socket actions; the task removes the socket file as it ends
+    if let Some(listener) = socket_listener {
+        listener.abort();
+        let _ = listener.await;
+    }
+
+    // Flush the final state
+    println!("Shutting down");
+    let state = state.read().await;
+    if state.autosave_every.is_some() {
+        state.autosave()?.write()?;
+    }
+    if let Some(log) = &state.debug_word {
+        if let Ok(mut log) = log.lock() {
+            log.flush()?;
+        }
+    }
+
     Ok(())
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use warp::Reply;
+
+    #[tokio::test]
+    async fn test_server_stops_on_shutdown_signal() {
+        let config = Config {
+            server: true,
+            port: 0,
+            ..Config::default()
+        };
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            start_server_until(&config, async {
+                stopped.await.ok();
+            })
+            .await
+            .map_err(|e| e.to_string())
+        });
+
+        stop.send(()).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .expect("start_server_until should resolve after the signal");
+        assert!(result.unwrap().is_ok());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_shutdown_removes_socket_file() {
+        let path =
+            std::env::temp_dir().join(format!("poker-braids-shutdown-{}.sock", std::process::id()));
+        let config = Config {
+            server: true,
+            port: 0,
+            socket: Some(path.to_str().unwrap().to_string()),
+            ..Config::default()
+        };
+        let (stop, stopped) = tokio::sync::oneshot::channel::<()>();
+        let server = tokio::spawn(async move {
+            start_server_until(&config, async {
+                stopped.await.ok();
+            })
+            .await
+            .map_err(|e| e.to_string())
+        });
+
+        // Wait for the listener to bind before stopping
+        for _ in 0..100 {
+            if path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(path.exists());
+        stop.send(()).unwrap();
+        let result = tokio::time::timeout(Duration::from_secs(5), server)
+            .await
+            .unwrap();
+        assert!(result.unwrap().is_ok());
+        assert!(!path.exists());
+    }
+
+    /// Calls `handle_action` directly and returns the status and JSON body.
+    async fn post_action(
+        state: &SharedState,
+        tx: &broadcast::Sender<ServerEvent>,
+        action_string: &str,
+    ) -> (warp::http::StatusCode, serde_json::Value) {
+        let req = ActionRequest {
+            action_string: action_string.to_string(),
+        };
+        let reply = handle_action(req, state.clone(), tx.clone()).await.unwrap();
+        let response = reply.into_response();
+        let status = response.status();
+        let body = warp::hyper::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn test_builder_applies_config() {
+        let state = ServerState::builder()
+            .reset_on_fold(true)
+            .dimension(6)
+            .activity_decay(0.5)
+            .build();
+        assert!(state.reset_on_fold);
+        assert_eq!(state.fingerprint.dimension(), 6);
+        assert_eq!(state.fingerprint.activity_decay, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_post_reset_broadcasts_hand_reset() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        let (_, body) = post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        assert_eq!(body["global"]["writhe"], 1);
+
+        let mut rx = tx.subscribe();
+        let response = warp::test::request()
+            .method("POST")
+            .path("/reset")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), warp::http::StatusCode::OK);
+
+        let broadcast = next_fingerprint(&mut rx).await;
+        assert_eq!(broadcast.action, "--- HAND RESET ---");
+        assert_eq!(broadcast.step, 0);
+        assert_eq!(broadcast.global_metrics.writhe, 0);
+
+        let guard = state.read().await;
+        assert_eq!(guard.step, 0);
+        assert!(guard.current_seat.is_none());
+
+        // The forced reset survives a snapshot round trip
+        let restored = ServerState::restore(&guard.snapshot(), &Config::default()).unwrap();
+        assert_eq!(restored.explicit_resets, 1);
+        assert_eq!(restored.fingerprint.writhe, 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_action_returns_fingerprint() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let mut rx = tx.subscribe();
+
+        let (status, body) = post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        assert_eq!(status, warp::http::StatusCode::OK);
+        assert_eq!(body["step"], 1);
+        assert_eq!(body["action"], "Seat 1 raise ($200)");
+        assert_eq!(body["global"]["writhe"], 0);
+
+        let (status, body) = post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        assert_eq!(status, warp::http::StatusCode::OK);
+        assert_eq!(body["step"], 2);
+        assert_eq!(body["global"]["writhe"], 1);
+        assert_eq!(body["players"]["2"]["name"], "Bob_p2");
+
+        // Both responses were broadcast to subscribers
+        assert_eq!(next_fingerprint(&mut rx).await.step, 1);
+        assert_eq!(next_fingerprint(&mut rx).await.step, 2);
+    }
+
+    #[tokio::test]
+    async fn test_wrap_aggressor_calls() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(6)
+                .flow_model(FlowModel::WrapAggressor)
+                .build(),
+        );
+
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        for caller in [
+            "Bob @ p2 calls 200",
+            "Carol @ p3 calls 200",
+            "Dave @ p4 calls 200",
+        ] {
+            let before = state.read().await.fingerprint.writhe;
+            post_action(&state, &tx, caller).await;
+            // Each wrap adds exactly one net full twist with the raiser
+            assert_eq!(state.read().await.fingerprint.writhe, before + 2);
+        }
+        assert_eq!(state.read().await.aggressor, Some(Seat::new(1)));
+    }
+
+    #[tokio::test]
+    async fn test_debug_word_trace() {
+        let path = std::env::temp_dir().join(format!(
+            "poker-braids-debug-word-{}.txt",
+            std::process::id()
+        ));
+        fs::remove_file(&path).ok();
+
+        let mut server_state = ServerState::builder().dimension(4).build();
+        server_state
+            .enable_debug_word(path.to_str().unwrap())
+            .unwrap();
+        let (state, tx) = shared_state(server_state);
+
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        post_action(&state, &tx, "Carol @ p3 raises to 600").await;
+        post_action(&state, &tx, "-- starting hand #2 --").await;
+        post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        if let Some(log) = &state.read().await.debug_word {
+            log.lock().unwrap().flush().unwrap();
+        }
+
+        let contents = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).ok();
+        let lines: Vec<&str> = contents.lines().collect();
+        // Only the new generators, with a restart line when the word is cut
+        assert_eq!(lines, vec!["1\t=", "2\tσ1", "3\tσ2", "0\t=", "1\t"]);
+    }
+
+    #[tokio::test]
+    async fn test_hand_index_restarts_but_total_keeps_climbing() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        let (_, body) = post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        assert_eq!(body["hand_action_index"], 2);
+        assert_eq!(body["total_actions"], 2);
+
+        let (_, body) = post_action(&state, &tx, "-- starting hand #2 --").await;
+        assert_eq!(body["hand_action_index"], 0);
+        assert_eq!(body["total_actions"], 2);
+
+        let (_, body) = post_action(&state, &tx, "Bob @ p2 bets 40").await;
+        assert_eq!(body["hand_action_index"], 1);
+        assert_eq!(body["total_actions"], 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_socket_lines_are_broadcast() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+        let path = std::env::temp_dir().join(format!("poker-braids-{}.sock", std::process::id()));
+        let path_str = path.to_str().unwrap();
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let mut rx = tx.subscribe();
+        let listener = spawn_socket_listener(path_str, state.clone(), tx.clone()).unwrap();
+
+        let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        writer
+            .write_all(b"Alice @ p1 raises to 200\nBob @ p2 calls 200\n")
+            .await
+            .unwrap();
+
+        assert_eq!(next_fingerprint(&mut rx).await.step, 1);
+        assert_eq!(next_fingerprint(&mut rx).await.step, 2);
+
+        // Each line also gets its JSON result written back
+        let mut lines = BufReader::new(reader).lines();
+        let first: serde_json::Value =
+            serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+        assert_eq!(first["action"], "Seat 1 raise ($200)");
+
+        listener.abort();
+        let _ = listener.await;
+        assert!(
+            !path.exists(),
+            "The socket file goes away with the listener"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_button_relative_braid_ignores_button_position() {
+        // Same hand (button opens, then the next three seats act) with the
+        // button on Alice (seat 1) and on Carol (seat 3)
+        let hand_word = |button_relative: bool, dealer: &'static str, actors: [&'static str; 4]| async move {
+            let (state, tx) = shared_state(
+                ServerState::builder()
+                    .dimension(5)
+                    .button_relative(button_relative)
+                    .build(),
+            );
+            {
+                let mut guard = state.write().await;
+                for player in ["Alice_a", "Bob_b", "Carol_c", "Dave_d", "Erin_e"] {
+                    guard.seat_resolver.get_or_assign_seat(player);
+                }
+            }
+            let header = format!(r#"-- starting hand #1 (id: x) (dealer: "{}") --"#, dealer);
+            post_action(&state, &tx, &header).await;
+            for actor in actors {
+                post_action(&state, &tx, &format!("{} calls 10", actor)).await;
+            }
+            let word = state.read().await.fingerprint.word.clone();
+            word
+        };
+
+        let alice_deals = ["Alice @ a", "Bob @ b", "Carol @ c", "Dave @ d"];
+        let carol_deals = ["Carol @ c", "Dave @ d", "Erin @ e", "Alice @ a"];
+        let first = hand_word(true, "Alice @ a", alice_deals).await;
+        let second = hand_word(true, "Carol @ c", carol_deals).await;
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+
+        // With absolute seats the wrap past seat 5 makes the braids differ
+        let first = hand_word(false, "Alice @ a", alice_deals).await;
+        let second = hand_word(false, "Carol @ c", carol_deals).await;
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_round_digits_only_affects_output() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(4)
+                .round_digits(Some(3))
+                .build(),
+        );
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        post_action(&state, &tx, "Carol @ p3 calls 200").await;
+        let (_, body) = post_action(&state, &tx, "Bob @ p2 calls 200").await;
+
+        let decimals = |value: &serde_json::Value| {
+            let text = value.to_string();
+            text.split_once('.')
+                .map_or(0, |(_, fraction)| fraction.len())
+        };
+        assert!(
+            decimals(&body["global"]["burau"]) <= 3,
+            "{}",
+            body["global"]["burau"]
+        );
+        for player in body["players"].as_object().unwrap().values() {
+            assert!(
+                decimals(&player["complexity"]) <= 3,
+                "{}",
+                player["complexity"]
+            );
+            assert!(decimals(&player["activity"]) <= 3, "{}", player["activity"]);
+        }
+
+        // Internal precision is untouched
+        let internal = state.read().await.fingerprint.burau_trace_magnitude();
+        assert!(decimals(&serde_json::json!(internal)) > 3);
+        assert_eq!(
+            body["global"]["burau"].as_f64().unwrap(),
+            round_metric(internal, Some(3))
+        );
+    }
+
+    /// Receives broadcasts until the next fingerprint update.
+    async fn next_fingerprint(rx: &mut broadcast::Receiver<ServerEvent>) -> FingerprintResponse {
+        loop {
+            if let ServerEvent::Fingerprint(response) = rx.recv().await.unwrap() {
+                return response;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_seat_broadcasts_seating() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+
+        let mut rx = tx.subscribe();
+        post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        post_action(&state, &tx, "Alice @ p1 calls 400").await;
+
+        let mut seating = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let ServerEvent::Seating(event) = event {
+                seating.push(event);
+            }
+        }
+        assert_eq!(
+            seating.len(),
+            1,
+            "Only the new player triggers a seating event"
+        );
+        let names: Vec<&str> = seating[0].seats.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Alice", "Bob"]);
+
+        let json = serde_json::to_value(ServerEvent::Seating(seating.remove(0))).unwrap();
+        assert_eq!(json["event"], "seating");
+        assert_eq!(json["seats"][1]["seat"], 2);
+        assert_eq!(json["seats"][1]["name"], "Bob");
+        assert_eq!(json["seats"][1]["id"], "Bob_p2");
+    }
+
+    #[tokio::test]
+    async fn test_dedup_consecutive() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dedup_consecutive(Some(Duration::from_secs(60)))
+                .build(),
+        );
+
+        post_action(&state, &tx, "Alice @ p1 calls 50").await;
+        let (status, body) = post_action(&state, &tx, "Alice @ p1 calls 50").await;
+        assert_eq!(status, warp::http::StatusCode::OK);
+        assert_eq!(body["duplicate"], true);
+        assert_eq!(state.read().await.total_actions, 1);
+        assert_eq!(state.read().await.action_log.len(), 1);
+
+        // Same action by different players is not a duplicate
+        post_action(&state, &tx, "Alice @ p1 checks").await;
+        post_action(&state, &tx, "Bob @ p2 checks").await;
+        assert_eq!(state.read().await.total_actions, 3);
+
+        // Outside the window the repeat is accepted again
+        let mut guard = state.write().await;
+        let later = Instant::now() + Duration::from_secs(61);
+        assert!(!guard.is_duplicate("Bob @ p2 checks", later));
+
+        // Without the flag nothing is dropped
+        let (state, tx) = shared_state(ServerState::builder().build());
+        post_action(&state, &tx, "Alice @ p1 calls 50").await;
+        post_action(&state, &tx, "Alice @ p1 calls 50").await;
+        assert_eq!(state.read().await.total_actions, 2);
+    }
+
+    #[test]
+    fn test_tempo_counts_actions_per_minute() {
+        let mut state = ServerState::builder().build();
+        let start = Instant::now();
+        assert_eq!(state.tempo(start), None);
+
+        // Six actions, one every 6 seconds, read back 30 seconds in
+        for i in 0..6 {
+            state.record_tempo(start + Duration::from_secs(i * 6));
+        }
+        let tempo = state.tempo(start + Duration::from_secs(30)).unwrap();
+        assert!(
+            (tempo - 12.0).abs() < 1e-9,
+            "Expected ~12 actions/minute, got {}",
+            tempo
+        );
+
+        // Once the table goes quiet the actions age out of the window
+        assert_eq!(state.tempo(start + Duration::from_secs(120)), None);
+    }
+
+    #[tokio::test]
+    async fn test_ws_client_gets_current_state_first() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        let (_, last) = post_action(&state, &tx, "Bob @ p2 calls 200").await;
+
+        let mut client = warp::test::ws()
+            .path("/ws")
+            .handshake(routes)
+            .await
+            .unwrap();
+        let message = client.recv().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+        assert_eq!(body["action"], "--- CURRENT STATE ---");
+        assert_eq!(body["step"], last["step"]);
+        assert_eq!(body["global"], last["global"]);
+        assert_eq!(body["players"], last["players"]);
+
+        // Later updates follow as usual
+        post_action(&state, &tx, "Alice @ p1 checks").await;
+        let message = client.recv().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+        assert_eq!(body["step"], 3);
+    }
+
+    #[tokio::test]
+    async fn test_ws_subprotocol_negotiation() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let routes = create_routes(state, tx, &[]);
+
+        let mut supported = warp::test::ws()
+            .path("/ws")
+            .header("sec-websocket-protocol", "pokerbraids.v1")
+            .handshake(routes.clone())
+            .await
+            .unwrap();
+        let message = supported.recv().await.unwrap();
+        let body: serde_json::Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+        assert_eq!(body["action"], "--- CURRENT STATE ---");
+
+        let mut unsupported = warp::test::ws()
+            .path("/ws")
+            .header("sec-websocket-protocol", "pokerbraids.v99")
+            .handshake(routes)
+            .await
+            .unwrap();
+        // Closed straight away, before any state is pushed
+        unsupported.recv_closed().await.unwrap();
+
+        assert_eq!(
+            negotiate_ws_protocol(Some("pokerbraids.v99, pokerbraids.v1")),
+            Ok(Some(WS_PROTOCOL))
+        );
+        assert_eq!(negotiate_ws_protocol(Some("pokerbraids.v99")), Err(()));
+        assert_eq!(negotiate_ws_protocol(None), Ok(None));
+    }
+
+    /// Plugin that blows up halfway through braiding σ2
+    #[derive(Debug, Clone)]
+    struct PanicOnSigma2;
+
+    impl braid_engine::Invariant for PanicOnSigma2 {
+        fn name(&self) -> &str {
+            "panic_on_sigma2"
+        }
+        fn update(&mut self, gen: &braid_engine::Generator) {
+            if gen.index() == 2 {
+                panic!("matrix exploded");
+            }
+        }
+        fn value(&self) -> f64 {
+            0.0
+        }
+        fn reset(&mut self) {}
+    }
+
+    #[tokio::test]
+    async fn test_panic_while_processing_returns_500() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        post_action(&state, &tx, "Bob @ p2 calls 100").await;
+        state
+            .write()
+            .await
+            .fingerprint
+            .register_invariant(Box::new(PanicOnSigma2));
+
+        // The raise braids σ2, which panics after the writhe and word moved
+        let response = warp::test::request()
+            .method("POST")
+            .path("/action")
+            .json(&serde_json::json!({"action_string": "Carol @ p3 raises to 300"}))
+            .reply(&create_routes(state.clone(), tx.clone(), &[]))
+            .await;
+        assert_eq!(
+            response.status(),
+            warp::http::StatusCode::INTERNAL_SERVER_ERROR
+        );
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert!(body["error"].as_str().unwrap().contains("matrix exploded"));
+
+        // Rebuilt from the log: nothing of the failed raise is left
+        {
+            let state = state.read().await;
+            assert_eq!(state.fingerprint.word.to_string(), "σ1");
+            assert_eq!(state.fingerprint.writhe, 1);
+            assert_eq!(state.step, 2);
+            assert_eq!(state.total_actions, 2);
+            assert_eq!(state.seat_resolver.player_count(), 2);
+        }
+
+        let (status, body) = post_action(&state, &tx, "Carol @ p3 raises to 300").await;
+        assert_eq!(status, warp::http::StatusCode::OK);
+        assert_eq!(body["step"], 3);
+        assert_eq!(body["global"]["writhe"], 2);
+
+        // Ordinary errors pass through unchanged
+        let mut state = state.write().await;
+        let err = run_guarded::<()>(&mut state, |_| Err("bad seat".into())).unwrap_err();
+        assert_eq!(err.to_string(), "bad seat");
+    }
+
+    #[tokio::test]
+    async fn test_jones_is_computed_on_request() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(2).build());
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+
+        // Alice -> Bob -> Alice: σ1 σ1⁻¹, which closes up into the unlink
+        for action in [
+            "Alice @ p1 bets 100",
+            "Bob @ p2 calls 100",
+            "Alice @ p1 checks",
+        ] {
+            post_action(&state, &tx, action).await;
+        }
+        assert!(
+            state.read().await.fingerprint.jones_poly_cache.is_none(),
+            "Not computed per action"
+        );
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/jones")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), warp::http::StatusCode::OK);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["polynomial"], "-t^-1/2 - t^1/2");
+        assert_eq!(body["step"], 3);
+        assert_eq!(body["crossings"], 2);
+        assert_eq!(
+            state.read().await.fingerprint.jones_poly_cache.as_deref(),
+            body["polynomial"].as_str(),
+            "Cached until the next generator"
+        );
+
+        post_action(&state, &tx, "Bob @ p2 checks").await;
+        assert!(state.read().await.fingerprint.jones_poly_cache.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_jones_reflects_live_hand() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(2)
+                .flow_model(FlowModel::WrapAggressor)
+                .build(),
+        );
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+
+        // σ1 for the raise, then the call wraps back around the raiser: the trefoil σ1³
+        for action in [
+            "Alice @ p1 bets 100",
+            "Bob @ p2 raises to 300",
+            "Alice @ p1 calls 300",
+        ] {
+            post_action(&state, &tx, action).await;
+        }
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/jones")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["polynomial"], "t + t^3 - t^4");
+        assert_eq!(body["strands"], 2);
+        assert_eq!(body["crossings"], 3);
+
+        // A new hand closes up to the unlink
+        post_action(&state, &tx, "-- starting hand #2 --").await;
+        let response = warp::test::request()
+            .method("GET")
+            .path("/jones")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["polynomial"], "-t^-1/2 - t^1/2");
+        assert_eq!(body["crossings"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_tables_are_independent() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let routes = create_routes(state.clone(), tx, &[]);
+        let post = |path: &'static str, action: &'static str| {
+            warp::test::request()
+                .method("POST")
+                .path(path)
+                .json(&serde_json::json!({"action_string": action}))
+                .reply(&routes)
+        };
+        let writhe = |path: &'static str| async {
+            let response = warp::test::request()
+                .method("GET")
+                .path(path)
+                .reply(&routes)
+                .await;
+            let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+            body["global"]["writhe"].as_i64().unwrap()
+        };
+
+        // Tables are created by writes, not by subscribing
+        let response = warp::test::request()
+            .method("POST")
+            .path("/reset?table=b")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 200);
+        let mut table_b = warp::test::ws()
+            .path("/ws?table=b")
+            .handshake(routes.clone())
+            .await
+            .unwrap();
+        table_b.recv().await.unwrap(); // Current state on connect
+
+        // Each table seats its own players. Table a: 1 -> 2 -> 3 (writhe 2),
+        // table b: Carol 1 -> Alice 2 (writhe 1)
+        post("/action?table=a", "Alice @ p1 bets 100").await;
+        post("/action?table=b", "Carol @ p3 bets 100").await;
+        post("/action?table=a", "Bob @ p2 calls 100").await;
+        post("/action?table=b", "Alice @ p1 calls 100").await;
+        post("/action?table=a", "Carol @ p3 calls 100").await;
+
+        assert_eq!(writhe("/state?table=a").await, 2);
+        assert_eq!(writhe("/state?table=b").await, 1);
+        assert_eq!(writhe("/state").await, 0, "The default table saw nothing");
+        assert_eq!(state.read().await.total_actions, 0);
+
+        // Table b's subscriber only hears table b: its seating, then its two actions
+        let mut steps = Vec::new();
+        while steps.len() < 2 {
+            let message = table_b.recv().await.unwrap();
+            let body: serde_json::Value = serde_json::from_str(message.to_str().unwrap()).unwrap();
+            if body.get("step").is_some() {
+                steps.push((
+                    body["step"].as_u64().unwrap(),
+                    body["global"]["writhe"].as_i64().unwrap(),
+                ));
+            }
+        }
+        assert_eq!(steps, [(1, 0), (2, 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_only_writes_create_tables() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let tables = Tables::new(state.clone(), tx.clone());
+        let routes = create_routes(state, tx, &[]);
+        let get = |path: &'static str| {
+            warp::test::request()
+                .method("GET")
+                .path(path)
+                .reply(&routes)
+        };
+
+        // Reads of an unknown table don't create it
+        for path in [
+            "/state?table=ghost",
+            "/history?table=ghost",
+            "/jones?table=ghost",
+        ] {
+            let response = get(path).await;
+            assert_eq!(response.status(), 404, "{}", path);
+            let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+            assert_eq!(body["error"], "Unknown table 'ghost'");
+        }
+        assert!(warp::test::ws()
+            .path("/ws?table=ghost")
+            .handshake(routes.clone())
+            .await
+            .is_err());
+        assert_eq!(get("/state?table=ghost").await.status(), 404);
+
+        let response = warp::test::request()
+            .method("POST")
+            .path("/reset?table=ghost")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(get("/state?table=ghost").await.status(), 200);
+        let close = |path: &'static str| {
+            warp::test::request()
+                .method("DELETE")
+                .path(path)
+                .reply(&routes)
+        };
+        assert_eq!(close("/table?table=ghost").await.status(), 200);
+        assert_eq!(close("/table?table=ghost").await.status(), 404);
+        assert_eq!(
+            close("/table").await.status(),
+            400,
+            "The default table stays"
+        );
+        assert_eq!(get("/state?table=ghost").await.status(), 404);
+
+        // At most MAX_TABLES are created
+        for i in 0..MAX_TABLES {
+            assert!(tables.get_or_create(Some(&format!("t{}", i))).await.is_ok());
+        }
+        assert!(
+            tables.get_or_create(Some("t0")).await.is_ok(),
+            "Existing tables are still served"
+        );
+        assert!(matches!(
+            tables.get_or_create(Some("one-too-many")).await,
+            Err(TableLimit(MAX_TABLES))
+        ));
+        assert!(tables.get_or_create(None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_idle_watch_stops_with_its_table() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .idle_after(Some(Duration::from_secs(60)))
+                .build(),
+        );
+        let tables = Tables::new(state, tx.clone());
+        let (table, _) = tables.get_or_create(Some("a")).await.unwrap();
+        let watcher = spawn_idle_watch(&table, tx);
+        drop(table);
+
+        assert!(tables.remove("a").await);
+        assert!(!tables.remove("a").await);
+        // The table's own watcher is gone with it; so is this one
+        tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_standing_players_are_not_ranked() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        post_action(&state, &tx, "Bob @ p2 calls 100").await;
+        let (_, body) = post_action(&state, &tx, "Carol @ p3 calls 100").await;
+        assert_eq!(body["players"]["3"]["activity_rank"], 1);
+        assert_eq!(body["players"]["2"]["activity_rank"], 2);
+
+        post_action(&state, &tx, "Bob @ p2 stands up").await;
+        let (_, body) = post_action(&state, &tx, "Alice @ p1 calls 100").await;
+        assert!(body["players"]["2"]["activity_rank"].is_null());
+        assert_eq!(body["players"]["1"]["activity_rank"], 1);
+        assert_eq!(body["players"]["3"]["activity_rank"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_history_keeps_recent_steps() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(4)
+                .history_capacity(3)
+                .build(),
+        );
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+        for action in [
+            "Alice @ p1 bets 100",
+            "Bob @ p2 calls 100",
+            "Carol @ p3 raises to 300",
+            "Alice @ p1 calls 300",
+            "Bob @ p2 folds",
+        ] {
+            post_action(&state, &tx, action).await;
+        }
+
+        // Oldest entries were evicted
+        let steps: Vec<usize> = state.read().await.history.iter().map(|r| r.step).collect();
+        assert_eq!(steps, [3, 4, 5]);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/history?limit=2")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        let steps: Vec<u64> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["step"].as_u64().unwrap())
+            .collect();
+        assert_eq!(steps, [4, 5]);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/history")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body.as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_idle_event_fires_once() {
+        let mut state = ServerState::builder()
+            .idle_after(Some(Duration::from_secs(30)))
+            .build();
+        let start = Instant::now();
+        assert_eq!(
+            state.check_idle(start + Duration::from_secs(60)),
+            None,
+            "Nothing before the first action"
+        );
+
+        assert_eq!(state.mark_active(start), None);
+        assert_eq!(state.check_idle(start + Duration::from_secs(10)), None);
+        assert_eq!(
+            state.check_idle(start + Duration::from_secs(31)),
+            Some(TableEvent::Idle { secs: 31 })
+        );
+        assert_eq!(
+            state.check_idle(start + Duration::from_secs(90)),
+            None,
+            "Only sent once"
+        );
+
+        assert_eq!(
+            state.mark_active(start + Duration::from_secs(95)),
+            Some(TableEvent::Active)
+        );
+        assert_eq!(state.mark_active(start + Duration::from_secs(96)), None);
+    }
+
+    #[tokio::test]
+    async fn test_action_after_idle_broadcasts_active() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .idle_after(Some(Duration::from_secs(30)))
+                .build(),
+        );
+        post_action(&state, &tx, "Alice @ p1 bets 100").await;
+
+        let mut rx = tx.subscribe();
+        let idle = state
+            .write()
+            .await
+            .check_idle(Instant::now() + Duration::from_secs(45));
+        assert_eq!(
+            serde_json::to_value(ServerEvent::Table(idle.unwrap())).unwrap(),
+            serde_json::json!({"event": "idle", "secs": 45})
+        );
+
+        // Alice is already seated, so no seating event comes first
+        post_action(&state, &tx, "Alice @ p1 checks").await;
+        let event = serde_json::to_value(rx.recv().await.unwrap()).unwrap();
+        assert_eq!(event, serde_json::json!({"event": "active"}));
+        assert_eq!(next_fingerprint(&mut rx).await.step, 2);
+    }
+
+    #[tokio::test]
+    async fn test_state_reports_peak() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(3).build());
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+
+        // Bob's call braids σ1: the Burau trace on three strands is
+        // (1 - t) + 0 + 1, so the magnitude is |2 - e^i| = sqrt(5 - 4 cos 1).
+        // Carol's call adds σ2 and the trace of U1 U2 drops to |1 - e^i|.
+        post_action(&state, &tx, "Alice @ p1 bets 20").await;
+        let (_, body) = post_action(&state, &tx, "Bob @ p2 calls 20").await;
+        let peak_burau = (5.0 - 4.0 * 1.0_f64.cos()).sqrt();
+        assert!((body["global"]["burau"].as_f64().unwrap() - peak_burau).abs() < 1e-9);
+        let (_, body) = post_action(&state, &tx, "Carol @ p3 calls 20").await;
+        let unwound = 2.0 * 0.5_f64.sin();
+        assert!((body["global"]["burau"].as_f64().unwrap() - unwound).abs() < 1e-9);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/state")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["peak"]["step"], 2);
+        assert!((body["peak"]["burau"].as_f64().unwrap() - peak_burau).abs() < 1e-9);
+
+        post_action(&state, &tx, "-- starting hand #2 --").await;
+        let response = warp::test::request()
+            .method("GET")
+            .path("/state")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert!(body["peak"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_call_reports_pot_odds() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+
+        let (_, body) = post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        assert!(
+            body["pot_odds"].is_null(),
+            "Non-call actions have no pot odds"
+        );
+        post_action(&state, &tx, "Bob @ p2 posts 50").await;
+
+        // A call of 50 into a pot of 150
+        let (_, body) = post_action(&state, &tx, "Carol @ p3 calls 50").await;
+        assert_eq!(body["pot_odds"], 0.25);
+        assert_eq!(body["pot"], 200);
+
+        let (_, body) = post_action(&state, &tx, "-- starting hand #2 --").await;
+        assert_eq!(body["pot"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_hand_count_counts_resets() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+
+        for hand in 1..=3 {
+            post_action(&state, &tx, &format!("-- starting hand #{} --", hand)).await;
+            post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        }
+        let (_, body) = post_action(&state, &tx, "Bob @ p2 calls 100").await;
+        assert_eq!(body["hand_count"], 3);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/state")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["hand_count"], 3);
+        assert_eq!(body["explicit_resets"], 3);
+        assert_eq!(body["fold_resets"], 0);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/metrics")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 200);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["hand_count"], 3);
+        assert_eq!(body["explicit_resets"], 3);
+        assert_eq!(body["fold_resets"], 0);
+        assert_eq!(body["total_actions"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_state_matches_last_update() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let routes = create_routes(state.clone(), tx.clone(), &[]);
+
+        post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        let (_, update) = post_action(&state, &tx, "Bob @ p2 raises to 300").await;
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/state")
+            .reply(&routes)
+            .await;
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["step"], update["step"]);
+        assert_eq!(body["global"], update["global"]);
+        assert_eq!(body["players"], update["players"]);
+        assert_eq!(body["players"]["2"]["name"], "Bob_p2");
+
+        // Reading the state doesn't move anything
+        warp::test::request()
+            .method("GET")
+            .path("/state")
+            .reply(&routes)
+            .await;
+        assert_eq!(state.read().await.step, 2);
+    }
+
+    #[tokio::test]
+    async fn test_min_amount_ignores_small_bets() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(4)
+                .min_amount(Some(20))
+                .build(),
+        );
+
+        post_action(&state, &tx, "Alice @ p1 checks").await;
+        let (_, body) = post_action(&state, &tx, "Bob @ p2 bets 10").await;
+        assert_eq!(
+            state.read().await.fingerprint.crossing_count,
+            0,
+            "A bet of 10 braids nothing"
+        );
+        assert_eq!(body["step"], 2);
+        assert_eq!(body["pot"], 10, "The chips still go in");
+
+        post_action(&state, &tx, "Carol @ p3 bets 50").await;
+        let guard = state.read().await;
+        assert_eq!(
+            guard.fingerprint.crossing_count, 1,
+            "Flow moved on from Bob's seat"
+        );
+        assert_eq!(guard.fingerprint.word.to_string(), "σ2");
+        assert_eq!(guard.fingerprint.collisions, 0);
+    }
+
+    /// Subscriber that only records the names of the spans created.
+    struct SpanNames(std::sync::Mutex<Vec<&'static str>>);
+
+    impl tracing::Subscriber for SpanNames {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            tracing::span::Id::from_u64(names.len() as u64)
+        }
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+        fn event(&self, _: &tracing::Event<'_>) {}
+        fn enter(&self, _: &tracing::span::Id) {}
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[test]
+    fn test_processing_records_phase_spans() {
+        let names = std::sync::Arc::new(SpanNames(std::sync::Mutex::new(Vec::new())));
+        let mut state = ServerState::builder().dimension(4).build();
+        tracing::subscriber::with_default(names.clone(), || {
+            let action = parse_action_string("Alice @ p1 bets 100", &mut state).unwrap();
+            process_action(action, &mut state).unwrap();
+        });
+
+        assert_eq!(
+            *names.0.lock().unwrap(),
+            [
+                "parse_action_string",
+                "process_action",
+                "expand_action",
+                "update_matrices"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disambiguate_names_splits_same_name_players() {
+        let mut state = ServerState::builder()
+            .dimension(4)
+            .disambiguate_names(true)
+            .build();
+        let first = parse_action_string("Player bets 20", &mut state).unwrap();
+        process_action(first.clone(), &mut state).unwrap();
+        let second = parse_action_string("Player calls 20", &mut state).unwrap();
+
+        assert_ne!(first.seat, second.seat);
+        assert_eq!(state.seat_resolver.player_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_player_filter_limits_player_metrics() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(4)
+                .players(vec!["Bob".to_string()])
+                .build(),
+        );
+
+        post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        post_action(&state, &tx, "Bob @ p2 calls 100").await;
+        let (_, body) = post_action(&state, &tx, "Carol @ p3 raises to 300").await;
+
+        let players = body["players"].as_object().unwrap();
+        assert_eq!(players.keys().collect::<Vec<_>>(), ["2"]);
+        assert_eq!(players["2"]["name"], "Bob_p2");
+        // The braid itself still sees all three: σ1 σ2
+        assert_eq!(body["global"]["writhe"], 2);
+
+        let restored =
+            ServerState::restore(&state.read().await.snapshot(), &Config::default()).unwrap();
+        assert_eq!(
+            restored.fingerprint.player_stats.keys().collect::<Vec<_>>(),
+            [&2]
+        );
+    }
+
+    #[test]
+    fn test_fold_resets_are_counted_separately() {
+        let mut state = ServerState::builder()
+            .dimension(4)
+            .reset_on_fold(true)
+            .build();
+        process_action(
+            Action::new(braid_engine::Seat::new(1), ActionType::Reset, 0),
+            &mut state,
+        )
+        .unwrap();
+        process_action(
+            Action::new(braid_engine::Seat::new(2), ActionType::Fold, 0),
+            &mut state,
+        )
+        .unwrap();
+
+        assert_eq!(state.explicit_resets, 1);
+        assert_eq!(state.fold_resets, 1);
+        assert_eq!(state.hand_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_raise_to_adds_only_the_difference() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+
+        post_action(&state, &tx, "Alice @ p1 bets 100").await;
+        post_action(&state, &tx, "Bob @ p2 raises to 300").await;
+        // Alice already has 100 in, the re-raise adds 800
+        let (_, body) = post_action(&state, &tx, "Alice @ p1 raises to 900").await;
+        assert_eq!(body["pot"], 1200);
+
+        // Totals start over on the next street
+        post_action(&state, &tx, "Flop: [Ah, Kd, 2c]").await;
+        post_action(&state, &tx, "Bob @ p2 bets 200").await;
+        let (_, body) = post_action(&state, &tx, "Alice @ p1 raises to 600").await;
+        assert_eq!(body["pot"], 2000);
+    }
+
+    #[test]
+    fn test_negative_amount_comes_out_of_pot() {
+        let mut state = ServerState::builder().dimension(4).build();
+        process_action(
+            Action::new(braid_engine::Seat::new(1), ActionType::Bet, 100),
+            &mut state,
+        )
+        .unwrap();
+
+        // A 30 chip correction
+        let response = process_action(
+            Action::new(braid_engine::Seat::new(1), ActionType::Bet, -30),
+            &mut state,
+        )
+        .unwrap();
+        assert_eq!(response.pot, 70);
+        assert_eq!(pot_odds(ActionType::Call, -30, 70), None);
+    }
+
+    #[tokio::test]
+    async fn test_autosave_restores_writhe() {
+        let path =
+            std::env::temp_dir().join(format!("poker-braids-autosave-{}.json", std::process::id()));
+        let path_str = path.to_str().unwrap().to_string();
+        fs::remove_file(&path).ok();
+
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(4)
+                .autosave(Some(3), path_str.clone())
+                .build(),
+        );
+
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        assert!(!path.exists(), "Autosave should wait for N actions");
+
+        post_action(&state, &tx, "Carol @ p3 raises to 600").await;
+        // Written off the request path, give the blocking thread a moment
+        for _ in 0..100 {
+            if path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(path.exists(), "Autosave file should exist after N actions");
+
+        let restored = ServerState::load_snapshot(&path_str, &Config::default()).unwrap();
+        fs::remove_file(&path).ok();
+
+        let original = state.read().await;
+        assert_eq!(restored.fingerprint.writhe, original.fingerprint.writhe);
+        assert_eq!(restored.step, original.step);
+        assert_eq!(restored.action_log, original.action_log);
+    }
+
+    #[tokio::test]
+    async fn test_load_keeps_serving_options_and_braid_settings() {
+        let (state, tx) = shared_state(
+            ServerState::builder()
+                .dimension(5)
+                .flow_model(FlowModel::WrapAggressor)
+                .normalization_window(Some(8))
+                .build(),
+        );
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        let snapshot = state.read().await.snapshot();
+
+        // The restarted server asks for other serving options and braid settings
+        let config = Config {
+            dimension: 9,
+            round_digits: Some(2),
+            history: 7,
+            dedup_consecutive: true,
+            dedup_window_ms: 250,
+            ..Config::default()
+        };
+        let restored = ServerState::restore(&snapshot, &config).unwrap();
+        assert_eq!(restored.round_digits, Some(2));
+        assert_eq!(restored.history_capacity, 7);
+        assert_eq!(restored.dedup_window, Some(Duration::from_millis(250)));
+        // ... but the braid replays as it was recorded
+        assert_eq!(restored.fingerprint.dimension(), 5);
+        assert_eq!(restored.flow_model, FlowModel::WrapAggressor);
+        assert_eq!(restored.fingerprint.normalization_window(), Some(8));
+        assert_eq!(
+            restored.fingerprint.word,
+            state.read().await.fingerprint.word
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_restores_generator_histogram() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        post_action(&state, &tx, "Bob @ p2 calls 200").await;
+        post_action(&state, &tx, "-- starting hand #2 --").await;
+        post_action(&state, &tx, "Carol @ p3 bets 100").await;
+        post_action(&state, &tx, "Alice @ p1 raises to 300").await;
+
+        let original = state.read().await;
+        let json = serde_json::to_string(&original.snapshot()).unwrap();
+        let restored =
+            ServerState::restore(&serde_json::from_str(&json).unwrap(), &Config::default())
+                .unwrap();
+
+        assert!(
+            original
+                .fingerprint
+                .generator_histogram
+                .iter()
+                .sum::<usize>()
+                > 0
+        );
+        assert_eq!(
+            restored.fingerprint.generator_histogram,
+            original.fingerprint.generator_histogram
+        );
+    }
+
+    #[tokio::test]
+    async fn test_action_log_keeps_only_the_current_hand() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        post_action(&state, &tx, "Alice @ p1 raises to 200").await;
+        post_action(&state, &tx, "Bob @ p2 stands up").await;
+        post_action(&state, &tx, "Carol @ p3 calls 200").await;
+        post_action(&state, &tx, "-- starting hand #2 --").await;
+        post_action(&state, &tx, "Carol @ p3 bets 100").await;
+        post_action(&state, &tx, "Alice @ p1 raises to 300").await;
+
+        let original = state.read().await;
+        assert_eq!(
+            original.action_log,
+            ["Carol @ p3 bets 100", "Alice @ p1 raises to 300"]
+        );
+        assert_eq!(original.logged_entries(), 6);
+
+        // The earlier hand lives on in the snapshot's base
+        let restored = ServerState::restore(&original.snapshot(), &Config::default()).unwrap();
+        assert_eq!(restored.fingerprint.word, original.fingerprint.word);
+        assert_eq!(restored.seat_resolver, original.seat_resolver);
+        assert_eq!(restored.total_actions, original.total_actions);
+        assert_eq!(restored.hand_count(), 1);
+        assert!(!restored.fingerprint.is_seat_active(2));
+        assert_eq!(restored.logged_entries(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_handle_action_rejects_unparseable_string() {
+        let (state, tx) = shared_state(ServerState::builder().build());
+
+        // Routed through the rejection path and recovered into JSON
+        let response = warp::test::request()
+            .method("POST")
+            .path("/action")
+            .json(&serde_json::json!({"action_string": "not a poker action"}))
+            .reply(&create_routes(state.clone(), tx, &[]))
+            .await;
+        assert_eq!(response.status(), warp::http::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"error": "Failed to parse action string"})
+        );
+        assert_eq!(state.read().await.step, 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_action_rejects_malformed_amount() {
+        let (state, tx) = shared_state(ServerState::builder().build());
+        let response = warp::test::request()
+            .method("POST")
+            .path("/action")
+            .json(&serde_json::json!({"action_string": "Alice @ p1 calls 1.2.3"}))
+            .reply(&create_routes(state.clone(), tx, &[]))
+            .await;
+        assert_eq!(response.status(), warp::http::StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert!(
+            body["error"]
+                .as_str()
+                .unwrap()
+                .contains("Invalid amount '1.2.3'"),
+            "{}",
+            body
+        );
+        assert_eq!(state.read().await.step, 0);
+    }
+
+    #[tokio::test]
+    async fn test_handle_action_parse_error_is_custom_rejection() {
+        let (state, tx) = shared_state(ServerState::builder().build());
+        let req = ActionRequest {
+            action_string: "not a poker action".to_string(),
+        };
+
+        let rejection = handle_action(req, state, tx).await.err().unwrap();
+        assert!(rejection.find::<ActionParseRejection>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_cors_rejects_disallowed_origin() {
+        let (state, tx) = shared_state(ServerState::builder().dimension(4).build());
+        let routes = create_routes(state, tx, &["https://www.pokernow.club".to_string()]);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/state")
+            .header("origin", "https://evil.example")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 403);
+
+        let response = warp::test::request()
+            .method("GET")
+            .path("/state")
+            .header("origin", "https://www.pokernow.club")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(
+            response.headers()["access-control-allow-origin"],
+            "https://www.pokernow.club"
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::fs;
+use std::process::Command;
+
+/// Runs the CLI with `args` and returns its exit code.
+fn exit_code(args: &[&str]) -> i32 {
+    let output = Command::new(env!("CARGO_BIN_EXE_poker-braids"))
+        .args(args)
+        .output()
+        .expect("Failed to run poker-braids");
+    output
+        .status
+        .code()
+        .expect("poker-braids was killed by a signal")
+}
+
+/// Writes `contents` to a temp file unique to this test and returns its path.
+fn temp_log(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "poker-braids-exit-{}-{}.csv",
+        name,
+        std::process::id()
+    ));
+    fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_success_exits_zero() {
+    let path = temp_log(
+        "ok",
+        "player_id,action,amount\nAlice,raise,100\nBob,call,100\n",
+    );
+    let code = exit_code(&["--dimension", "4", &path]);
+    fs::remove_file(&path).ok();
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn test_usage_errors_exit_one() {
+    assert_eq!(exit_code(&["--bogus"]), 1);
+    assert_eq!(exit_code(&["--dimension", "1", "log.csv"]), 1);
+    // No input file
+    assert_eq!(exit_code(&[]), 1);
+}
+
+#[test]
+fn test_missing_file_exits_two() {
+    assert_eq!(exit_code(&["/nonexistent/poker-braids-log.csv"]), 2);
+    assert_eq!(
+        exit_code(&["--config", "/nonexistent/poker-braids.json", "log.csv"]),
+        2
+    );
+}
+
+#[test]
+fn test_unwritable_output_exits_two() {
+    let path = temp_log(
+        "unwritable",
+        "player_id,action,amount\nAlice,raise,100\nBob,call,100\n",
+    );
+    let export = exit_code(&["--export-hands", "/nonexistent/dir/hands.json", &path]);
+    let report = exit_code(&["--player-report", "/nonexistent/dir/players.csv", &path]);
+    fs::remove_file(&path).ok();
+    assert_eq!(export, 2);
+    assert_eq!(report, 2);
+}
+
+#[test]
+fn test_parse_error_exits_three() {
+    let path = temp_log("parse", "player_id,action,amount\nAlice,limp,100\n");
+    let code = exit_code(&[&path]);
+    fs::remove_file(&path).ok();
+    assert_eq!(code, 3);
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Card suit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+/// A playing card, e.g. `Ah` or `T♠`.
+///
+/// Ranks are stored as their one-character symbol (`2`-`9`, `T`, `J`, `Q`,
+/// `K`, `A`); "10" is accepted as `T` when parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Card {
+    pub rank: char,
+    pub suit: Suit,
+}
+
+impl fmt::Display for Card {
+    /// Renders the short form, e.g. `Ah`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suit = match self.suit {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        };
+        write!(f, "{}{}", self.rank, suit)
+    }
+}
+
+impl FromStr for Card {
+    type Err = String;
+
+    /// Parses `Ah`, `10d` or `K♠` (suit as a letter or a symbol, any case).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let suit_char = s.chars().last().ok_or("Empty card")?;
+        let rank_str = &s[..s.len() - suit_char.len_utf8()];
+
+        let suit = match suit_char.to_ascii_lowercase() {
+            'c' | '♣' => Suit::Clubs,
+            'd' | '♦' => Suit::Diamonds,
+            'h' | '♥' => Suit::Hearts,
+            's' | '♠' => Suit::Spades,
+            _ => return Err(format!("Invalid suit in card '{}'", s)),
+        };
+        let rank = match rank_str.to_ascii_uppercase().as_str() {
+            "10" | "T" => 'T',
+            r @ ("2" | "3" | "4" | "5" | "6" | "7" | "8" | "9" | "J" | "Q" | "K" | "A") => {
+                r.chars().next().unwrap_or_default()
+            }
+            _ => return Err(format!("Invalid rank in card '{}'", s)),
+        };
+
+        Ok(Card { rank, suit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(
+            "Ah".parse(),
+            Ok(Card {
+                rank: 'A',
+                suit: Suit::Hearts
+            })
+        );
+        assert_eq!(
+            "10♠".parse(),
+            Ok(Card {
+                rank: 'T',
+                suit: Suit::Spades
+            })
+        );
+        assert_eq!(
+            "tD".parse(),
+            Ok(Card {
+                rank: 'T',
+                suit: Suit::Diamonds
+            })
+        );
+        assert_eq!("K♦".parse::<Card>().unwrap().to_string(), "Kd");
+        assert!("1h".parse::<Card>().is_err());
+        assert!("Ax".parse::<Card>().is_err());
+        assert!("".parse::<Card>().is_err());
+    }
+}
@@ -1,9 +1,104 @@
 use braid_engine::{Action, ActionType, Seat};
 use csv::StringRecord;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
 
+pub mod card;
 pub mod pokernow;
+pub mod pokerstars;
+pub mod stream;
+
+pub use card::{Card, Suit};
+pub use pokernow::NameDisambiguator;
+pub use stream::{parse_stream, ActionStream, Format, StreamOptions};
+
+/// Error returned when a log record cannot be turned into an `Action`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The record has no field at `index` (`name` is the expected column)
+    MissingField { index: usize, name: &'static str },
+    /// The action column is not a recognised action
+    UnknownAction(String),
+    /// The amount column is not an integer
+    InvalidAmount { raw: String, source: ParseIntError },
+    /// The seat column is not a 1-based seat number (numeric-seat logs)
+    InvalidSeat(String),
+    /// The input itself is not valid CSV
+    Csv(String),
+    /// The input could not be read
+    Io(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingField { index, name } => {
+                write!(f, "Missing {} field (column {})", name, index)
+            }
+            ParseError::UnknownAction(action) => write!(f, "Unknown action type: '{}'", action),
+            ParseError::InvalidAmount { raw, source } => {
+                write!(f, "Invalid amount '{}': {}", raw, source)
+            }
+            ParseError::InvalidSeat(seat) => write!(f, "Invalid seat number: '{}'", seat),
+            ParseError::Csv(message) => write!(f, "Malformed CSV input: {}", message),
+            ParseError::Io(message) => write!(f, "Failed to read input: {}", message),
+        }
+    }
+}
+
+impl Error for ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseError::InvalidAmount { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a chip amount as exported by various sites: `$1,200`, `1 200`,
+/// `1200.50` or `-50`.
+///
+/// Currency signs, thousands separators and spaces are stripped and any
+/// decimals are truncated (`$1,200.50` is 1200). Anything else is an error,
+/// never a silent 0.
+pub fn parse_amount(raw: &str) -> Result<i64, ParseIntError> {
+    let cleaned: String = raw
+        .chars()
+        .filter(|c| !matches!(c, '$' | ',') && !c.is_whitespace())
+        .collect();
+    let (whole, fraction) = match cleaned.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (cleaned.as_str(), None),
+    };
+    if let Some(fraction) = fraction {
+        // Validated but dropped: amounts are whole chips
+        fraction.parse::<u64>()?;
+    }
+    whole.parse()
+}
+
+/// Parses a chip amount captured by a site's log regex (see `parse_amount`).
+///
+/// A malformed amount (e.g. `1.2.3`) is an `InvalidAmount` error rather than
+/// a skipped row. The regexes never capture a sign, so the amount can't be
+/// negative.
+pub(crate) fn parse_chip_amount(raw: &str) -> Result<u64, ParseError> {
+    let amount = parse_amount(raw).map_err(|source| ParseError::InvalidAmount {
+        raw: raw.to_string(),
+        source,
+    })?;
+    Ok(amount.max(0) as u64)
+}
+
+/// Expected columns of a generic CSV record, in order.
+const RECORD_FIELDS: [&str; 3] = ["player_id", "action", "amount"];
+
+/// Default value marking a hand boundary in a generic CSV log, see
+/// `parse_record_with_sentinel`.
+pub const DEFAULT_RESET_SENTINEL: &str = "reset";
 
 /// Parses a CSV record into an Action.
 /// 
@@ -14,30 +109,82 @@ pub mod pokernow;
 /// * `seat_resolver` - Resolver to map player_id to Seat
 /// 
 /// # Returns
-/// A Result containing the parsed Action or an error
-
+/// The parsed Action, or a `ParseError` saying which part of the record is bad
 pub fn parse_record(
     record: &StringRecord,
     seat_resolver: &mut SeatResolver,
-) -> Result<Action, Box<dyn Error>> {
-    if record.len() < 3 {
-        return Err("CSV record must have at least 3 fields: player_id, action, amount".into());
+) -> Result<Action, ParseError> {
+    parse_record_with_sentinel(record, seat_resolver, DEFAULT_RESET_SENTINEL)
+}
+
+/// Parses a CSV record into an Action, treating `reset_sentinel` as a hand
+/// boundary.
+///
+/// A record whose player_id or action equals the sentinel (case-insensitive),
+/// e.g. `system,reset,0` or `reset,,`, becomes an `ActionType::Reset`. Reset
+/// records don't take a seat and their amount is ignored.
+pub fn parse_record_with_sentinel(
+    record: &StringRecord,
+    seat_resolver: &mut SeatResolver,
+    reset_sentinel: &str,
+) -> Result<Action, ParseError> {
+    parse_record_fields(record, reset_sentinel, |player_id| {
+        Ok(seat_resolver.get_or_assign_seat(player_id))
+    })
+}
+
+/// Parses a CSV record whose first column is already the seat number
+/// (`3,raise,100`), bypassing the `SeatResolver` entirely.
+///
+/// Reset records are handled as in `parse_record_with_sentinel`. A seat that
+/// isn't a positive integer is a `ParseError::InvalidSeat`.
+pub fn parse_record_with_numeric_seat(
+    record: &StringRecord,
+    reset_sentinel: &str,
+) -> Result<Action, ParseError> {
+    parse_record_fields(record, reset_sentinel, |seat| {
+        seat.trim()
+            .parse()
+            .ok()
+            .and_then(|seat| Seat::try_new(seat).ok())
+            .ok_or_else(|| ParseError::InvalidSeat(seat.to_string()))
+    })
+}
+
+/// Shared body of the generic record parsers; `resolve_seat` turns the first
+/// column into a seat.
+fn parse_record_fields(
+    record: &StringRecord,
+    reset_sentinel: &str,
+    resolve_seat: impl FnOnce(&str) -> Result<Seat, ParseError>,
+) -> Result<Action, ParseError> {
+    let field = |index: usize| {
+        record.get(index).ok_or(ParseError::MissingField {
+            index,
+            name: RECORD_FIELDS[index],
+        })
+    };
+    let player_id = field(0)?;
+    let action_str = field(1)?;
+
+    let is_sentinel = |value: &str| value.trim().eq_ignore_ascii_case(reset_sentinel);
+    if is_sentinel(player_id) || is_sentinel(action_str) {
+        return Ok(Action::new(Seat::new(1), ActionType::Reset, 0));
     }
 
-    let player_id = record.get(0).ok_or("Missing player_id field")?;
-    let action_str = record.get(1).ok_or("Missing action field")?;
-    let amount_str = record.get(2).ok_or("Missing amount field")?;
+    let amount_str = field(2)?;
 
     // Resolve player_id to Seat
-    let seat = seat_resolver.get_or_assign_seat(player_id);
+    let seat = resolve_seat(player_id)?;
 
     // Parse action string to ActionType
     let action_type = parse_action_type(action_str)?;
 
-    // Parse amount
-    let amount = amount_str
-        .parse::<u64>()
-        .map_err(|e| format!("Invalid amount '{}': {}", amount_str, e))?;
+    // Parse amount (signed: negative amounts are refunds/corrections)
+    let amount = parse_amount(amount_str).map_err(|source| ParseError::InvalidAmount {
+        raw: amount_str.to_string(),
+        source,
+    })?;
 
     Ok(Action::new(seat, action_type, amount))
 }
@@ -52,7 +199,7 @@ pub fn parse_record(
 /// - "fold" -> ActionType::Fold
 /// - "reraise" or "re-raise" -> ActionType::ReRaise
 /// - "allin" or "all-in" -> ActionType::AllIn
-fn parse_action_type(action_str: &str) -> Result<ActionType, Box<dyn Error>> {
+fn parse_action_type(action_str: &str) -> Result<ActionType, ParseError> {
     match action_str.to_lowercase().trim() {
         "bet" => Ok(ActionType::Bet),
         "raise" => Ok(ActionType::Raise),
@@ -61,16 +208,23 @@ fn parse_action_type(action_str: &str) -> Result<ActionType, Box<dyn Error>> {
         "fold" => Ok(ActionType::Fold),
         "reraise" | "re-raise" => Ok(ActionType::ReRaise),
         "allin" | "all-in" => Ok(ActionType::AllIn),
-        _ => Err(format!("Unknown action type: '{}'", action_str).into()),
+        _ => Err(ParseError::UnknownAction(action_str.to_string())),
     }
 }
 
 /// Resolves player IDs to Seat numbers, thereby assigning seats sequentially as new player IDs appear in the stream.
-
-#[derive(Debug, Clone)]
+/// Seats freed with `release_player` are handed out again before new ones.
+///
+/// Serializable so the player -> seat mapping can be saved and restored
+/// across restarts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SeatResolver {
     player_to_seat: HashMap<String, Seat>,
     next_seat: usize,
+    /// Seats given up by departed players, reused lowest first
+    free_seats: BTreeSet<usize>,
+    /// Seat holding the dealer button in the current hand, if known
+    button: Option<Seat>,
 }
 
 impl SeatResolver {
@@ -79,9 +233,64 @@ impl SeatResolver {
         SeatResolver {
             player_to_seat: HashMap::new(),
             next_seat: 1, // Start with seat 1 (1-based indexing)
+            free_seats: BTreeSet::new(),
+            button: None,
         }
     }
 
+    /// Returns every assigned seat with its current player ID, ordered by seat.
+    pub fn seating(&self) -> Vec<(Seat, String)> {
+        let mut seating: Vec<(Seat, String)> = self
+            .player_to_seat
+            .iter()
+            .map(|(name, &seat)| (seat, name.clone()))
+            .collect();
+        seating.sort_by_key(|(seat, _)| seat.value());
+        seating
+    }
+
+    /// Records which (absolute) seat holds the dealer button.
+    pub fn set_button(&mut self, seat: Seat) {
+        self.button = Some(seat);
+    }
+
+    /// Returns the seat holding the dealer button, if one was set.
+    pub fn button(&self) -> Option<Seat> {
+        self.button
+    }
+
+    /// Maps an absolute seat to a button-relative one: the button is seat 1
+    /// and numbering continues clockwise (in seat order) around a table of
+    /// `total_seats`. Seats are returned unchanged until a button is set.
+    ///
+    /// This makes hands that differ only by the button position produce the
+    /// same braid.
+    pub fn to_button_relative(&self, seat: Seat, total_seats: usize) -> Seat {
+        let Some(button) = self.button else {
+            return seat;
+        };
+        if total_seats == 0 {
+            return seat;
+        }
+        let absolute = (seat.value() - 1) % total_seats;
+        let button = (button.value() - 1) % total_seats;
+        Seat::new((absolute + total_seats - button) % total_seats + 1)
+    }
+
+    /// Inverse of `to_button_relative`: maps a button-relative seat back to
+    /// the absolute seat it currently corresponds to.
+    pub fn from_button_relative(&self, seat: Seat, total_seats: usize) -> Seat {
+        let Some(button) = self.button else {
+            return seat;
+        };
+        if total_seats == 0 {
+            return seat;
+        }
+        let relative = (seat.value() - 1) % total_seats;
+        let button = (button.value() - 1) % total_seats;
+        Seat::new((relative + button) % total_seats + 1)
+    }
+
     /// Gets the Seat for a player ID, or assigns a new seat if the player is new.
     /// 
     /// # Arguments
@@ -103,7 +312,7 @@ impl SeatResolver {
         
         // Try to match by ID part (for name updates like "PlayerName_ID" -> "[S5] PlayerName_ID")
         // Extract ID part: look for pattern "name_ID" or "name_generated"
-        if let Some(id_part) = player_id.split('_').last() {
+        if let Some(id_part) = player_id.rsplit('_').next() {
             // Search for existing entries with the same ID part
             // Collect matching entries first to avoid borrowing issues
             let mut matching_entry: Option<(String, Seat)> = None;
@@ -122,19 +331,51 @@ impl SeatResolver {
             }
         }
         
-        // New player - assign new seat
-        let seat = Seat::new(self.next_seat);
+        // New player - reuse a freed seat if there is one, else assign a new seat
+        let seat = match self.free_seats.pop_first() {
+            Some(free) => Seat::new(free),
+            None => {
+                self.next_seat += 1;
+                Seat::new(self.next_seat - 1)
+            }
+        };
         self.player_to_seat.insert(player_id, seat);
-        self.next_seat += 1;
         seat
     }
 
+    /// Seats a player at a fixed seat (e.g. the table seat from a hand
+    /// history), moving them if they sat elsewhere and evicting whoever held
+    /// that seat. Seats assigned later by `get_or_assign_seat` come after it.
+    pub fn assign_seat(&mut self, player_id: &str, seat: Seat) {
+        let player_id = player_id.trim().to_string();
+        if let Some(previous) = self.player_to_seat.remove(&player_id) {
+            if previous != seat {
+                self.free_seats.insert(previous.value());
+            }
+        }
+        self.player_to_seat.retain(|_, held| *held != seat);
+        self.free_seats.remove(&seat.value());
+        self.next_seat = self.next_seat.max(seat.value() + 1);
+        self.player_to_seat.insert(player_id, seat);
+    }
+
+    /// Frees the seat of a player who left the table, so the next new player
+    /// takes it instead of growing the table (and forcing modulo wrap-around).
+    ///
+    /// # Returns
+    /// The released seat, or `None` if the player wasn't seated
+    pub fn release_player(&mut self, player_id: &str) -> Option<Seat> {
+        let seat = self.player_to_seat.remove(player_id.trim())?;
+        self.free_seats.insert(seat.value());
+        Some(seat)
+    }
+
     /// Returns the total number of unique players seen so far.
     pub fn player_count(&self) -> usize {
         self.player_to_seat.len()
     }
 
-    /// Returns the maximum seat number assigned.
+    /// Returns the maximum seat number assigned (released seats included).
     pub fn max_seat(&self) -> usize {
         self.next_seat - 1
     }
@@ -201,6 +442,120 @@ mod tests {
         assert_eq!(resolver.max_seat(), 2);
     }
 
+    #[test]
+    fn test_released_seat_is_reused() {
+        let mut resolver = SeatResolver::new();
+        resolver.get_or_assign_seat("Alice");
+        resolver.get_or_assign_seat("Bob");
+        resolver.get_or_assign_seat("Carol");
+
+        assert_eq!(resolver.release_player("Bob"), Some(Seat::new(2)));
+        assert_eq!(resolver.release_player("Bob"), None);
+        assert_eq!(resolver.player_count(), 2);
+
+        assert_eq!(resolver.get_or_assign_seat("Dave"), Seat::new(2));
+        assert_eq!(resolver.get_or_assign_seat("Erin"), Seat::new(4));
+        assert_eq!(resolver.max_seat(), 4);
+        assert_eq!(resolver.get_player_name(Seat::new(2)), "Dave");
+    }
+
+    #[test]
+    fn test_assigned_seats_are_kept() {
+        let mut resolver = SeatResolver::new();
+        resolver.assign_seat("Alice", Seat::new(3));
+        resolver.assign_seat("Bob", Seat::new(5));
+        assert_eq!(resolver.get_or_assign_seat("Alice"), Seat::new(3));
+        assert_eq!(resolver.get_or_assign_seat("Carol"), Seat::new(6));
+
+        // Next hand: Alice moves to 5, Bob's old seat
+        resolver.assign_seat("Alice", Seat::new(5));
+        assert_eq!(resolver.get_player_name(Seat::new(5)), "Alice");
+        assert_eq!(resolver.player_count(), 2);
+        assert_eq!(
+            resolver.get_or_assign_seat("Dave"),
+            Seat::new(3),
+            "Alice's old seat is free again"
+        );
+    }
+
+    #[test]
+    fn test_resolver_round_trips_through_json() {
+        let mut resolver = SeatResolver::new();
+        for player in ["Alice_p1", "Bob_p2", "Carol_p3", "Dave_p4"] {
+            resolver.get_or_assign_seat(player);
+        }
+        resolver.release_player("Bob_p2");
+        resolver.set_button(Seat::new(3));
+
+        let json = serde_json::to_string(&resolver).unwrap();
+        let mut restored: SeatResolver = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, resolver);
+        assert_eq!(restored.get_or_assign_seat("Carol_p3"), Seat::new(3));
+        assert_eq!(
+            restored.get_or_assign_seat("Erin_p5"),
+            Seat::new(2),
+            "Freed seats survive too"
+        );
+        assert_eq!(restored.button(), Some(Seat::new(3)));
+    }
+
+    #[test]
+    fn test_seating_follows_renames() {
+        let mut resolver = SeatResolver::new();
+        resolver.get_or_assign_seat("Bob_b");
+        resolver.get_or_assign_seat("Alice_a");
+        resolver.get_or_assign_seat("[S5] Bob_b");
+        assert_eq!(
+            resolver.seating(),
+            vec![
+                (Seat::new(1), "[S5] Bob_b".to_string()),
+                (Seat::new(2), "Alice_a".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_button_relative_seats() {
+        let mut resolver = SeatResolver::new();
+        assert_eq!(resolver.to_button_relative(Seat::new(4), 6), Seat::new(4));
+
+        resolver.set_button(Seat::new(4));
+        let relative: Vec<usize> = (1..=6)
+            .map(|seat| resolver.to_button_relative(Seat::new(seat), 6).value())
+            .collect();
+        assert_eq!(relative, [4, 5, 6, 1, 2, 3]);
+        for seat in 1..=6 {
+            let relative = resolver.to_button_relative(Seat::new(seat), 6);
+            assert_eq!(resolver.from_button_relative(relative, 6), Seat::new(seat));
+        }
+    }
+
+    #[test]
+    fn test_same_action_under_rotated_button_gives_same_braid() {
+        use braid_engine::{expand_action, BraidWord};
+
+        // The same betting round (button+1, button+2, button+3, button) seen
+        // with the button on seat 2 and on seat 5 of a 6-seat table
+        let relative_word = |button: usize| {
+            let mut resolver = SeatResolver::new();
+            resolver.set_button(Seat::new(button));
+            let seats: Vec<Seat> = [1, 2, 3, 0]
+                .iter()
+                .map(|offset| Seat::new((button - 1 + offset) % 6 + 1))
+                .map(|seat| resolver.to_button_relative(seat, 6))
+                .collect();
+            let mut word = BraidWord::new();
+            for pair in seats.windows(2) {
+                for gen in expand_action(pair[0], pair[1], 6) {
+                    word.push(gen);
+                }
+            }
+            word
+        };
+        assert_eq!(relative_word(2), relative_word(5));
+        assert!(!relative_word(2).is_empty());
+    }
+
     #[test]
     fn test_parse_record() {
         let mut resolver = SeatResolver::new();
@@ -214,4 +569,116 @@ mod tests {
         assert_eq!(action.action_type, ActionType::Raise);
         assert_eq!(action.amount, 100);
     }
+
+    #[test]
+    fn test_parse_record_reset_sentinel() {
+        let mut resolver = SeatResolver::new();
+        let record = |fields: &[&str]| StringRecord::from(fields.to_vec());
+
+        let action = parse_record(&record(&["system", "reset", "0"]), &mut resolver).unwrap();
+        assert_eq!(action.action_type, ActionType::Reset);
+        assert_eq!(resolver.player_count(), 0, "Reset rows don't take a seat");
+
+        let action = parse_record(&record(&["RESET", "", ""]), &mut resolver).unwrap();
+        assert_eq!(action.action_type, ActionType::Reset);
+
+        let action = parse_record_with_sentinel(
+            &record(&["dealer", "new_hand", "0"]),
+            &mut resolver,
+            "new_hand",
+        )
+        .unwrap();
+        assert_eq!(action.action_type, ActionType::Reset);
+        assert!(parse_record_with_sentinel(
+            &record(&["Alice", "reset", "0"]),
+            &mut resolver,
+            "new_hand"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_record_with_numeric_seat() {
+        let record = |fields: &[&str]| StringRecord::from(fields.to_vec());
+
+        let action =
+            parse_record_with_numeric_seat(&record(&["3", "raise", "100"]), DEFAULT_RESET_SENTINEL)
+                .unwrap();
+        assert_eq!(action.seat, Seat::new(3));
+        assert_eq!(action.action_type, ActionType::Raise);
+
+        let action =
+            parse_record_with_numeric_seat(&record(&["reset", "", ""]), DEFAULT_RESET_SENTINEL)
+                .unwrap();
+        assert_eq!(action.action_type, ActionType::Reset);
+
+        for bad in ["0", "Alice", "-1"] {
+            assert_eq!(
+                parse_record_with_numeric_seat(
+                    &record(&[bad, "call", "10"]),
+                    DEFAULT_RESET_SENTINEL
+                ),
+                Err(ParseError::InvalidSeat(bad.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_record_negative_amount() {
+        let mut resolver = SeatResolver::new();
+        let record = StringRecord::from(vec!["Alice", "bet", "-50"]);
+
+        let action = parse_record(&record, &mut resolver).unwrap();
+        assert_eq!(action.action_type, ActionType::Bet);
+        assert_eq!(action.amount, -50);
+    }
+
+    #[test]
+    fn test_parse_amount_formats() {
+        assert_eq!(parse_amount("$1,200"), Ok(1200));
+        assert_eq!(parse_amount("1 200"), Ok(1200));
+        assert_eq!(parse_amount("1200.50"), Ok(1200));
+        assert_eq!(parse_amount("$1,200.50"), Ok(1200));
+        assert_eq!(parse_amount("-50"), Ok(-50));
+        assert!(parse_amount("lots").is_err());
+        assert!(parse_amount("12.x").is_err());
+        assert!(parse_amount("").is_err());
+
+        let mut resolver = SeatResolver::new();
+        let action = parse_record(
+            &StringRecord::from(vec!["Alice", "bet", "$1,200"]),
+            &mut resolver,
+        )
+        .unwrap();
+        assert_eq!(action.amount, 1200);
+    }
+
+    #[test]
+    fn test_parse_record_errors() {
+        let mut resolver = SeatResolver::new();
+        let record = |fields: &[&str]| StringRecord::from(fields.to_vec());
+
+        assert_eq!(
+            parse_record(&record(&["Alice", "raise"]), &mut resolver).unwrap_err(),
+            ParseError::MissingField {
+                index: 2,
+                name: "amount"
+            }
+        );
+        assert_eq!(
+            parse_record(&record(&["Alice", "limp", "100"]), &mut resolver).unwrap_err(),
+            ParseError::UnknownAction("limp".to_string())
+        );
+
+        let err = parse_record(&record(&["Alice", "raise", "lots"]), &mut resolver).unwrap_err();
+        match &err {
+            ParseError::InvalidAmount { raw, .. } => assert_eq!(raw, "lots"),
+            other => panic!("Expected InvalidAmount, got {:?}", other),
+        }
+        assert!(err.source().is_some());
+        assert_eq!(
+            err.to_string(),
+            "Invalid amount 'lots': invalid digit found in string"
+        );
+    }
 }
@@ -1,11 +1,17 @@
+use crate::card::Card;
+use crate::{parse_chip_amount, ParseError};
 use braid_engine::ActionType;
 use lazy_static::lazy_static;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// PokerNow CSV row structure.
 /// 
 /// PokerNow logs have columns: "entry", "at", "order"
+///
+/// Columns are matched by header name, and any extra columns added by newer
+/// exports are ignored (this struct must never use `deny_unknown_fields`).
 #[derive(Debug, Deserialize)]
 pub struct PokerNowRow {
     /// The log entry text (e.g., "Alice @ p1 raises to 200")
@@ -21,21 +27,58 @@ pub struct PokerNowRow {
 
 // Master regex for parsing PokerNow log entries.
 // Pattern supports:
-// - Optional timestamp: "23:18 "
+// - Optional timestamp: "23:18 " or "23:18:05 "
 // - Hand reset delimiter: "-- starting hand"
+// - Street boundaries: "Flop:", "Turn:", "River:" (also "Flop (second run):")
 // - Optional player ID: "@ p1" (can be missing in live DOM)
 // - Action keywords: folds, checks, calls, bets, raises, posts, etc.
-// - Optional amount: "90" or "90.5"
+// - Pot/stack events (collected, returned, gained, wins) are matched explicitly
+//   so their lines can't fall through to a betting keyword further along
+// - Blind phrasing after "posts": "a big blind of", "a missed big blind of", "a missing small blind of"
+// - Optional amount: "90", "90.5", "$1,200" or "1 200", after "to" when it's a
+//   total ("raises to 200")
 // Matches lines like:
 // - "-- starting hand #5 --" (hand delimiter)
 // - "Alice @ p1 folds" (CSV format)
 // - "23:18 le_chiffre calls 90" (Live DOM format)
 // - "Bob @ p2 calls 50" (CSV format)
 // - "Charlie raises to 200" (Live DOM format without ID)
+// - "Alice @ p1 posts a missed big blind of 20" (returning player)
 lazy_static! {
     static ref POKERNOW_REGEX: Regex = Regex::new(
-        r"^(?:(?P<time>\d{1,2}:\d{2})\s+)?(?:(?P<reset>-- starting hand)|(?P<name>.+?)(?: @ (?P<id>.+?))? (?P<action>folds|checks|calls|bets|raises|shows|quits|joins|posts))(?: to | )?(?P<amount>[\d\.]+)?"
+        r"^(?:(?P<time>\d{1,2}:\d{2}(?::\d{2})?)\s+)?(?:(?P<reset>-- starting hand)|(?P<street>(?:Flop|Turn|River)(?: \(second run\))?:)|(?P<name>.+?)(?: @ (?P<id>.+?))? (?P<action>folds|checks|calls|bets|raises|shows|quits|joins|posts|collected|returned|gained|wins))(?:(?P<to> to )| an? (?:missed |missing )?(?:small |big )?blind of | )?(?P<amount>\$?\d(?:[\d,\.]|\s\d)*)?"
     ).expect("Invalid PokerNow regex pattern");
+
+    // A player leaving or returning to their seat, e.g.
+    // "The player "Alice @ p1" stand up." or "Alice @ p1 sits back down".
+    // Anchored at the end, so an entry merely mentioning "stands up" isn't one
+    static ref PRESENCE_REGEX: Regex = Regex::new(
+        r#"^(?:\d{1,2}:\d{2}(?::\d{2})?\s+)?(?:The player )?"?(?P<name>.+?)(?: @ (?P<id>[^"]+?))?"? (?P<presence>stands? up|sits? back(?: down)?(?: with the stack of \$?[\d,\.]+)?)\.?$"#
+    ).expect("Invalid PokerNow presence regex pattern");
+
+    // The dealer named in a hand header, e.g.
+    // "-- starting hand #5 (id: abc) (No Limit Texas Hold'em) (dealer: "Alice @ p1") --"
+    static ref DEALER_REGEX: Regex = Regex::new(
+        r#"\(dealer: "(?P<name>.+?)(?: @ (?P<id>[^"]+?))?"\)"#
+    ).expect("Invalid PokerNow dealer regex pattern");
+
+    // The new cards of a street line (the last bracketed list), e.g.
+    // "Flop:  [A♥, K♦, 2♣]" or "Turn: A♥, K♦, 2♣ [9♠]"
+    static ref BOARD_REGEX: Regex = Regex::new(
+        r"^(?:\d{1,2}:\d{2}(?::\d{2})?\s+)?(?:Flop|Turn|River)(?: \(second run\))?:.*\[(?P<cards>[^\[\]]*)\]\s*$"
+    ).expect("Invalid PokerNow board regex pattern");
+}
+
+/// An action extracted from a PokerNow row by `parse_row`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokerNowAction {
+    /// Unique player ID ("name_id"), or "system_reset" for hand delimiters
+    pub player_id: String,
+    pub action_type: ActionType,
+    pub amount: u64,
+    /// True if the amount followed "to" ("raises to 200": the total bet is
+    /// 200), false if it was logged bare ("raises 200": possibly the increment)
+    pub amount_is_total: bool,
 }
 
 /// Parses a PokerNow row and extracts action information.
@@ -44,41 +87,69 @@ lazy_static! {
 /// * `row` - The PokerNowRow to parse
 /// 
 /// # Returns
-/// `Some((player_id, action_type, amount))` if the row contains a valid action,
-/// `None` if the row should be filtered out (e.g., system messages, chat, etc.)
+/// `Ok(Some(PokerNowAction))` if the row contains a valid action,
+/// `Ok(None)` if the row should be filtered out (e.g., system messages, chat,
+/// etc.) and `Err(ParseError::InvalidAmount)` if the action's amount is
+/// malformed.
 /// 
 /// # Player ID Generation
 /// Combines name and ID (e.g., "Alice_p1") to ensure uniqueness if people share names.
-/// For Reset actions, player_id is "system_reset".
-pub fn parse_row(row: &PokerNowRow) -> Option<(String, ActionType, u64)> {
+/// For Reset actions, player_id is "system_reset"; for StreetBoundary markers
+/// it is "system_street".
+///
+/// "stands up" / "sits back" lines become StandUp / SitBack for the player.
+pub fn parse_row(row: &PokerNowRow) -> Result<Option<PokerNowAction>, ParseError> {
+    if let Some(caps) = PRESENCE_REGEX.captures(&row.entry) {
+        let name = caps["name"].trim();
+        let id = caps.name("id").map(|m| m.as_str().trim());
+        let action_type = if caps["presence"].starts_with("stand") {
+            ActionType::StandUp
+        } else {
+            ActionType::SitBack
+        };
+        return Ok(Some(PokerNowAction {
+            player_id: player_id(name, id),
+            action_type,
+            amount: 0,
+            amount_is_total: false,
+        }));
+    }
+
     // Try to match the regex
-    let caps = POKERNOW_REGEX.captures(&row.entry)?;
+    let Some(caps) = POKERNOW_REGEX.captures(&row.entry) else {
+        return Ok(None);
+    };
     
     // Check for hand reset delimiter first
     if caps.name("reset").is_some() {
         // This is a "starting hand" line
-        return Some(("system_reset".to_string(), ActionType::Reset, 0));
+        return Ok(Some(PokerNowAction {
+            player_id: "system_reset".to_string(),
+            action_type: ActionType::Reset,
+            amount: 0,
+            amount_is_total: false,
+        }));
+    }
+
+    // Board cards mark a new street within the same hand
+    if caps.name("street").is_some() {
+        return Ok(Some(PokerNowAction {
+            player_id: "system_street".to_string(),
+            action_type: ActionType::StreetBoundary,
+            amount: 0,
+            amount_is_total: false,
+        }));
     }
     
-    // Extract name (required for non-reset actions)
-    let name = caps.name("name")?.as_str().trim();
+    // Not a reset or street, so the name and action alternative matched
+    let name = caps["name"].trim();
     
     // Extract ID (optional - may be missing in live DOM format)
     let id = caps.name("id").map(|m| m.as_str().trim());
     
-    // Generate unique player ID
-    // If ID exists: "name_id", otherwise: "name_generated"
-    let player_id = if let Some(id_str) = id {
-        if !id_str.is_empty() {
-            format!("{}_{}", name, id_str)
-        } else {
-            format!("{}_generated", name)
-        }
-    } else {
-        format!("{}_generated", name)
-    };
+    let player_id = player_id(name, id);
     
-    let action_str = caps.name("action")?.as_str().to_lowercase();
+    let action_str = caps["action"].to_lowercase();
     
     // Parse action type
     let action_type = match action_str.as_str() {
@@ -88,29 +159,20 @@ pub fn parse_row(row: &PokerNowRow) -> Option<(String, ActionType, u64)> {
         "bets" => ActionType::Bet,
         "raises" => ActionType::Raise,
         "posts" => ActionType::Bet, // Map blinds/posts to Bet
-        "shows" | "quits" | "joins" => {
+        "shows" | "quits" | "joins" | "collected" | "returned" | "gained" | "wins" => {
             // Filter out non-betting actions
-            return None;
+            return Ok(None);
         }
         _ => {
             // Unknown action type, filter out
-            return None;
+            return Ok(None);
         }
     };
     
-    // Parse amount (handles both integer and decimal formats)
+    // Parse amount ("$1,200.50" -> 1200). A malformed amount is an error
+    // rather than reading as 0.
     let amount = match caps.name("amount") {
-        Some(amt) => {
-            let amt_str = amt.as_str();
-            // Try parsing as f64 first (handles decimals), then convert to u64
-            amt_str
-                .parse::<f64>()
-                .map(|f| f as u64)
-                .unwrap_or_else(|_| {
-                    // Fallback to integer parsing
-                    amt_str.parse::<u64>().unwrap_or(0)
-                })
-        }
+        Some(amt) => parse_chip_amount(amt.as_str())?,
         None => 0,
     };
     
@@ -120,13 +182,155 @@ pub fn parse_row(row: &PokerNowRow) -> Option<(String, ActionType, u64)> {
         _ => amount,
     };
     
-    Some((player_id, action_type, final_amount))
+    Ok(Some(PokerNowAction {
+        player_id,
+        action_type,
+        amount: final_amount,
+        amount_is_total: caps.name("to").is_some(),
+    }))
+}
+
+/// Extracts the dealer's player ID from a "-- starting hand" row.
+///
+/// The ID has the same `name_id` form as the one returned by `parse_row`, so
+/// it resolves to the dealer's existing seat. Returns `None` for other rows
+/// and for headers without a dealer (e.g. older exports).
+pub fn parse_dealer(row: &PokerNowRow) -> Option<String> {
+    let caps = DEALER_REGEX.captures(&row.entry)?;
+    let name = caps.name("name")?.as_str().trim();
+    let id = caps.name("id").map(|m| m.as_str().trim());
+    Some(player_id(name, id))
+}
+
+/// Extracts the cards dealt on a Flop/Turn/River line.
+///
+/// Only the new cards are returned (the bracketed list; PokerNow repeats the
+/// earlier board outside the brackets). Cards may be separated by spaces or
+/// commas and use suit letters or symbols (`[Ah Kd 2c]`, `[A♥, K♦, 2♣]`).
+/// Returns `None` for other lines and if any card in the brackets is malformed.
+pub fn parse_board(entry: &str) -> Option<Vec<Card>> {
+    let caps = BOARD_REGEX.captures(entry.trim())?;
+    let cards = caps
+        .name("cards")?
+        .as_str()
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|card| !card.is_empty())
+        .map(|card| card.parse().ok())
+        .collect::<Option<Vec<Card>>>()?;
+    if cards.is_empty() {
+        return None;
+    }
+    Some(cards)
+}
+
+/// Suffix of the player IDs `parse_row` generates for names logged without
+/// an `@ id` (live DOM mode).
+const GENERATED_SUFFIX: &str = "_generated";
+
+/// Tells apart players who share a display name and have no ID, which would
+/// otherwise collapse onto one `name_generated` seat (`--disambiguate-names`).
+///
+/// This is a heuristic: nobody acts twice in a row within a betting round, so
+/// an ID-less name acting right after itself must be a second player and gets
+/// its own ID (`Player_generated2`, `Player_generated3`, ...). Later actions
+/// by that name go to whichever of its players acted least recently and
+/// wasn't the last to act. Only betting actions are disambiguated; hand
+/// headers and street markers start a new round. Players whose names come
+/// with an ID are never touched.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NameDisambiguator {
+    /// Generated ID -> the IDs handed out for that name, first one unchanged
+    variants: HashMap<String, Vec<String>>,
+    /// ID -> tick of its most recent action
+    last_acted: HashMap<String, u64>,
+    tick: u64,
+    /// ID of the last player to act in the current betting round
+    last_actor: Option<String>,
+}
+
+impl NameDisambiguator {
+    /// Creates a disambiguator that has seen no players yet.
+    pub fn new() -> Self {
+        NameDisambiguator::default()
+    }
+
+    /// Rewrites the player ID of `action` if it belongs to another player
+    /// with the same ID-less name.
+    pub fn disambiguate(&mut self, action: &mut PokerNowAction) {
+        match action.action_type {
+            ActionType::Reset | ActionType::StreetBoundary => {
+                self.last_actor = None;
+                return;
+            }
+            ActionType::StandUp | ActionType::SitBack => return,
+            _ => {}
+        }
+
+        if action.player_id.ends_with(GENERATED_SUFFIX) {
+            let variants = self
+                .variants
+                .entry(action.player_id.clone())
+                .or_insert_with(|| vec![action.player_id.clone()]);
+            let chosen = variants
+                .iter()
+                .filter(|variant| self.last_actor.as_ref() != Some(*variant))
+                .min_by_key(|variant| self.last_acted.get(*variant).copied().unwrap_or(0))
+                .cloned();
+            action.player_id = chosen.unwrap_or_else(|| {
+                let variant = format!("{}{}", action.player_id, variants.len() + 1);
+                variants.push(variant.clone());
+                variant
+            });
+        }
+
+        self.tick += 1;
+        self.last_acted.insert(action.player_id.clone(), self.tick);
+        self.last_actor = Some(action.player_id.clone());
+    }
+}
+
+/// Generates a unique player ID.
+/// If ID exists: "name_id", otherwise: "name_generated"
+fn player_id(name: &str, id: Option<&str>) -> String {
+    match id {
+        Some(id_str) if !id_str.is_empty() => format!("{}_{}", name, id_str),
+        _ => format!("{}{}", name, GENERATED_SUFFIX),
+    }
+}
+
+/// Recovers the display name from a player ID built by `player_id` (or
+/// renamed by `NameDisambiguator`): "Alice_p1" and "Alice_generated2" are
+/// both "Alice". Like `SeatResolver`, takes the ID to be whatever follows
+/// the last `_`.
+pub fn display_name(player_id: &str) -> &str {
+    if let Some((name, variant)) = player_id.rsplit_once(GENERATED_SUFFIX) {
+        if variant.chars().all(|c| c.is_ascii_digit()) {
+            return name;
+        }
+    }
+    player_id
+        .rsplit_once('_')
+        .map_or(player_id, |(name, _)| name)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_deserialize_ignores_extra_columns() {
+        let data = "\"entry\",\"at\",\"order\",\"nickname\"\n\"Alice @ p1 calls 50\",2025-01-01T12:00:00,7,ally\n";
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(data.as_bytes());
+
+        let rows: Vec<PokerNowRow> = reader.deserialize().collect::<Result<_, _>>().unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].entry, "Alice @ p1 calls 50");
+        assert_eq!(rows[0].at, "2025-01-01T12:00:00");
+        assert_eq!(rows[0].order, 7);
+    }
+
     #[test]
     fn test_parse_fold() {
         let row = PokerNowRow {
@@ -135,9 +339,14 @@ mod tests {
             order: 1,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some());
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = result.unwrap();
         assert_eq!(player_id, "Alice_p1");
         assert_eq!(action_type, ActionType::Fold);
         assert_eq!(amount, 0);
@@ -151,9 +360,14 @@ mod tests {
             order: 2,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some());
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = result.unwrap();
         assert_eq!(player_id, "Bob_p2");
         assert_eq!(action_type, ActionType::Check);
         assert_eq!(amount, 0);
@@ -167,9 +381,14 @@ mod tests {
             order: 3,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some());
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = result.unwrap();
         assert_eq!(player_id, "Charlie_p3");
         assert_eq!(action_type, ActionType::Call);
         assert_eq!(amount, 50);
@@ -183,9 +402,14 @@ mod tests {
             order: 4,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some());
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = result.unwrap();
         assert_eq!(player_id, "Dave_p4");
         assert_eq!(action_type, ActionType::Bet);
         assert_eq!(amount, 100);
@@ -199,12 +423,32 @@ mod tests {
             order: 5,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some());
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            amount_is_total,
+        } = result.unwrap();
         assert_eq!(player_id, "Alice_p1");
         assert_eq!(action_type, ActionType::Raise);
         assert_eq!(amount, 200);
+        assert!(amount_is_total, "'raises to' is a total");
+    }
+
+    #[test]
+    fn test_parse_raises_by_increment() {
+        let row = PokerNowRow {
+            entry: "Alice @ p1 raises 200".to_string(),
+            at: "2025-01-01T12:00:04".to_string(),
+            order: 5,
+        };
+
+        let action = parse_row(&row).unwrap().unwrap();
+        assert_eq!(action.action_type, ActionType::Raise);
+        assert_eq!(action.amount, 200);
+        assert!(!action.amount_is_total, "A bare amount may be an increment");
     }
 
     #[test]
@@ -215,7 +459,7 @@ mod tests {
             order: 6,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_none(), "Shows action should be filtered out");
     }
 
@@ -227,10 +471,29 @@ mod tests {
             order: 7,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_none(), "System messages should be filtered out");
     }
 
+    #[test]
+    fn test_pot_events_filtered() {
+        for entry in [
+            "Alice @ p1 collected 300 from pot",
+            "Alice @ p1 collected 300 from pot with Two Pair, calls on the river (combination: Ah, Ad)",
+            "Uncalled bet of 100 returned to Bob @ p2",
+            "Uncalled bet of 100 returned to Bob",
+            "Charlie @ p3 gained 50",
+            "23:18 le_chiffre wins 420",
+        ] {
+            let row = PokerNowRow {
+                entry: entry.to_string(),
+                at: "2025-01-01T12:00:00".to_string(),
+                order: 1,
+            };
+            assert!(parse_row(&row).unwrap().is_none(), "'{}' should be filtered out", entry);
+        }
+    }
+
     #[test]
     fn test_player_id_uniqueness() {
         // Test that same name with different IDs gets different player_ids
@@ -246,14 +509,22 @@ mod tests {
             order: 2,
         };
         
-        let result1 = parse_row(&row1);
-        let result2 = parse_row(&row2);
+        let result1 = parse_row(&row1).unwrap();
+        let result2 = parse_row(&row2).unwrap();
         
         assert!(result1.is_some());
         assert!(result2.is_some());
         
-        assert_eq!(result1.unwrap().0, "Alice_p1");
-        assert_eq!(result2.unwrap().0, "Alice_p2");
+        assert_eq!(result1.unwrap().player_id, "Alice_p1");
+        assert_eq!(result2.unwrap().player_id, "Alice_p2");
+    }
+
+    #[test]
+    fn test_display_name_drops_the_id() {
+        assert_eq!(display_name("Alice_p1"), "Alice");
+        assert_eq!(display_name("le_chiffre_generated"), "le_chiffre");
+        assert_eq!(display_name("Bob_generated2"), "Bob");
+        assert_eq!(display_name("[S5] Carol_p3"), "[S5] Carol");
     }
 
     #[test]
@@ -265,14 +536,46 @@ mod tests {
             order: 1,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some(), "Should parse live DOM format with timestamp");
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction { player_id, action_type, amount, .. } = result.unwrap();
         assert_eq!(player_id, "le_chiffre_generated", "Should generate ID when missing");
         assert_eq!(action_type, ActionType::Call);
         assert_eq!(amount, 90);
     }
 
+    #[test]
+    fn test_parse_live_dom_format_with_seconds() {
+        // Some DOM variants include seconds: "23:18:05 le_chiffre calls 90"
+        let row = PokerNowRow {
+            entry: "23:18:05 le_chiffre calls 90".to_string(),
+            at: "2025-01-01T12:00:00".to_string(),
+            order: 1,
+        };
+
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = parse_row(&row)
+            .unwrap()
+            .expect("Should parse HH:MM:SS timestamp");
+        assert_eq!(player_id, "le_chiffre_generated");
+        assert_eq!(action_type, ActionType::Call);
+        assert_eq!(amount, 90);
+
+        let reset = PokerNowRow {
+            entry: "23:18:05 -- starting hand #3 --".to_string(),
+            at: String::new(),
+            order: 2,
+        };
+        assert_eq!(
+            parse_row(&reset).unwrap().unwrap().action_type,
+            ActionType::Reset
+        );
+    }
+
     #[test]
     fn test_parse_live_dom_format_without_timestamp() {
         // Test live DOM format without timestamp: "le_chiffre calls 90"
@@ -282,9 +585,9 @@ mod tests {
             order: 1,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some(), "Should parse live DOM format without timestamp");
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction { player_id, action_type, amount, .. } = result.unwrap();
         assert_eq!(player_id, "le_chiffre_generated");
         assert_eq!(action_type, ActionType::Call);
         assert_eq!(amount, 90);
@@ -305,14 +608,169 @@ mod tests {
             order: 2,
         };
         
-        let csv_result = parse_row(&csv_row);
-        let live_result = parse_row(&live_row);
+        let csv_result = parse_row(&csv_row).unwrap();
+        let live_result = parse_row(&live_row).unwrap();
         
         assert!(csv_result.is_some());
         assert!(live_result.is_some());
         
-        assert_eq!(csv_result.unwrap().0, "Alice_p1");
-        assert_eq!(live_result.unwrap().0, "Bob_generated");
+        assert_eq!(csv_result.unwrap().player_id, "Alice_p1");
+        assert_eq!(live_result.unwrap().player_id, "Bob_generated");
+    }
+
+    #[test]
+    fn test_parse_formatted_amounts() {
+        for (entry, expected) in [
+            ("Alice @ p1 bets $1,200", Some(1200)),
+            ("Alice @ p1 bets 1 200", Some(1200)),
+            ("Alice @ p1 raises to 1200.50", Some(1200)),
+        ] {
+            let row = PokerNowRow {
+                entry: entry.to_string(),
+                at: "2025-01-01T12:00:00".to_string(),
+                order: 1,
+            };
+            assert_eq!(
+                parse_row(&row).unwrap().map(|action| action.amount),
+                expected,
+                "{}",
+                entry
+            );
+        }
+
+        // A malformed amount is an error, not a skipped row
+        let row = PokerNowRow {
+            entry: "Alice @ p1 calls 1.2.3".to_string(),
+            at: "2025-01-01T12:00:00".to_string(),
+            order: 1,
+        };
+        assert!(
+            matches!(parse_row(&row), Err(ParseError::InvalidAmount { raw, .. }) if raw == "1.2.3")
+        );
+    }
+
+    #[test]
+    fn test_parse_stand_up_and_sit_back() {
+        for (entry, player, expected) in [
+            ("Alice stands up", "Alice_generated", ActionType::StandUp),
+            (
+                "Alice sits back down",
+                "Alice_generated",
+                ActionType::SitBack,
+            ),
+            (
+                r#"The player "Bob @ p2" stand up."#,
+                "Bob_p2",
+                ActionType::StandUp,
+            ),
+            (
+                r#"The player "Bob @ p2" sit back with the stack of 1000."#,
+                "Bob_p2",
+                ActionType::SitBack,
+            ),
+        ] {
+            let row = PokerNowRow {
+                entry: entry.to_string(),
+                at: "2025-01-01T12:00:00".to_string(),
+                order: 1,
+            };
+            let action = parse_row(&row)
+                .unwrap()
+                .unwrap_or_else(|| panic!("'{}' should parse", entry));
+            assert_eq!(action.player_id, player, "{}", entry);
+            assert_eq!(action.action_type, expected, "{}", entry);
+        }
+        // Only the whole entry counts, not a mention of standing up
+        for entry in [
+            "Alice @ p1 stands up to stretch",
+            r#""Bob @ p2" sits back and relaxes"#,
+        ] {
+            let row = PokerNowRow {
+                entry: entry.to_string(),
+                at: "2025-01-01T12:00:00".to_string(),
+                order: 1,
+            };
+            assert_eq!(parse_row(&row).unwrap(), None, "{}", entry);
+        }
+    }
+
+    #[test]
+    fn test_disambiguate_same_name_without_id() {
+        let mut names = NameDisambiguator::new();
+        let mut parse = |entry: &str| {
+            let row = PokerNowRow {
+                entry: entry.to_string(),
+                at: String::new(),
+                order: 0,
+            };
+            let mut action = parse_row(&row).unwrap().unwrap();
+            names.disambiguate(&mut action);
+            action.player_id
+        };
+
+        // Two "Player"s heads-up: the second can't be the first acting again
+        assert_eq!(parse("Player bets 20"), "Player_generated");
+        assert_eq!(parse("Player raises to 60"), "Player_generated2");
+        assert_eq!(parse("Player calls 60"), "Player_generated");
+        // Players with an ID are left alone and break the chain
+        assert_eq!(parse("Bob @ p2 checks"), "Bob_p2");
+        assert_eq!(parse("Player checks"), "Player_generated2");
+
+        // A new street may start with the player who closed the last one
+        parse("Flop: [Ah Kd 2c]");
+        assert_eq!(parse("Player checks"), "Player_generated");
+    }
+
+    #[test]
+    fn test_parse_street_boundaries() {
+        for entry in [
+            "Flop:  [A♥, K♦, 2♣]",
+            "Turn: A♥, K♦, 2♣ [9♠]",
+            "River: A♥, K♦, 2♣, 9♠ [5♥]",
+            "Flop (second run): [Q♣, J♦, 3♥]",
+            "23:18 Turn: A♥, K♦, 2♣ [9♠]",
+        ] {
+            let row = PokerNowRow {
+                entry: entry.to_string(),
+                at: "2025-01-01T12:00:00".to_string(),
+                order: 1,
+            };
+            let action = parse_row(&row)
+                .unwrap()
+                .unwrap_or_else(|| panic!("'{}' should parse", entry));
+            assert_eq!(action.player_id, "system_street");
+            assert_eq!(action.action_type, ActionType::StreetBoundary, "{}", entry);
+            assert_eq!(action.amount, 0);
+        }
+    }
+
+    #[test]
+    fn test_parse_board_flop() {
+        let cards = parse_board("Flop: [Ah Kd 2c]").unwrap();
+        let shown: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+        assert_eq!(shown, ["Ah", "Kd", "2c"]);
+        assert_eq!(parse_board("Flop:  [A♥, K♦, 2♣]"), Some(cards));
+    }
+
+    #[test]
+    fn test_parse_board_turn_card() {
+        let cards = parse_board("Turn: A♥, K♦, 2♣ [10♠]").unwrap();
+        assert_eq!(
+            cards,
+            vec![Card {
+                rank: 'T',
+                suit: crate::card::Suit::Spades
+            }]
+        );
+        assert_eq!(parse_board("River: [5h]").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_parse_board_malformed() {
+        assert_eq!(parse_board("Flop: [Ah Kx 2c]"), None);
+        assert_eq!(parse_board("Flop: []"), None);
+        assert_eq!(parse_board("Flop: [Ah Kd 2c"), None);
+        assert_eq!(parse_board("Alice @ p1 shows a [Ah Kd]"), None);
     }
 
     #[test]
@@ -324,14 +782,40 @@ mod tests {
             order: 1,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some(), "Should parse hand reset delimiter");
-        let (player_id, action_type, amount) = result.unwrap();
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = result.unwrap();
         assert_eq!(player_id, "system_reset");
         assert_eq!(action_type, ActionType::Reset);
         assert_eq!(amount, 0);
     }
 
+    #[test]
+    fn test_parse_dealer() {
+        let header = PokerNowRow {
+            entry: r#"-- starting hand #5 (id: xk2) (No Limit Texas Hold'em) (dealer: "Alice @ p1") --"#.to_string(),
+            at: "2025-01-01T12:00:00".to_string(),
+            order: 1,
+        };
+        assert_eq!(parse_dealer(&header).as_deref(), Some("Alice_p1"));
+        assert_eq!(
+            parse_row(&header).unwrap().unwrap().action_type,
+            ActionType::Reset
+        );
+
+        let plain = PokerNowRow {
+            entry: "-- starting hand #5 --".to_string(),
+            at: String::new(),
+            order: 2,
+        };
+        assert_eq!(parse_dealer(&plain), None);
+    }
+
     #[test]
     fn test_parse_hand_reset_with_timestamp() {
         // Test hand reset with timestamp
@@ -341,9 +825,13 @@ mod tests {
             order: 1,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some(), "Should parse hand reset with timestamp");
-        let (player_id, action_type, _) = result.unwrap();
+        let PokerNowAction {
+            player_id,
+            action_type,
+            ..
+        } = result.unwrap();
         assert_eq!(player_id, "system_reset");
         assert_eq!(action_type, ActionType::Reset);
     }
@@ -357,9 +845,53 @@ mod tests {
             order: 1,
         };
         
-        let result = parse_row(&row);
+        let result = parse_row(&row).unwrap();
         assert!(result.is_some(), "Should parse posts action");
-        let (_, action_type, amount) = result.unwrap();
+        let PokerNowAction {
+            action_type,
+            amount,
+            ..
+        } = result.unwrap();
+        assert_eq!(action_type, ActionType::Bet);
+        assert_eq!(amount, 10);
+    }
+
+    #[test]
+    fn test_parse_posts_missed_big_blind() {
+        let row = PokerNowRow {
+            entry: "Alice @ p1 posts a missed big blind of 20".to_string(),
+            at: "2025-01-01T12:00:00".to_string(),
+            order: 1,
+        };
+
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = parse_row(&row).unwrap().expect("Should parse missed blind");
+        assert_eq!(player_id, "Alice_p1");
+        assert_eq!(action_type, ActionType::Bet);
+        assert_eq!(amount, 20);
+    }
+
+    #[test]
+    fn test_parse_posts_missing_small_blind() {
+        let row = PokerNowRow {
+            entry: "Bob @ p2 posts a missing small blind of 10".to_string(),
+            at: "2025-01-01T12:00:00".to_string(),
+            order: 1,
+        };
+
+        let PokerNowAction {
+            player_id,
+            action_type,
+            amount,
+            ..
+        } = parse_row(&row)
+            .unwrap()
+            .expect("Should parse missing blind");
+        assert_eq!(player_id, "Bob_p2");
         assert_eq!(action_type, ActionType::Bet);
         assert_eq!(amount, 10);
     }
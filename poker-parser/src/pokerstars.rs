@@ -0,0 +1,299 @@
+use crate::{parse_chip_amount, ParseError};
+use braid_engine::ActionType;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// Regexes for PokerStars `.txt` hand histories.
+// Matches lines like:
+// - "PokerStars Hand #245118309822: ..." (hand delimiter; streets are ignored)
+// - "Player1: raises 200 to 400"
+// - "Player2: calls 200"
+// - "Player3: folds"
+// - "Player1: posts big blind 20"
+// - "Player2: bets 660 and is all-in"
+// - "Seat 3: Player1 ($1000 in chips)" (seat assignment, see `parse_seat`)
+// Amounts may carry a currency symbol ("$0.50") and thousands separators;
+// amounts with a symbol are cash and are read in cents.
+lazy_static! {
+    static ref ACTION_REGEX: Regex = Regex::new(
+        r"^(?:(?P<reset>PokerStars (?:Zoom )?(?:Hand|Game) #)|(?P<name>.+?): (?P<action>folds|checks|calls|bets|raises|posts)\b(?:[^\d$€£]*(?P<currency>[$€£])?(?P<amount>[\d,\.]+))?(?: to (?P<to_currency>[$€£])?(?P<to>[\d,\.]+))?(?P<allin>.* and is all-in)?)"
+    ).expect("Invalid PokerStars action regex pattern");
+
+    static ref SEAT_REGEX: Regex = Regex::new(
+        r"^Seat (?P<seat>\d+): (?P<name>.+?) \([$€£]?[\d,\.]+ in chips"
+    ).expect("Invalid PokerStars seat regex pattern");
+}
+
+/// An action extracted from a PokerStars line by `parse_line`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PokerStarsAction {
+    /// Screen name, or "system_reset" for the hand header
+    pub player_id: String,
+    pub action_type: ActionType,
+    /// Chips, or cents for cash amounts written with a currency symbol
+    pub amount: u64,
+    /// True if the amount followed "to" ("raises 200 to 400": the total bet
+    /// is 400), as in `pokernow::PokerNowAction`
+    pub amount_is_total: bool,
+}
+
+/// Parses one line of a PokerStars hand history.
+///
+/// # Returns
+/// `Ok(Some(PokerStarsAction))` for betting actions and for the
+/// `PokerStars Hand #...` header (as a Reset by "system_reset", matching
+/// `pokernow::parse_row`), `Ok(None)` for everything else (headers, board
+/// cards, summaries, chat, ...) and `Err(ParseError::InvalidAmount)` for an
+/// action with a malformed amount.
+///
+/// Raises report the total they raise *to* (with `amount_is_total` set),
+/// blinds are mapped to Bet, and any action ending in "and is all-in"
+/// becomes AllIn. Cash amounts (`$0.50`) are read in cents so micro-stakes
+/// blinds and bets keep their value; tournament chips are read as they are.
+/// The player ID is the screen name, which PokerStars keeps unique per table.
+pub fn parse_line(line: &str) -> Result<Option<PokerStarsAction>, ParseError> {
+    let Some(caps) = ACTION_REGEX.captures(line.trim()) else {
+        return Ok(None);
+    };
+
+    if caps.name("reset").is_some() {
+        return Ok(Some(PokerStarsAction {
+            player_id: "system_reset".to_string(),
+            action_type: ActionType::Reset,
+            amount: 0,
+            amount_is_total: false,
+        }));
+    }
+
+    let name = caps["name"].trim();
+    let action_type = if caps.name("allin").is_some() {
+        ActionType::AllIn
+    } else {
+        match &caps["action"] {
+            "folds" => ActionType::Fold,
+            "checks" => ActionType::Check,
+            "calls" => ActionType::Call,
+            "bets" | "posts" => ActionType::Bet,
+            "raises" => ActionType::Raise,
+            _ => return Ok(None),
+        }
+    };
+
+    let (amount, currency) = match caps.name("to") {
+        Some(to) => (Some(to), caps.name("to_currency")),
+        None => (caps.name("amount"), caps.name("currency")),
+    };
+    let amount = match (action_type, amount) {
+        (ActionType::Fold | ActionType::Check, _) | (_, None) => 0,
+        (_, Some(amt)) if currency.is_some() => parse_cents(amt.as_str())?,
+        (_, Some(amt)) => parse_chip_amount(amt.as_str())?,
+    };
+
+    Ok(Some(PokerStarsAction {
+        player_id: name.to_string(),
+        action_type,
+        amount,
+        amount_is_total: caps.name("to").is_some(),
+    }))
+}
+
+/// Parses a cash amount ("0.50", "1,200") in cents. Sub-cent digits, which
+/// PokerStars never writes, are truncated.
+fn parse_cents(raw: &str) -> Result<u64, ParseError> {
+    let (whole, fraction) = raw.split_once('.').unwrap_or((raw, ""));
+    let (cents, rest) = fraction.split_at(fraction.len().min(2));
+    let shifted = if rest.is_empty() {
+        format!("{}{:0<2}", whole, cents)
+    } else {
+        format!("{}{:0<2}.{}", whole, cents, rest)
+    };
+    parse_chip_amount(&shifted).map_err(|err| match err {
+        ParseError::InvalidAmount { source, .. } => ParseError::InvalidAmount {
+            raw: raw.to_string(),
+            source,
+        },
+        other => other,
+    })
+}
+
+/// Parses a seat-assignment line such as `Seat 3: Player1 ($1000 in chips)`.
+///
+/// # Returns
+/// `Some((table_seat, player_id))`, or `None` for any other line (including
+/// the `Seat N: ...` lines of the summary section).
+pub fn parse_seat(line: &str) -> Option<(usize, String)> {
+    let caps = SEAT_REGEX.captures(line.trim())?;
+    let seat = caps.name("seat")?.as_str().parse().ok()?;
+    Some((seat, caps.name("name")?.as_str().trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A parsed action with a bare (not "to") amount.
+    fn action(player_id: &str, action_type: ActionType, amount: u64) -> Option<PokerStarsAction> {
+        Some(PokerStarsAction {
+            player_id: player_id.to_string(),
+            action_type,
+            amount,
+            amount_is_total: false,
+        })
+    }
+
+    #[test]
+    fn test_parse_raise_uses_total() {
+        let PokerStarsAction {
+            player_id,
+            action_type,
+            amount,
+            amount_is_total,
+        } = parse_line("Player1: raises 200 to 400").unwrap().unwrap();
+        assert_eq!(player_id, "Player1");
+        assert_eq!(action_type, ActionType::Raise);
+        assert_eq!(amount, 400);
+        assert!(amount_is_total, "'raises ... to' is a total");
+    }
+
+    #[test]
+    fn test_parse_basic_actions() {
+        assert_eq!(
+            parse_line("Player2: calls 200").unwrap(),
+            action("Player2", ActionType::Call, 200)
+        );
+        assert_eq!(
+            parse_line("Player3: folds").unwrap(),
+            action("Player3", ActionType::Fold, 0)
+        );
+        assert_eq!(
+            parse_line("Player 4: checks").unwrap(),
+            action("Player 4", ActionType::Check, 0)
+        );
+        assert_eq!(
+            parse_line("Player1: bets 1,250").unwrap(),
+            action("Player1", ActionType::Bet, 1250)
+        );
+        assert_eq!(
+            parse_line("Player2: calls 300 and is all-in").unwrap(),
+            action("Player2", ActionType::AllIn, 300)
+        );
+    }
+
+    #[test]
+    fn test_cash_amounts_are_cents() {
+        assert_eq!(
+            parse_line("Player3: posts big blind $0.50").unwrap(),
+            action("Player3", ActionType::Bet, 50)
+        );
+        assert_eq!(
+            parse_line("Player2: calls $0.02").unwrap(),
+            action("Player2", ActionType::Call, 2)
+        );
+        assert_eq!(
+            parse_line("Player1: bets €1,200.5").unwrap(),
+            action("Player1", ActionType::Bet, 120050)
+        );
+        let raise = parse_line("Player1: raises $0.04 to $0.06")
+            .unwrap()
+            .unwrap();
+        assert_eq!((raise.amount, raise.amount_is_total), (6, true));
+        assert!(matches!(
+            parse_line("Player2: calls $1.2.3").unwrap_err(),
+            ParseError::InvalidAmount { ref raw, .. } if raw == "1.2.3"
+        ));
+    }
+
+    #[test]
+    fn test_malformed_amount_is_an_error() {
+        let err = parse_line("Player2: calls 1.2.3").unwrap_err();
+        assert!(
+            matches!(err, ParseError::InvalidAmount { ref raw, .. } if raw == "1.2.3"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_hand_header_is_reset() {
+        let reset = action("system_reset", ActionType::Reset, 0);
+        assert_eq!(
+            parse_line(
+                "PokerStars Hand #245118309822: Hold'em No Limit (10/20) - 2023/05/14 21:04:11 ET"
+            )
+            .unwrap(),
+            reset
+        );
+        assert_eq!(
+            parse_line("PokerStars Zoom Hand #1: Hold'em No Limit ($0.01/$0.02)").unwrap(),
+            reset
+        );
+        // Blinds are posted before the hole cards, so those can't start the hand
+        assert_eq!(parse_line("*** HOLE CARDS ***").unwrap(), None);
+        assert_eq!(parse_line("*** FLOP *** [7h 8c Kd]").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_seat() {
+        assert_eq!(
+            parse_seat("Seat 3: Player1 ($1000 in chips)"),
+            Some((3, "Player1".to_string()))
+        );
+        assert_eq!(
+            parse_seat("Seat 5: Player3 (820 in chips) is sitting out"),
+            Some((5, "Player3".to_string()))
+        );
+        assert_eq!(
+            parse_seat("Seat 1: Player1 (button) folded on the Turn"),
+            None
+        );
+        assert_eq!(
+            parse_line("Seat 3: Player1 ($1000 in chips)").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_non_actions_filtered() {
+        for line in [
+            "Table 'Aludra II' 6-max Seat #1 is the button",
+            "Uncalled bet (660) returned to Player2",
+            "Player2 collected 720 from pot",
+            "Player2: doesn't show hand",
+            "Seat 3: Player2 (small blind) collected (720)",
+        ] {
+            assert_eq!(parse_line(line).unwrap(), None, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_parse_sample_hand() {
+        let actions: Vec<PokerStarsAction> = include_str!("../../pokerstars_sample.txt")
+            .lines()
+            .filter_map(|line| parse_line(line).unwrap())
+            .collect();
+        let summary: Vec<(&str, ActionType)> = actions
+            .iter()
+            .map(|action| (action.player_id.as_str(), action.action_type))
+            .collect();
+
+        assert_eq!(
+            summary,
+            [
+                ("system_reset", ActionType::Reset),
+                ("Player2", ActionType::Bet),
+                ("Player3", ActionType::Bet),
+                ("Player1", ActionType::Raise),
+                ("Player2", ActionType::Call),
+                ("Player3", ActionType::Fold),
+                ("Player2", ActionType::Check),
+                ("Player1", ActionType::Bet),
+                ("Player2", ActionType::Raise),
+                ("Player1", ActionType::Call),
+                ("Player2", ActionType::AllIn),
+                ("Player1", ActionType::Fold),
+            ]
+        );
+        assert_eq!(actions[8].amount, 280);
+        assert_eq!(actions[10].amount, 660);
+    }
+}
@@ -0,0 +1,376 @@
+use crate::pokernow::{self, NameDisambiguator, PokerNowRow};
+use crate::pokerstars;
+use crate::{
+    parse_record_with_numeric_seat, parse_record_with_sentinel, ParseError, SeatResolver,
+    DEFAULT_RESET_SENTINEL,
+};
+use braid_engine::{Action, ActionType, Seat};
+use csv::{ReaderBuilder, StringRecord, StringRecordsIntoIter};
+use std::io::{BufRead, BufReader, Lines, Read};
+
+/// Layout of the log being streamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// `player_id,action,amount` records
+    #[default]
+    Generic,
+    /// PokerNow `entry,at,order` exports
+    PokerNow,
+    /// PokerStars `.txt` hand histories (plain text, not CSV)
+    PokerStars,
+}
+
+/// Options shared by every format.
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// Renumber seats from the dealer named in PokerNow hand headers
+    pub button_relative: bool,
+    /// Table size used for button-relative numbering
+    pub total_seats: usize,
+    /// Generic format: player_id or action value marking a hand boundary
+    pub reset_sentinel: String,
+    /// Generic format: the first column is the seat number, not a player_id
+    pub seats_are_numeric: bool,
+    /// PokerNow: tell apart ID-less players sharing a name (see `NameDisambiguator`)
+    pub disambiguate_names: bool,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        StreamOptions {
+            button_relative: false,
+            total_seats: 0,
+            reset_sentinel: DEFAULT_RESET_SENTINEL.to_string(),
+            seats_are_numeric: false,
+            disambiguate_names: false,
+        }
+    }
+}
+
+/// Iterator over the actions in a log, created by [`parse_stream`].
+///
+/// The stream owns the `SeatResolver` used to assign seats; borrow it with
+/// `resolver()` (e.g. to look up names) once iteration is done.
+pub struct ActionStream<R: Read> {
+    source: Source<R>,
+    pending_error: Option<ParseError>,
+    format: Format,
+    options: StreamOptions,
+    resolver: SeatResolver,
+    names: NameDisambiguator,
+}
+
+/// Where an `ActionStream` reads from: CSV records or raw text lines.
+enum Source<R: Read> {
+    Csv {
+        records: StringRecordsIntoIter<R>,
+        headers: Option<StringRecord>,
+    },
+    Lines(Lines<BufReader<R>>),
+}
+
+/// Parses actions from any reader (a file, stdin or an in-memory buffer).
+///
+/// The input must be UTF-8: CSV with a header row, or plain text for
+/// PokerStars. PokerNow rows and PokerStars lines that are not actions (chat,
+/// system messages, ...) are skipped; every other problem is
+/// yielded as an `Err` so the caller can choose to skip the row or abort.
+///
+/// # Arguments
+/// * `reader` - Source of the CSV log
+/// * `format` - Layout of the log
+/// * `options` - Seat numbering options
+pub fn parse_stream<R: Read>(
+    reader: R,
+    format: Format,
+    options: &StreamOptions,
+) -> ActionStream<R> {
+    let (source, pending_error) = match format {
+        Format::PokerStars => (Source::Lines(BufReader::new(reader).lines()), None),
+        Format::Generic | Format::PokerNow => {
+            let mut csv_reader = ReaderBuilder::new().has_headers(true).from_reader(reader);
+            let (headers, pending_error) = match csv_reader.headers() {
+                Ok(headers) => (Some(headers.clone()), None),
+                Err(e) => (None, Some(ParseError::Csv(e.to_string()))),
+            };
+            (
+                Source::Csv {
+                    records: csv_reader.into_records(),
+                    headers,
+                },
+                pending_error,
+            )
+        }
+    };
+
+    ActionStream {
+        source,
+        pending_error,
+        format,
+        options: options.clone(),
+        resolver: SeatResolver::new(),
+        names: NameDisambiguator::new(),
+    }
+}
+
+impl<R: Read> ActionStream<R> {
+    /// The resolver holding every seat assigned so far.
+    pub fn resolver(&self) -> &SeatResolver {
+        &self.resolver
+    }
+
+    /// Parses one PokerNow row, returning `None` for rows that aren't actions.
+    fn parse_pokernow(&mut self, row: &PokerNowRow) -> Result<Option<Action>, ParseError> {
+        // Hand headers name the dealer; track the button for relative seats
+        if self.options.button_relative {
+            if let Some(dealer) = pokernow::parse_dealer(row) {
+                let button = self.resolver.get_or_assign_seat(&dealer);
+                self.resolver.set_button(button);
+            }
+        }
+
+        let Some(mut parsed) = pokernow::parse_row(row)? else {
+            return Ok(None);
+        };
+        if self.options.disambiguate_names {
+            self.names.disambiguate(&mut parsed);
+        }
+        // Street markers aren't players, don't give them a seat
+        let seat = if parsed.action_type == ActionType::StreetBoundary {
+            Seat::new(1)
+        } else {
+            self.resolver.get_or_assign_seat(&parsed.player_id)
+        };
+        Ok(Some(Action::new(
+            self.relative(seat),
+            parsed.action_type,
+            parsed.amount as i64,
+        )))
+    }
+
+    /// Parses one PokerStars line, returning `None` for lines that aren't actions.
+    ///
+    /// Seat lines put each player on the strand of their table seat, so
+    /// strands follow the players around the table even before they act.
+    fn parse_pokerstars(&mut self, line: &str) -> Result<Option<Action>, ParseError> {
+        if let Some((table_seat, player_id)) = pokerstars::parse_seat(line) {
+            self.resolver.assign_seat(&player_id, Seat::new(table_seat));
+            return Ok(None);
+        }
+
+        let Some(parsed) = pokerstars::parse_line(line)? else {
+            return Ok(None);
+        };
+        let seat = self.resolver.get_or_assign_seat(&parsed.player_id);
+        Ok(Some(Action::new(
+            self.relative(seat),
+            parsed.action_type,
+            parsed.amount as i64,
+        )))
+    }
+
+    /// Applies button-relative numbering if enabled.
+    fn relative(&self, seat: Seat) -> Seat {
+        if self.options.button_relative {
+            self.resolver
+                .to_button_relative(seat, self.options.total_seats)
+        } else {
+            seat
+        }
+    }
+}
+
+impl<R: Read> Iterator for ActionStream<R> {
+    type Item = Result<Action, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(err) = self.pending_error.take() {
+            return Some(Err(err));
+        }
+
+        loop {
+            match &mut self.source {
+                Source::Lines(lines) => {
+                    let line = match lines.next()? {
+                        Ok(line) => line,
+                        Err(e) => return Some(Err(ParseError::Io(e.to_string()))),
+                    };
+                    if let Some(action) = self.parse_pokerstars(&line).transpose() {
+                        return Some(action);
+                    }
+                    // Not an action (headers, board cards, ...), keep going
+                }
+                Source::Csv { records, headers } => {
+                    let record = match records.next()? {
+                        Ok(record) => record,
+                        Err(e) => return Some(Err(ParseError::Csv(e.to_string()))),
+                    };
+
+                    if self.format == Format::Generic {
+                        let sentinel = &self.options.reset_sentinel;
+                        let action = if self.options.seats_are_numeric {
+                            parse_record_with_numeric_seat(&record, sentinel)
+                        } else {
+                            parse_record_with_sentinel(&record, &mut self.resolver, sentinel)
+                        };
+                        let action = action.map(|action| {
+                            Action::new(
+                                self.relative(action.seat),
+                                action.action_type,
+                                action.amount,
+                            )
+                        });
+                        return Some(action);
+                    }
+
+                    let row: PokerNowRow = match record.deserialize(headers.as_ref()) {
+                        Ok(row) => row,
+                        Err(e) => return Some(Err(ParseError::Csv(e.to_string()))),
+                    };
+                    if let Some(action) = self.parse_pokernow(&row).transpose() {
+                        return Some(action);
+                    }
+                    // Not an action (filtered out), keep going
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_generic_from_bytes() {
+        let log: &[u8] = b"player_id,action,amount\nAlice,raise,100\nBob,call,100\nAlice,check,0\n";
+        let actions: Vec<Action> = parse_stream(log, Format::Generic, &StreamOptions::default())
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(actions.len(), 3);
+        assert_eq!(actions[1].seat, Seat::new(2));
+        assert_eq!(actions[1].action_type, ActionType::Call);
+        assert_eq!(actions[2].seat, Seat::new(1));
+    }
+
+    #[test]
+    fn test_numeric_seats_bypass_resolver() {
+        let log: &[u8] = b"player_id,action,amount\n1,raise,100\n4,call,100\n";
+        let options = StreamOptions {
+            seats_are_numeric: true,
+            ..StreamOptions::default()
+        };
+        let mut stream = parse_stream(log, Format::Generic, &options);
+        let actions: Vec<Action> = stream.by_ref().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(actions[0].seat, Seat::new(1));
+        assert_eq!(actions[1].seat, Seat::new(4));
+        assert_eq!(stream.resolver().player_count(), 0);
+    }
+
+    #[test]
+    fn test_parse_pokernow_from_bytes() {
+        let log: &[u8] = b"entry,at,order\n\
+            -- starting hand #1 --,2025-01-01T12:00:00,1\n\
+            Alice @ p1 raises to 200,2025-01-01T12:00:01,2\n\
+            The admin approved the player,2025-01-01T12:00:02,3\n\
+            Bob @ p2 calls 200,2025-01-01T12:00:03,4\n";
+        let mut stream = parse_stream(log, Format::PokerNow, &StreamOptions::default());
+        let actions: Vec<Action> = stream.by_ref().collect::<Result<_, _>>().unwrap();
+
+        let types: Vec<ActionType> = actions.iter().map(|a| a.action_type).collect();
+        assert_eq!(
+            types,
+            [ActionType::Reset, ActionType::Raise, ActionType::Call]
+        );
+        assert_eq!(stream.resolver().get_player_name(actions[2].seat), "Bob_p2");
+    }
+
+    #[test]
+    fn test_disambiguated_names_take_separate_seats() {
+        let log: &[u8] = b"entry,at,order\n\
+            Player bets 20,2025-01-01T12:00:01,1\n\
+            Player calls 20,2025-01-01T12:00:02,2\n";
+        let seats = |disambiguate_names| {
+            let options = StreamOptions {
+                disambiguate_names,
+                ..StreamOptions::default()
+            };
+            parse_stream(log, Format::PokerNow, &options)
+                .map(|action| action.unwrap().seat.value())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(seats(false), [1, 1]);
+        assert_eq!(seats(true), [1, 2]);
+    }
+
+    #[test]
+    fn test_street_markers_take_no_seat() {
+        let log: &[u8] = b"entry,at,order\n\
+            Alice @ p1 bets 100,2025-01-01T12:00:01,1\n\
+            \"Flop:  [Ah, Kd, 2c]\",2025-01-01T12:00:02,2\n\
+            Bob @ p2 checks,2025-01-01T12:00:03,3\n";
+        let mut stream = parse_stream(log, Format::PokerNow, &StreamOptions::default());
+        let actions: Vec<Action> = stream.by_ref().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(actions[1].action_type, ActionType::StreetBoundary);
+        assert_eq!(actions[2].seat, Seat::new(2));
+        assert_eq!(stream.resolver().player_count(), 2);
+    }
+
+    #[test]
+    fn test_parse_pokerstars_from_text() {
+        let log = include_str!("../../pokerstars_sample.txt");
+        let mut stream = parse_stream(
+            log.as_bytes(),
+            Format::PokerStars,
+            &StreamOptions::default(),
+        );
+        let actions: Vec<Action> = stream.by_ref().collect::<Result<_, _>>().unwrap();
+
+        assert_eq!(actions.len(), 12);
+        // The hand header opens the hand, so the blinds belong to it
+        assert_eq!(actions[0].action_type, ActionType::Reset);
+        // Strands are the table seats, not the order of first action
+        assert_eq!(stream.resolver().get_player_name(Seat::new(1)), "Player1");
+        assert_eq!(stream.resolver().get_player_name(Seat::new(3)), "Player2");
+        assert_eq!(stream.resolver().get_player_name(Seat::new(5)), "Player3");
+        assert_eq!(actions[1].seat, Seat::new(3));
+        assert_eq!(actions[2].seat, Seat::new(5));
+        assert_eq!(actions[3].seat, Seat::new(1));
+        assert_eq!(actions[3].amount, 60);
+    }
+
+    #[test]
+    fn test_parse_errors_are_yielded() {
+        let log: &[u8] = b"player_id,action,amount\nAlice,limp,100\nBob,call,100\n";
+        let results: Vec<Result<Action, ParseError>> =
+            parse_stream(log, Format::Generic, &StreamOptions::default()).collect();
+
+        assert_eq!(
+            results[0],
+            Err(ParseError::UnknownAction("limp".to_string()))
+        );
+        assert!(results[1].is_ok(), "Later rows are still parsed");
+    }
+
+    #[test]
+    fn test_malformed_amounts_are_yielded() {
+        let log: &[u8] = b"entry,at,order\nAlice @ p1 calls 1.2.3,2025-01-01T12:00:00,1\nBob @ p2 calls 50,2025-01-01T12:00:01,2\n";
+        let results: Vec<Result<Action, ParseError>> =
+            parse_stream(log, Format::PokerNow, &StreamOptions::default()).collect();
+        assert_eq!(results.len(), 2, "The bad row is reported, not skipped");
+        assert!(
+            matches!(&results[0], Err(ParseError::InvalidAmount { raw, .. }) if raw == "1.2.3")
+        );
+        assert!(results[1].is_ok());
+
+        let log: &[u8] = b"Player1: bets 1.2.3\nPlayer2: folds\n";
+        let results: Vec<Result<Action, ParseError>> =
+            parse_stream(log, Format::PokerStars, &StreamOptions::default()).collect();
+        assert!(matches!(&results[0], Err(ParseError::InvalidAmount { .. })));
+        assert!(results[1].is_ok());
+    }
+}